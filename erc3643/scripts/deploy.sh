@@ -80,7 +80,8 @@ echo -e "${GREEN}ERC3643Token deployed at: $TOKEN_ADDR${NC}"
 
 # Set up relationships between contracts
 echo -e "${YELLOW}Setting up contract relationships...${NC}"
-sncast --profile $NETWORK invoke --contract-address $IDENTITY_STORAGE_ADDR --function set_registry --calldata $IDENTITY_REGISTRY_ADDR
+sncast --profile $NETWORK invoke --contract-address $IDENTITY_STORAGE_ADDR --function bind_identity_registry --calldata $IDENTITY_REGISTRY_ADDR
+sncast --profile $NETWORK invoke --contract-address $COMPLIANCE_ADDR --function bind_token --calldata $TOKEN_ADDR
 
 echo -e "${GREEN}Deployment completed successfully!${NC}"
 echo -e "Token address: $TOKEN_ADDR"
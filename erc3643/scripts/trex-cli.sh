@@ -0,0 +1,187 @@
+#!/bin/bash
+set -e
+
+# Color codes
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[0;33m'
+NC='\033[0m' # No Color
+
+# Issuer operations on a deployed suite through sncast.
+# Usage: ./scripts/trex-cli.sh <command> [arguments...]
+# The network is read from TREX_NETWORK (testnet or mainnet, default testnet).
+
+NETWORK="${TREX_NETWORK:-testnet}"
+
+if [ "$NETWORK" != "testnet" ] && [ "$NETWORK" != "mainnet" ]; then
+    echo -e "${RED}Error: TREX_NETWORK must be either 'testnet' or 'mainnet'${NC}"
+    exit 1
+fi
+
+usage() {
+    cat <<EOF
+Usage: $0 <command> [arguments...]
+
+Suite deployment
+  deploy-suite <factory> <salt> <name> <symbol> <owner> [claim_topic,...]
+
+Identities and claims
+  register-identity <identity_registry> <wallet> <identity> <country>
+  delete-identity <identity_registry> <wallet>
+  add-claim <identity_registry> <identity> <claim_topic> <data>
+  remove-claim <identity_registry> <identity> <claim_topic> <issuer>
+  add-claim-topic <claim_topics_registry> <claim_topic>
+  add-trusted-issuer <trusted_issuers_registry> <issuer> <claim_topic,...>
+
+Agents
+  add-agent <token> <agent>
+  remove-agent <token> <agent>
+
+Pause and freeze
+  pause <token>
+  unpause <token>
+  freeze <token> <wallet> [reason]
+  unfreeze <token> <wallet>
+  freeze-partial <token> <wallet> <amount> [reason]
+  unfreeze-partial <token> <wallet> <amount>
+
+Supply and transfers
+  mint <token> <to> <amount>
+  burn <token> <from> <amount>
+  forced-transfer <token> <from> <to> <amount>
+
+Amounts are token units below 2^128. Names, symbols and topics are short strings.
+EOF
+    exit 1
+}
+
+# Hex encoding of a Cairo short string
+short_string() {
+    echo "0x$(echo -n "$1" | xxd -p)"
+}
+
+# u256 calldata (low, high) for amounts that fit in the low part
+amount() {
+    echo "$1 0"
+}
+
+# Array calldata (length, items) from a comma separated list
+list() {
+    if [ -z "$1" ]; then
+        echo "0"
+        return
+    fi
+    local items
+    IFS=',' read -ra items <<< "$1"
+    local calldata="${#items[@]}"
+    for item in "${items[@]}"; do
+        calldata="$calldata $(short_string "$item")"
+    done
+    echo "$calldata"
+}
+
+invoke() {
+    local contract=$1
+    local function=$2
+    shift 2
+    echo -e "${YELLOW}Calling $function on $contract...${NC}"
+    sncast --profile $NETWORK invoke --contract-address $contract --function $function --calldata "$@"
+    echo -e "${GREEN}$function done${NC}"
+}
+
+require_args() {
+    if [ "$1" -lt "$2" ]; then
+        usage
+    fi
+}
+
+COMMAND=$1
+[ -z "$COMMAND" ] && usage
+shift
+
+case "$COMMAND" in
+    deploy-suite)
+        require_args $# 5
+        # Suites deployed this way have no trusted issuers; add them with add-trusted-issuer
+        invoke $1 deploy_suite $2 $(short_string "$3") $(short_string "$4") $5 $(list "$6") 0 0
+        ;;
+    register-identity)
+        require_args $# 4
+        invoke $1 register_identity $2 $3 $4
+        ;;
+    delete-identity)
+        require_args $# 2
+        invoke $1 delete_identity $2
+        ;;
+    add-claim)
+        require_args $# 4
+        invoke $1 add_claim $2 $(short_string "$3") $4
+        ;;
+    remove-claim)
+        require_args $# 4
+        invoke $1 remove_claim $2 $(short_string "$3") $4
+        ;;
+    add-claim-topic)
+        require_args $# 2
+        invoke $1 add_claim_topic $(short_string "$2")
+        ;;
+    add-trusted-issuer)
+        require_args $# 3
+        invoke $1 add_trusted_issuer $2 $(list "$3")
+        ;;
+    add-agent)
+        require_args $# 2
+        invoke $1 add_agent $2
+        ;;
+    remove-agent)
+        require_args $# 2
+        invoke $1 remove_agent $2
+        ;;
+    pause)
+        require_args $# 1
+        sncast --profile $NETWORK invoke --contract-address $1 --function pause
+        ;;
+    unpause)
+        require_args $# 1
+        sncast --profile $NETWORK invoke --contract-address $1 --function unpause
+        ;;
+    freeze)
+        require_args $# 2
+        if [ -n "$3" ]; then
+            invoke $1 set_address_frozen_with_reason $2 1 $(short_string "$3")
+        else
+            invoke $1 freeze_address $2
+        fi
+        ;;
+    unfreeze)
+        require_args $# 2
+        invoke $1 unfreeze_address $2
+        ;;
+    freeze-partial)
+        require_args $# 3
+        if [ -n "$4" ]; then
+            invoke $1 freeze_partial_tokens_with_reason $2 $(amount $3) $(short_string "$4")
+        else
+            invoke $1 freeze_partial_tokens $2 $(amount $3)
+        fi
+        ;;
+    unfreeze-partial)
+        require_args $# 3
+        invoke $1 unfreeze_partial_tokens $2 $(amount $3)
+        ;;
+    mint)
+        require_args $# 3
+        invoke $1 mint $2 $(amount $3)
+        ;;
+    burn)
+        require_args $# 3
+        invoke $1 forced_burn $2 $(amount $3)
+        ;;
+    forced-transfer)
+        require_args $# 4
+        invoke $1 forced_transfer $2 $3 $(amount $4)
+        ;;
+    *)
+        usage
+        ;;
+esac
@@ -0,0 +1,152 @@
+//! Bridge
+//!
+//! Connects a security token issued on Ethereum to its Starknet leg through native
+//! L1 <-> L2 messaging. Tokens locked in the L1 bridge contract configured by the token
+//! owner are released here by an L1 handler that mints them to the recipient, which must
+//! be a verified address of the token; if it is not, the message stays pending on L1 and
+//! can be cancelled there. Holders bridge back by locking tokens here, which burns them
+//! and sends a message for the L1 bridge to release them, once the compliance modules
+//! have allowed the tokens to leave the holder's wallet.
+//!
+//! The bridge must be an agent of the token with the mint and burn permissions.
+
+use starknet::{
+    ContractAddress,
+    EthAddress,
+    get_caller_address,
+    syscalls::send_message_to_l1_syscall,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait, DEFAULT_PARTITION};
+use crate::compliance::{IComplianceDispatcher, IComplianceDispatcherTrait};
+
+// Bridge Interface
+#[starknet::interface]
+pub trait IBridge<TContractState> {
+    fn set_l1_bridge(ref self: TContractState, l1_bridge: EthAddress) -> bool;
+    fn lock_for_bridge(ref self: TContractState, l1_recipient: EthAddress, amount: u256) -> bool;
+    fn l1_bridge(self: @TContractState) -> EthAddress;
+    fn token(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod Bridge {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        L1BridgeSet: L1BridgeSet,
+        LockedForBridge: LockedForBridge,
+        ReleasedFromBridge: ReleasedFromBridge,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct L1BridgeSet {
+        l1_bridge: EthAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct LockedForBridge {
+        #[key]
+        holder: ContractAddress,
+        l1_recipient: EthAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ReleasedFromBridge {
+        #[key]
+        recipient: ContractAddress,
+        amount: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Bridged token and the L1 contract allowed to send release messages
+        token_map: Map<felt252, ContractAddress>,  // Using 'token' as key
+        l1_bridge_map: Map<felt252, EthAddress>,  // Using 'l1_bridge' as key
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, token: ContractAddress) {
+        self.token_map.write('token', token);
+    }
+    
+    #[abi(embed_v0)]
+    impl BridgeImpl of super::IBridge<ContractState> {
+        fn set_l1_bridge(ref self: ContractState, l1_bridge: EthAddress) -> bool {
+            // Only the token owner configures the bridge
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.token_map.read('token') };
+            assert(get_caller_address() == token_dispatcher.owner(), 'Only token owner allowed');
+            let l1_bridge_address: felt252 = l1_bridge.into();
+            assert(l1_bridge_address != 0, 'Invalid L1 bridge');
+            
+            self.l1_bridge_map.write('l1_bridge', l1_bridge);
+            
+            self.emit(L1BridgeSet { l1_bridge });
+            true
+        }
+        
+        fn lock_for_bridge(ref self: ContractState, l1_recipient: EthAddress, amount: u256) -> bool {
+            let l1_bridge: felt252 = self.l1_bridge_map.read('l1_bridge').into();
+            let l1_recipient_address: felt252 = l1_recipient.into();
+            assert(l1_bridge != 0, 'L1 bridge not set');
+            assert(l1_recipient_address != 0, 'Invalid L1 recipient');
+            assert(amount > 0, 'Invalid amount');
+            
+            // Forced burns skip the checks a holder's own transfer would go through
+            let holder = get_caller_address();
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.token_map.read('token') };
+            assert(!token_dispatcher.is_frozen(holder), 'Holder frozen');
+            let available = token_dispatcher.balance_of_by_partition(holder, DEFAULT_PARTITION);
+            assert(available >= token_dispatcher.get_frozen_tokens(holder) + amount, 'Insufficient unfrozen balance');
+            
+            // Sender-side rules (lockups, vesting, holding periods, blackouts...) apply to the
+            // tokens leaving for L1, the holder standing in as recipient so no new holder counts
+            let compliance = IComplianceDispatcher { contract_address: token_dispatcher.compliance() };
+            assert(compliance.check_compliance(holder, holder, amount), 'Transfer not compliant');
+            token_dispatcher.forced_burn(holder, amount);
+            
+            // Payload read by the L1 bridge: recipient, amount low, amount high
+            let payload = array![l1_recipient_address, amount.low.into(), amount.high.into()];
+            send_message_to_l1_syscall(l1_bridge, payload.span()).unwrap();
+            
+            self.emit(LockedForBridge { holder, l1_recipient, amount });
+            true
+        }
+        
+        fn l1_bridge(self: @ContractState) -> EthAddress {
+            self.l1_bridge_map.read('l1_bridge')
+        }
+        
+        fn token(self: @ContractState) -> ContractAddress {
+            self.token_map.read('token')
+        }
+    }
+    
+    #[l1_handler]
+    fn release_from_bridge(
+        ref self: ContractState,
+        from_address: felt252,
+        recipient: ContractAddress,
+        amount_low: u128,
+        amount_high: u128
+    ) {
+        // Only messages from the configured L1 bridge are honoured
+        let l1_bridge: felt252 = self.l1_bridge_map.read('l1_bridge').into();
+        assert(l1_bridge != 0 && from_address == l1_bridge, 'Unknown L1 sender');
+        
+        // Minting also runs the compliance modules on the recipient
+        let amount = u256 { low: amount_low, high: amount_high };
+        let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.token_map.read('token') };
+        assert(token_dispatcher.is_verified_address(recipient), 'Recipient not verified');
+        token_dispatcher.mint(recipient, amount);
+        
+        self.emit(ReleasedFromBridge { recipient, amount });
+    }
+}
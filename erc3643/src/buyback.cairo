@@ -0,0 +1,227 @@
+//! Buyback
+//!
+//! Issuer buyback programs. A token agent opens a program by funding a pool of an ERC20
+//! payment token and publishing a price per whole security token. Holders sell into the
+//! program until the pool runs out: the tokens are either moved to the issuer's treasury
+//! through `transfer_from`, with the token's identity and compliance checks, or burnt when
+//! the program has no treasury, in which case the holder must still be verified and the
+//! tokens unfrozen. The agent can reprice the program and closes it to take back what is
+//! left of the pool.
+//!
+//! Programs burning tokens need this contract to be an agent of the token with the burn
+//! permission.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait, DEFAULT_PARTITION};
+use crate::payments;
+
+// Security tokens use 18 decimals, prices are given per whole token
+const TOKEN_UNIT: u256 = 1_000_000_000_000_000_000;
+
+// Buyback Interface
+#[starknet::interface]
+pub trait IBuyback<TContractState> {
+    fn open_buyback(
+        ref self: TContractState,
+        token: ContractAddress,
+        payment_token: ContractAddress,
+        price: u256,
+        funding: u256,
+        treasury: ContractAddress
+    ) -> u64;
+    fn set_buyback_price(ref self: TContractState, buyback_id: u64, price: u256) -> bool;
+    fn close_buyback(ref self: TContractState, buyback_id: u64) -> u256;
+    fn sell_to_buyback(ref self: TContractState, buyback_id: u64, amount: u256) -> u256;
+    fn get_buyback(self: @TContractState, buyback_id: u64) -> (ContractAddress, ContractAddress, u256, u256, ContractAddress, bool);
+    fn get_buyback_count(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod Buyback {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        BuybackOpened: BuybackOpened,
+        BuybackPriceSet: BuybackPriceSet,
+        BuybackClosed: BuybackClosed,
+        TokensSold: TokensSold,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BuybackOpened {
+        #[key]
+        buyback_id: u64,
+        token: ContractAddress,
+        payment_token: ContractAddress,
+        price: u256,
+        funding: u256,
+        treasury: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BuybackPriceSet {
+        #[key]
+        buyback_id: u64,
+        price: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BuybackClosed {
+        #[key]
+        buyback_id: u64,
+        refunded: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TokensSold {
+        #[key]
+        buyback_id: u64,
+        #[key]
+        seller: ContractAddress,
+        amount: u256,
+        payout: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Programs, by id starting at 1
+        buyback_count_map: Map<felt252, u64>,  // Using 'buyback_count' as key
+        buyback_tokens: Map<u64, ContractAddress>,
+        buyback_payment_tokens: Map<u64, ContractAddress>,
+        buyback_prices: Map<u64, u256>,
+        buyback_pools: Map<u64, u256>,  // Payment tokens left to pay out
+        buyback_treasuries: Map<u64, ContractAddress>,  // Zero address when bought tokens are burnt
+        buyback_open: Map<u64, bool>,
+    }
+    
+    #[abi(embed_v0)]
+    impl BuybackImpl of super::IBuyback<ContractState> {
+        fn open_buyback(
+            ref self: ContractState,
+            token: ContractAddress,
+            payment_token: ContractAddress,
+            price: u256,
+            funding: u256,
+            treasury: ContractAddress
+        ) -> u64 {
+            // Only token agents run buybacks
+            let caller = get_caller_address();
+            self._assert_token_agent(token);
+            assert(price > 0, 'Invalid price');
+            assert(funding > 0, 'Invalid funding');
+            
+            // Pull the pool into this contract
+            payments::collect(payment_token, caller, funding);
+            
+            let buyback_id = self.buyback_count_map.read('buyback_count') + 1;
+            self.buyback_count_map.write('buyback_count', buyback_id);
+            
+            self.buyback_tokens.write(buyback_id, token);
+            self.buyback_payment_tokens.write(buyback_id, payment_token);
+            self.buyback_prices.write(buyback_id, price);
+            self.buyback_pools.write(buyback_id, funding);
+            self.buyback_treasuries.write(buyback_id, treasury);
+            self.buyback_open.write(buyback_id, true);
+            
+            self.emit(BuybackOpened { buyback_id, token, payment_token, price, funding, treasury });
+            buyback_id
+        }
+        
+        fn set_buyback_price(ref self: ContractState, buyback_id: u64, price: u256) -> bool {
+            assert(self.buyback_open.read(buyback_id), 'Buyback not open');
+            self._assert_token_agent(self.buyback_tokens.read(buyback_id));
+            assert(price > 0, 'Invalid price');
+            
+            self.buyback_prices.write(buyback_id, price);
+            
+            self.emit(BuybackPriceSet { buyback_id, price });
+            true
+        }
+        
+        fn close_buyback(ref self: ContractState, buyback_id: u64) -> u256 {
+            assert(self.buyback_open.read(buyback_id), 'Buyback not open');
+            self._assert_token_agent(self.buyback_tokens.read(buyback_id));
+            
+            // The rest of the pool goes back to the closing agent
+            let refunded = self.buyback_pools.read(buyback_id);
+            self.buyback_open.write(buyback_id, false);
+            self.buyback_pools.write(buyback_id, 0);
+            
+            if refunded > 0 {
+                let payment_token = self.buyback_payment_tokens.read(buyback_id);
+                payments::pay(payment_token, get_caller_address(), refunded);
+            }
+            
+            self.emit(BuybackClosed { buyback_id, refunded });
+            refunded
+        }
+        
+        fn sell_to_buyback(ref self: ContractState, buyback_id: u64, amount: u256) -> u256 {
+            assert(self.buyback_open.read(buyback_id), 'Buyback not open');
+            assert(amount > 0, 'Invalid amount');
+            
+            let payout = amount * self.buyback_prices.read(buyback_id) / TOKEN_UNIT;
+            let pool = self.buyback_pools.read(buyback_id);
+            assert(payout > 0, 'Payout too small');
+            assert(payout <= pool, 'Buyback pool exhausted');
+            self.buyback_pools.write(buyback_id, pool - payout);
+            
+            let seller = get_caller_address();
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.buyback_tokens.read(buyback_id) };
+            let treasury = self.buyback_treasuries.read(buyback_id);
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if treasury != zero_address {
+                // The seller allowed this contract to move the tokens
+                let delivered = token_dispatcher.transfer_from(seller, treasury, amount);
+                assert(delivered, 'Delivery failed');
+            } else {
+                // Forced burns skip the checks a holder's own transfer would go through
+                assert(token_dispatcher.is_verified_address(seller), 'Seller not verified');
+                assert(!token_dispatcher.is_frozen(seller), 'Seller frozen');
+                let available = token_dispatcher.balance_of_by_partition(seller, DEFAULT_PARTITION);
+                assert(available >= token_dispatcher.get_frozen_tokens(seller) + amount, 'Insufficient unfrozen balance');
+                token_dispatcher.forced_burn(seller, amount);
+            }
+            
+            let payment_token = self.buyback_payment_tokens.read(buyback_id);
+            payments::pay(payment_token, seller, payout);
+            
+            self.emit(TokensSold { buyback_id, seller, amount, payout });
+            payout
+        }
+        
+        fn get_buyback(self: @ContractState, buyback_id: u64) -> (ContractAddress, ContractAddress, u256, u256, ContractAddress, bool) {
+            (
+                self.buyback_tokens.read(buyback_id),
+                self.buyback_payment_tokens.read(buyback_id),
+                self.buyback_prices.read(buyback_id),
+                self.buyback_pools.read(buyback_id),
+                self.buyback_treasuries.read(buyback_id),
+                self.buyback_open.read(buyback_id)
+            )
+        }
+        
+        fn get_buyback_count(self: @ContractState) -> u64 {
+            self.buyback_count_map.read('buyback_count')
+        }
+    }
+    
+    // Internal functions implementation
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_token_agent(self: @ContractState, token: ContractAddress) {
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.is_compliance_agent(get_caller_address()), 'Only agents allowed');
+        }
+    }
+}
@@ -1,9 +1,11 @@
 // Import OpenZeppelin's components
 use openzeppelin::access::ownable::OwnableComponent;
+use openzeppelin::upgrades::UpgradeableComponent;
 // The following import is not needed as we use OwnableComponent directly
 // use openzeppelin::access::ownable::OwnableComponent::InternalTrait as OwnableInternalTrait;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     // get_caller_address not needed as we use OwnableComponent::assert_only_owner
     // get_caller_address,
     storage::StorageMapReadAccess,
@@ -18,8 +20,14 @@ pub trait IClaimTopicsRegistry<TContractState> {
     fn add_claim_topic(ref self: TContractState, claim_topic: felt252) -> bool;
     fn remove_claim_topic(ref self: TContractState, claim_topic: felt252) -> bool;
     fn get_claim_topics(self: @TContractState) -> Array<felt252>;
+    fn get_claim_topics_epoch(self: @TContractState) -> u64;
     fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
     fn owner(self: @TContractState) -> ContractAddress;
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 #[starknet::contract]
@@ -28,11 +36,13 @@ pub mod ClaimTopicsRegistry {
     
     // Component declarations
     component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
     
     // Implement component interfaces
     // We're removing abi(embed_v0) to avoid duplicate entry points in testing
     impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
     impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Events
     #[event]
@@ -40,8 +50,11 @@ pub mod ClaimTopicsRegistry {
     enum Event {
         #[flat]
         OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
         ClaimTopicAdded: ClaimTopicAdded,
         ClaimTopicRemoved: ClaimTopicRemoved,
+        StorageMigrated: StorageMigrated,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -54,18 +67,35 @@ pub mod ClaimTopicsRegistry {
         claim_topic: felt252,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
         // Component storage
         #[substorage(v0)]
         ownable: OwnableComponent::Storage,
         
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
         // Claim topics storage using maps to avoid direct storage access issues
         topic_count_map: Map<felt252, u32>,  // Using 'count' as key
         topics: Map<u32, felt252>,  // Index to topic mapping
         topic_indices: Map<felt252, u32>,  // Topic to index mapping
+        
+        // Bumped on every topic change, so registries know when cached verifications are stale
+        epoch_map: Map<felt252, u64>,  // Using 'epoch' as key
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: Map<felt252, u8>,  // Using 'version' as key
     }
     
+    const STORAGE_VERSION: u8 = 1;
+    
     #[constructor]
     fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
         // Initialize Ownable component
@@ -73,6 +103,9 @@ pub mod ClaimTopicsRegistry {
         
         // Initialize counter using a Map entry for simplicity
         self.topic_count_map.write('count', 0);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
@@ -97,6 +130,7 @@ pub mod ClaimTopicsRegistry {
             
             // Increment count
             self.set_topic_count(topic_count + 1);
+            self._bump_epoch();
             
             // Emit event
             self.emit(ClaimTopicAdded { claim_topic });
@@ -133,6 +167,7 @@ pub mod ClaimTopicsRegistry {
             
             // Decrement count
             self.set_topic_count(topic_count - 1);
+            self._bump_epoch();
             
             // Emit event
             self.emit(ClaimTopicRemoved { claim_topic });
@@ -160,6 +195,10 @@ pub mod ClaimTopicsRegistry {
             topics
         }
         
+        fn get_claim_topics_epoch(self: @ContractState) -> u64 {
+            self.epoch_map.read('epoch')
+        }
+        
         fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
             self.ownable.transfer_ownership(new_owner);
             true
@@ -168,6 +207,32 @@ pub mod ClaimTopicsRegistry {
         fn owner(self: @ContractState) -> ContractAddress {
             self.ownable.owner()
         }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the implementation
+            self.ownable.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.ownable.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, 'Storage already migrated');
+            
+            // No layout changes yet between version 0 and 1, only record the version
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     // Internal helper functions
@@ -181,5 +246,9 @@ pub mod ClaimTopicsRegistry {
         fn set_topic_count(ref self: ContractState, value: u32) {
             self.topic_count_map.write('count', value);
         }
+        
+        fn _bump_epoch(ref self: ContractState) {
+            self.epoch_map.write('epoch', self.epoch_map.read('epoch') + 1);
+        }
     }
 }
\ No newline at end of file
@@ -1,14 +1,23 @@
 // Import OpenZeppelin's components
 use openzeppelin::access::ownable::OwnableComponent;
 use openzeppelin::access::ownable::OwnableComponent::InternalTrait as OwnableInternalTrait;
+use openzeppelin::upgrades::UpgradeableComponent;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     get_caller_address,
+    get_contract_address,
+    get_block_timestamp,
+    syscalls::get_class_hash_at_syscall,
     storage::StorageMapReadAccess,
     storage::StorageMapWriteAccess,
     storage::Map,
 };
 use core::array::ArrayTrait;
+use core::poseidon::poseidon_hash_span;
+
+// Compliance module interface
+use crate::interfaces::icompliance_module::{IComplianceModuleDispatcher, IComplianceModuleDispatcherTrait};
 
 // Compliance Interface
 #[starknet::interface]
@@ -19,24 +28,53 @@ pub trait ICompliance<TContractState> {
         to: ContractAddress, 
         amount: u256
     ) -> bool;
-    fn add_rule(ref self: TContractState, rule: ContractAddress) -> bool;
+    fn check_forced_transfer(
+        self: @TContractState, 
+        from: ContractAddress, 
+        to: ContractAddress, 
+        amount: u256
+    ) -> bool;
+    fn add_rule(ref self: TContractState, rule: ContractAddress, forced_transfer_exempt: bool) -> bool;
     fn remove_rule(ref self: TContractState, rule: ContractAddress) -> bool;
     fn add_compliance_check(ref self: TContractState, claim_topic: felt252) -> bool;
     fn remove_compliance_check(ref self: TContractState, claim_topic: felt252) -> bool;
+    fn set_zero_amount_transfers_allowed(ref self: TContractState, allowed: bool) -> bool;
+    fn are_zero_amount_transfers_allowed(self: @TContractState) -> bool;
     fn get_rules(self: @TContractState) -> Array<ContractAddress>;
-    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
-    fn owner(self: @TContractState) -> ContractAddress;
-}
-
-// Compliance Rule Interface
-#[starknet::interface]
-trait IComplianceRule<TContractState> {
-    fn check_compliance(
+    fn set_rule_priority(ref self: TContractState, rule: ContractAddress, priority: u8) -> bool;
+    fn get_rule_priority(self: @TContractState, rule: ContractAddress) -> u8;
+    fn is_forced_transfer_exempt(self: @TContractState, rule: ContractAddress) -> bool;
+    fn find_failing_rule(
         self: @TContractState, 
         from: ContractAddress, 
         to: ContractAddress, 
-        amount: u256
-    ) -> bool;
+        amount: u256,
+        forced: bool
+    ) -> (bool, u32, ContractAddress);
+    
+    // Token binding and module state hooks
+    fn bind_token(ref self: TContractState, token: ContractAddress) -> bool;
+    fn unbind_token(ref self: TContractState, token: ContractAddress) -> bool;
+    fn is_token_bound(self: @TContractState, token: ContractAddress) -> bool;
+    fn get_bound_tokens(self: @TContractState) -> Array<ContractAddress>;
+    fn get_token(self: @TContractState) -> ContractAddress;
+    fn transferred(ref self: TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool;
+    fn created(ref self: TContractState, to: ContractAddress, amount: u256) -> bool;
+    fn destroyed(ref self: TContractState, from: ContractAddress, amount: u256) -> bool;
+    
+    // Audit trail of the configuration
+    fn snapshot_configuration(ref self: TContractState) -> felt252;
+    fn get_configuration_hash(self: @TContractState) -> felt252;
+    fn get_snapshot_count(self: @TContractState) -> u32;
+    fn get_snapshot(self: @TContractState, index: u32) -> (u64, felt252);
+    
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 #[starknet::contract]
@@ -45,11 +83,13 @@ pub mod Compliance {
     
     // Component declarations
     component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
     
     // Implement component interfaces
     // We're removing abi(embed_v0) to avoid duplicate entry points in testing
     impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
     impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Events
     #[event]
@@ -57,15 +97,24 @@ pub mod Compliance {
     enum Event {
         #[flat]
         OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
         RuleAdded: RuleAdded,
         RuleRemoved: RuleRemoved,
+        RulePriorityUpdated: RulePriorityUpdated,
         ComplianceCheckAdded: ComplianceCheckAdded,
         ComplianceCheckRemoved: ComplianceCheckRemoved,
+        TokenBound: TokenBound,
+        TokenUnbound: TokenUnbound,
+        ConfigurationSnapshot: ConfigurationSnapshot,
+        ZeroAmountTransferPolicySet: ZeroAmountTransferPolicySet,
+        StorageMigrated: StorageMigrated,
     }
     
     #[derive(Drop, starknet::Event)]
     struct RuleAdded {
         rule: ContractAddress,
+        forced_transfer_exempt: bool,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -73,6 +122,12 @@ pub mod Compliance {
         rule: ContractAddress,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct RulePriorityUpdated {
+        rule: ContractAddress,
+        priority: u8,
+    }
+    
     #[derive(Drop, starknet::Event)]
     struct ComplianceCheckAdded {
         claim_topic: felt252,
@@ -83,23 +138,79 @@ pub mod Compliance {
         claim_topic: felt252,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct TokenBound {
+        token: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TokenUnbound {
+        token: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ConfigurationSnapshot {
+        #[key]
+        index: u32,
+        timestamp: u64,
+        configuration_hash: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ZeroAmountTransferPolicySet {
+        allowed: bool,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
         // Component storage
         #[substorage(v0)]
         ownable: OwnableComponent::Storage,
         
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
         // Compliance rules storage using maps
         rule_count_map: Map<felt252, u32>,  // Using 'rule_count' as key
         rules: Map<u32, ContractAddress>,
         rule_indices: Map<ContractAddress, u32>,
+        rule_priorities: Map<ContractAddress, u8>,  // Higher runs first, rules are kept sorted
+        forced_transfer_exempt: Map<ContractAddress, bool>,  // Rules skipped by `check_forced_transfer`
         
         // Compliance check topics
         check_count_map: Map<felt252, u32>,  // Using 'check_count' as key
         checks: Map<u32, felt252>,
         check_indices: Map<felt252, u32>,
+        
+        // Tokens governed by this compliance, their balance changes are forwarded to the modules
+        token_count_map: Map<felt252, u32>,  // Using 'token_count' as key
+        tokens: Map<u32, ContractAddress>,
+        token_indices: Map<ContractAddress, u32>,  // 1-based, 0 if not bound
+        
+        // Append-only log of configuration snapshots
+        snapshot_count_map: Map<felt252, u32>,  // Using 'snapshot_count' as key
+        snapshot_timestamps: Map<u32, u64>,
+        snapshot_hashes: Map<u32, felt252>,
+        
+        // Zero-amount transfers are allowed unless blocked by the owner
+        zero_amount_blocked_map: Map<felt252, bool>,  // Using 'zero_amount_blocked' as key
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: Map<felt252, u8>,  // Using 'version' as key
     }
     
+    // Version 2 records forced transfer exemptions per rule, version 1 exempted blackout modules by name
+    const STORAGE_VERSION: u8 = 2;
+    
+    // Upper bound on registered modules, keeps a transfer's module loop affordable
+    const MAX_RULES: u32 = 25;
+    
     #[constructor]
     fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
         // Initialize Ownable component
@@ -108,6 +219,9 @@ pub mod Compliance {
         // Initialize counters using maps
         self.rule_count_map.write('rule_count', 0);
         self.check_count_map.write('check_count', 0);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
@@ -118,38 +232,22 @@ pub mod Compliance {
             to: ContractAddress, 
             amount: u256
         ) -> bool {
-            // Get the number of rules
-            let rule_count = self.get_rule_count();
-            
-            // If no rules, transfer is compliant
-            if rule_count == 0 {
-                return true;
-            }
-            
-            // Check each rule
-            let mut i: u32 = 0;
-            loop {
-                if i >= rule_count {
-                    break;
-                }
-                
-                // Get rule and check compliance
-                let rule = self.rules.read(i);
-                let is_rule_valid = super::IComplianceRuleDispatcher { contract_address: rule }
-                    .check_compliance(from, to, amount);
-                    
-                if !is_rule_valid {
-                    return false;
-                }
-                
-                i += 1;
-            };
-            
-            // All rules passed, transfer is compliant
-            true
+            let (found, _, _) = self._find_failing_rule(from, to, amount, false);
+            !found
+        }
+        
+        fn check_forced_transfer(
+            self: @ContractState, 
+            from: ContractAddress, 
+            to: ContractAddress, 
+            amount: u256
+        ) -> bool {
+            // Rules the owner exempted from forced transfers are skipped, every other rule applies
+            let (found, _, _) = self._find_failing_rule(from, to, amount, true);
+            !found
         }
         
-        fn add_rule(ref self: ContractState, rule: ContractAddress) -> bool {
+        fn add_rule(ref self: ContractState, rule: ContractAddress, forced_transfer_exempt: bool) -> bool {
             // Only owner can add rules
             assert(get_caller_address() == self.ownable.owner(), 'Caller is not the owner');
             
@@ -162,16 +260,19 @@ pub mod Compliance {
             
             // Get current rule count
             let rule_count = self.get_rule_count();
+            assert(rule_count < MAX_RULES, 'Too many rules');
             
-            // Add rule
-            self.rules.write(rule_count, rule);
-            self.rule_indices.write(rule, rule_count + 1); // +1 to differentiate from 0 (not found)
+            // A module only accepts actions from the compliance it was deployed for
+            let bound_compliance = IComplianceModuleDispatcher { contract_address: rule }.get_compliance();
+            assert(bound_compliance == get_contract_address(), 'Module bound elsewhere');
             
-            // Increment count
-            self.set_rule_count(rule_count + 1);
+            // New rules have the lowest priority and run after the existing ones
+            self.rule_priorities.write(rule, 0);
+            self.forced_transfer_exempt.write(rule, forced_transfer_exempt);
+            self._insert_rule(rule);
             
             // Emit event
-            self.emit(RuleAdded { rule });
+            self.emit(RuleAdded { rule, forced_transfer_exempt });
             
             true
         }
@@ -187,24 +288,10 @@ pub mod Compliance {
                 return true;
             }
             
-            // Convert index from 1-based to 0-based
-            let index = existing_index - 1;
-            
-            // Get current rule count
-            let rule_count = self.get_rule_count();
-            
-            // If not the last rule, move the last rule to this index
-            if index < rule_count - 1 {
-                let last_rule = self.rules.read(rule_count - 1);
-                self.rules.write(index, last_rule);
-                self.rule_indices.write(last_rule, index + 1); // +1 to differentiate from 0 (not found)
-            }
-            
-            // Remove rule
-            self.rule_indices.write(rule, 0);
-            
-            // Decrement count
-            self.set_rule_count(rule_count - 1);
+            // Remove rule, keeping the order of the others
+            self._remove_rule_at(existing_index - 1);
+            self.rule_priorities.write(rule, 0);
+            self.forced_transfer_exempt.write(rule, false);
             
             // Emit event
             self.emit(RuleRemoved { rule });
@@ -275,6 +362,19 @@ pub mod Compliance {
             true
         }
         
+        fn set_zero_amount_transfers_allowed(ref self: ContractState, allowed: bool) -> bool {
+            // Only owner decides whether zero-amount transfers (e.g. venue pings) go through
+            self.ownable.assert_only_owner();
+            
+            self.zero_amount_blocked_map.write('zero_amount_blocked', !allowed);
+            self.emit(ZeroAmountTransferPolicySet { allowed });
+            true
+        }
+        
+        fn are_zero_amount_transfers_allowed(self: @ContractState) -> bool {
+            !self.zero_amount_blocked_map.read('zero_amount_blocked')
+        }
+        
         fn get_rules(self: @ContractState) -> Array<ContractAddress> {
             let mut rules = ArrayTrait::<ContractAddress>::new();
             let rule_count = self.get_rule_count();
@@ -295,6 +395,202 @@ pub mod Compliance {
             rules
         }
         
+        fn set_rule_priority(ref self: ContractState, rule: ContractAddress, priority: u8) -> bool {
+            // Only owner can order rules
+            self.ownable.assert_only_owner();
+            
+            let existing_index = self.rule_indices.read(rule);
+            assert(existing_index != 0, 'Rule not found');
+            
+            // Re-insert the rule at its new position
+            self._remove_rule_at(existing_index - 1);
+            self.rule_priorities.write(rule, priority);
+            self._insert_rule(rule);
+            
+            self.emit(RulePriorityUpdated { rule, priority });
+            true
+        }
+        
+        fn get_rule_priority(self: @ContractState, rule: ContractAddress) -> u8 {
+            self.rule_priorities.read(rule)
+        }
+        
+        fn is_forced_transfer_exempt(self: @ContractState, rule: ContractAddress) -> bool {
+            self.forced_transfer_exempt.read(rule)
+        }
+        
+        fn find_failing_rule(
+            self: @ContractState, 
+            from: ContractAddress, 
+            to: ContractAddress, 
+            amount: u256,
+            forced: bool
+        ) -> (bool, u32, ContractAddress) {
+            // First rejecting rule of `check_compliance`, or `check_forced_transfer` when forced
+            self._find_failing_rule(from, to, amount, forced)
+        }
+        
+        fn bind_token(ref self: ContractState, token: ContractAddress) -> bool {
+            // Only owner can consent to govern a token
+            self.ownable.assert_only_owner();
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(token != zero_address, 'Invalid token address');
+            assert(self.token_indices.read(token) == 0, 'Token already bound');
+            
+            // Module state (holders, balances, limits) is kept per compliance, so a
+            // compliance governs a single token. Unbind it before binding another.
+            let token_count = self.token_count_map.read('token_count');
+            assert(token_count == 0, 'Compliance already bound');
+            self.tokens.write(token_count, token);
+            self.token_indices.write(token, token_count + 1);
+            self.token_count_map.write('token_count', token_count + 1);
+            
+            self.emit(TokenBound { token });
+            true
+        }
+        
+        fn unbind_token(ref self: ContractState, token: ContractAddress) -> bool {
+            // Only owner can release a token
+            self.ownable.assert_only_owner();
+            
+            let index = self.token_indices.read(token);
+            assert(index != 0, 'Token not bound');
+            
+            // Move the last token into the freed slot
+            let token_count = self.token_count_map.read('token_count');
+            let last_index = token_count - 1;
+            if index - 1 != last_index {
+                let last_token = self.tokens.read(last_index);
+                self.tokens.write(index - 1, last_token);
+                self.token_indices.write(last_token, index);
+            }
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            self.tokens.write(last_index, zero_address);
+            self.token_indices.write(token, 0);
+            self.token_count_map.write('token_count', last_index);
+            
+            self.emit(TokenUnbound { token });
+            true
+        }
+        
+        fn is_token_bound(self: @ContractState, token: ContractAddress) -> bool {
+            self.token_indices.read(token) != 0
+        }
+        
+        fn get_bound_tokens(self: @ContractState) -> Array<ContractAddress> {
+            let mut bound_tokens = ArrayTrait::<ContractAddress>::new();
+            let token_count = self.token_count_map.read('token_count');
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= token_count {
+                    break;
+                }
+                
+                bound_tokens.append(self.tokens.read(i));
+                
+                i += 1;
+            };
+            
+            bound_tokens
+        }
+        
+        fn get_token(self: @ContractState) -> ContractAddress {
+            // The only bound token, modules read investor data through it
+            self.tokens.read(0)
+        }
+        
+        fn transferred(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Only the bound token reports balance changes
+            self._assert_only_token();
+            
+            let rule_count = self.get_rule_count();
+            let mut i: u32 = 0;
+            loop {
+                if i >= rule_count {
+                    break;
+                }
+                
+                IComplianceModuleDispatcher { contract_address: self.rules.read(i) }
+                    .module_transfer_action(from, to, amount);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn created(ref self: ContractState, to: ContractAddress, amount: u256) -> bool {
+            // Only the bound token reports balance changes
+            self._assert_only_token();
+            
+            let rule_count = self.get_rule_count();
+            let mut i: u32 = 0;
+            loop {
+                if i >= rule_count {
+                    break;
+                }
+                
+                IComplianceModuleDispatcher { contract_address: self.rules.read(i) }
+                    .module_mint_action(to, amount);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn destroyed(ref self: ContractState, from: ContractAddress, amount: u256) -> bool {
+            // Only the bound token reports balance changes
+            self._assert_only_token();
+            
+            let rule_count = self.get_rule_count();
+            let mut i: u32 = 0;
+            loop {
+                if i >= rule_count {
+                    break;
+                }
+                
+                IComplianceModuleDispatcher { contract_address: self.rules.read(i) }
+                    .module_burn_action(from, amount);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn snapshot_configuration(ref self: ContractState) -> felt252 {
+            // Only owner records snapshots
+            self.ownable.assert_only_owner();
+            
+            let configuration_hash = self._configuration_hash();
+            let timestamp = get_block_timestamp();
+            let index = self.snapshot_count_map.read('snapshot_count');
+            
+            self.snapshot_timestamps.write(index, timestamp);
+            self.snapshot_hashes.write(index, configuration_hash);
+            self.snapshot_count_map.write('snapshot_count', index + 1);
+            
+            self.emit(ConfigurationSnapshot { index, timestamp, configuration_hash });
+            configuration_hash
+        }
+        
+        fn get_configuration_hash(self: @ContractState) -> felt252 {
+            self._configuration_hash()
+        }
+        
+        fn get_snapshot_count(self: @ContractState) -> u32 {
+            self.snapshot_count_map.read('snapshot_count')
+        }
+        
+        fn get_snapshot(self: @ContractState, index: u32) -> (u64, felt252) {
+            assert(index < self.snapshot_count_map.read('snapshot_count'), 'Snapshot not found');
+            (self.snapshot_timestamps.read(index), self.snapshot_hashes.read(index))
+        }
+        
         fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
             self.ownable.transfer_ownership(new_owner);
             true
@@ -303,11 +599,175 @@ pub mod Compliance {
         fn owner(self: @ContractState) -> ContractAddress {
             self.ownable.owner()
         }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the implementation
+            self.ownable.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.ownable.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, 'Storage already migrated');
+            
+            // Version 2 replaced the built-in blackout exemption of forced transfers with a
+            // per-rule flag, set here for the blackout modules already registered
+            if from_version < 2 {
+                let rule_count = self.get_rule_count();
+                let mut i: u32 = 0;
+                while i < rule_count {
+                    let rule = self.rules.read(i);
+                    if IComplianceModuleDispatcher { contract_address: rule }.name() == 'BlackoutModule' {
+                        self.forced_transfer_exempt.write(rule, true);
+                    }
+                    i += 1;
+                };
+            }
+            
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     // Internal helper methods
     #[generate_trait]
     impl InternalFunctions of InternalTrait {
+        fn _find_failing_rule(
+            self: @ContractState, 
+            from: ContractAddress, 
+            to: ContractAddress, 
+            amount: u256,
+            forced: bool
+        ) -> (bool, u32, ContractAddress) {
+            // Rules run in priority order, the first rejecting one is reported with its index
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            let rule_count = self.get_rule_count();
+            let mut i: u32 = 0;
+            loop {
+                if i >= rule_count {
+                    break (false, 0, zero_address);
+                }
+                
+                // Forced transfers skip the rules registered as exempt
+                let rule = IComplianceModuleDispatcher { contract_address: self.rules.read(i) };
+                let skipped = forced && self.forced_transfer_exempt.read(rule.contract_address);
+                if !skipped && !rule.module_check(from, to, amount) {
+                    break (true, i, rule.contract_address);
+                }
+                
+                i += 1;
+            }
+        }
+        
+        fn _assert_only_token(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(self.token_indices.read(caller) != 0, 'Only bound token');
+        }
+        
+        fn _configuration_hash(self: @ContractState) -> felt252 {
+            // Poseidon hash over the ordered modules (address, priority, class hash and
+            // parameters through `config_hash`), the claim topic checks and the bound tokens
+            let mut data = ArrayTrait::<felt252>::new();
+            
+            let rule_count = self.get_rule_count();
+            data.append(rule_count.into());
+            let mut i: u32 = 0;
+            while i < rule_count {
+                let rule = self.rules.read(i);
+                let class_hash = get_class_hash_at_syscall(rule).unwrap();
+                data.append(rule.into());
+                data.append(self.rule_priorities.read(rule).into());
+                data.append(class_hash.into());
+                data.append(IComplianceModuleDispatcher { contract_address: rule }.config_hash());
+                
+                // Only exemptions are hashed, so configurations from before they existed keep their hash
+                if self.forced_transfer_exempt.read(rule) {
+                    data.append('forced_transfer_exempt');
+                }
+                i += 1;
+            };
+            
+            let check_count = self.get_check_count();
+            data.append(check_count.into());
+            let mut j: u32 = 0;
+            while j < check_count {
+                data.append(self.checks.read(j));
+                j += 1;
+            };
+            
+            let token_count = self.token_count_map.read('token_count');
+            data.append(token_count.into());
+            let mut k: u32 = 0;
+            while k < token_count {
+                data.append(self.tokens.read(k).into());
+                k += 1;
+            };
+            
+            // Only a blocked policy is hashed, so configurations from before it existed keep their hash
+            if self.zero_amount_blocked_map.read('zero_amount_blocked') {
+                data.append('zero_amount_blocked');
+            }
+            
+            poseidon_hash_span(data.span())
+        }
+        
+        fn _insert_rule(ref self: ContractState, rule: ContractAddress) {
+            // Shift lower priority rules up, equal priorities keep their insertion order
+            let priority = self.rule_priorities.read(rule);
+            let mut position = self.get_rule_count();
+            loop {
+                if position == 0 {
+                    break;
+                }
+                
+                let previous = self.rules.read(position - 1);
+                if self.rule_priorities.read(previous) >= priority {
+                    break;
+                }
+                
+                self.rules.write(position, previous);
+                self.rule_indices.write(previous, position + 1); // +1 to differentiate from 0 (not found)
+                position -= 1;
+            };
+            
+            self.rules.write(position, rule);
+            self.rule_indices.write(rule, position + 1); // +1 to differentiate from 0 (not found)
+            self.set_rule_count(self.get_rule_count() + 1);
+        }
+        
+        fn _remove_rule_at(ref self: ContractState, index: u32) {
+            // Shift the following rules down
+            let rule_count = self.get_rule_count();
+            self.rule_indices.write(self.rules.read(index), 0);
+            
+            let mut i = index;
+            loop {
+                if i + 1 >= rule_count {
+                    break;
+                }
+                
+                let next = self.rules.read(i + 1);
+                self.rules.write(i, next);
+                self.rule_indices.write(next, i + 1); // +1 to differentiate from 0 (not found)
+                i += 1;
+            };
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            self.rules.write(rule_count - 1, zero_address);
+            self.set_rule_count(rule_count - 1);
+        }
+        
         // Helper methods for accessing the rule counter
         fn get_rule_count(self: @ContractState) -> u32 {
             self.rule_count_map.read('rule_count')
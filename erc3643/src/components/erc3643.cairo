@@ -75,6 +75,9 @@ pub mod ERC3643Component {
         // Token metadata - only used if not already present in ERC20 component
         stored_name: starknet::storage::Map::<felt252, felt252>,  // Store name as felt252
         stored_symbol: starknet::storage::Map::<felt252, felt252>,  // Store symbol as felt252
+        
+        // Set by the initializer, which must only run once
+        initialized_map: starknet::storage::Map::<felt252, bool>,  // Using 'initialized' as key
     }
 
     // Events from the component
@@ -348,6 +351,10 @@ pub mod ERC3643Component {
             compliance: ContractAddress,
             identity_registry: ContractAddress
         ) {
+            // Embedding contracts must not reset the registries or owner agent later on
+            assert(!self.initialized_map.read('initialized'), 'Already initialized');
+            self.initialized_map.write('initialized', true);
+            
             // Initialize token metadata
             self.stored_name.write('name', name);
             self.stored_symbol.write('symbol', symbol);
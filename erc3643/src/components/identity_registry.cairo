@@ -72,6 +72,9 @@ pub mod IdentityRegistryComponent {
         identity_storage_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'identity_storage' as key
         claim_topics_registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'claim_topics_registry' as key
         trusted_issuers_registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'trusted_issuers_registry' as key
+        
+        // Set by the initializer, which must only run once
+        initialized_map: starknet::storage::Map::<felt252, bool>,  // Using 'initialized' as key
     }
 
     // Events
@@ -407,6 +410,10 @@ pub mod IdentityRegistryComponent {
             claim_topics_registry: ContractAddress,
             trusted_issuers_registry: ContractAddress
         ) {
+            // Embedding contracts must not reset the registries or grant roles again later on
+            assert(!self.initialized_map.read('initialized'), 'Already initialized');
+            self.initialized_map.write('initialized', true);
+            
             // Grant AGENT_ROLE to initial owner
             self.roles_map.write((AGENT_ROLE, initial_owner), true);
             
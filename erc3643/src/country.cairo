@@ -0,0 +1,62 @@
+//! Country helpers
+//!
+//! Countries are stored as ISO-3166-1 numeric codes (e.g. 250 for France). These helpers
+//! validate codes and group them into regions compliance modules commonly need.
+
+// ISO-3166-1 numeric codes range from 001 to 999
+pub fn is_valid_country(country: felt252) -> bool {
+    match _to_code(country) {
+        Option::Some(code) => code >= 1 && code <= 999,
+        Option::None => false,
+    }
+}
+
+// European Union member states
+pub fn is_eu(country: felt252) -> bool {
+    _contains(eu_countries(), country)
+}
+
+// European Economic Area: EU plus Iceland, Liechtenstein and Norway
+pub fn is_eea(country: felt252) -> bool {
+    is_eu(country) || _contains(array![352, 438, 578].span(), country)
+}
+
+// Jurisdictions under increased monitoring by the FATF (grey list of June 2025).
+// The list changes several times a year; modules relying on it should be reviewed accordingly.
+pub fn is_fatf_grey_list(country: felt252) -> bool {
+    _contains(fatf_grey_list(), country)
+}
+
+pub fn eu_countries() -> Span<u16> {
+    array![
+        40, 56, 100, 191, 196, 203, 208, 233, 246, 250, 276, 300, 348, 372,
+        380, 428, 440, 442, 470, 528, 616, 620, 642, 703, 705, 724, 752,
+    ].span()
+}
+
+pub fn fatf_grey_list() -> Span<u16> {
+    array![
+        12, 24, 68, 92, 100, 120, 180, 332, 384, 404, 418, 422, 492, 508,
+        516, 524, 566, 704, 710, 728, 760, 854, 862, 887,
+    ].span()
+}
+
+fn _to_code(country: felt252) -> Option<u16> {
+    country.try_into()
+}
+
+fn _contains(codes: Span<u16>, country: felt252) -> bool {
+    let code = match _to_code(country) {
+        Option::Some(code) => code,
+        Option::None => { return false; },
+    };
+    
+    let mut found = false;
+    for candidate in codes {
+        if *candidate == code {
+            found = true;
+            break;
+        }
+    };
+    found
+}
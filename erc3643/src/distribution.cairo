@@ -0,0 +1,423 @@
+//! Distributions
+//!
+//! Pays dividends (or any pro-rata payout) to the holders of a security token at a record
+//! date. A token agent takes a snapshot of the token balances, then funds a distribution
+//! with an ERC20 payment token against that snapshot id. Each holder's share is the pool
+//! amount times their balance at the snapshot over the total supply at the snapshot.
+//! Holders claim their own share, or an agent pays out a batch of holders; each holder is
+//! paid at most once per distribution.
+//!
+//! Debt tokens can also be given a coupon schedule: a face value, an annual rate, a
+//! day-count convention and the payment dates. Once a payment date has passed, an agent
+//! records the coupon, which snapshots the token and computes the amount due on the supply
+//! at that snapshot, then executes it, which funds a distribution of that amount. Recording
+//! coupons requires this contract to be a token agent with the snapshot permission.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+use crate::payments;
+
+// Day-count conventions of coupon schedules
+pub const DAY_COUNT_ACTUAL_365: u8 = 1;
+pub const DAY_COUNT_ACTUAL_360: u8 = 2;
+
+// Security tokens use 18 decimals, face values are given per whole token
+const TOKEN_UNIT: u256 = 1_000_000_000_000_000_000;
+const SECONDS_PER_DAY: u64 = 86400;
+const BASIS_POINTS: u256 = 10000;
+
+// Distribution Interface
+#[starknet::interface]
+pub trait IDistribution<TContractState> {
+    fn create_distribution(
+        ref self: TContractState,
+        token: ContractAddress,
+        snapshot_id: u64,
+        payment_token: ContractAddress,
+        amount: u256
+    ) -> u64;
+    fn claim(ref self: TContractState, distribution_id: u64) -> u256;
+    fn batch_claim(ref self: TContractState, distribution_id: u64, holders: Array<ContractAddress>) -> u256;
+    fn get_claimable(self: @TContractState, distribution_id: u64, holder: ContractAddress) -> u256;
+    fn has_claimed(self: @TContractState, distribution_id: u64, holder: ContractAddress) -> bool;
+    fn get_distribution(self: @TContractState, distribution_id: u64) -> (ContractAddress, u64, ContractAddress, u256, u256);
+    fn get_distribution_count(self: @TContractState) -> u64;
+    
+    // Coupon schedules of debt tokens
+    fn set_coupon_schedule(
+        ref self: TContractState,
+        token: ContractAddress,
+        payment_token: ContractAddress,
+        face_value: u256,
+        rate_bps: u32,
+        day_count: u8,
+        start_date: u64,
+        payment_dates: Array<u64>
+    ) -> bool;
+    fn record_coupon(ref self: TContractState, token: ContractAddress, period: u32) -> u256;
+    fn execute_coupon(ref self: TContractState, token: ContractAddress, period: u32) -> u64;
+    fn get_coupon_schedule(self: @TContractState, token: ContractAddress) -> (ContractAddress, u256, u32, u8, u64, u32);
+    fn get_coupon_payment_date(self: @TContractState, token: ContractAddress, period: u32) -> u64;
+    fn get_coupon(self: @TContractState, token: ContractAddress, period: u32) -> (u64, u256, u64);
+}
+
+#[starknet::contract]
+pub mod Distribution {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        DistributionCreated: DistributionCreated,
+        Claimed: Claimed,
+        CouponScheduleSet: CouponScheduleSet,
+        CouponRecorded: CouponRecorded,
+        CouponExecuted: CouponExecuted,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DistributionCreated {
+        #[key]
+        distribution_id: u64,
+        token: ContractAddress,
+        snapshot_id: u64,
+        payment_token: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct Claimed {
+        #[key]
+        distribution_id: u64,
+        #[key]
+        holder: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CouponScheduleSet {
+        #[key]
+        token: ContractAddress,
+        payment_token: ContractAddress,
+        face_value: u256,
+        rate_bps: u32,
+        day_count: u8,
+        start_date: u64,
+        period_count: u32,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CouponRecorded {
+        #[key]
+        token: ContractAddress,
+        period: u32,
+        snapshot_id: u64,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CouponExecuted {
+        #[key]
+        token: ContractAddress,
+        period: u32,
+        distribution_id: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Distributions, by id starting at 1
+        distribution_count_map: Map<felt252, u64>,  // Using 'distribution_count' as key
+        distribution_tokens: Map<u64, ContractAddress>,
+        distribution_snapshot_ids: Map<u64, u64>,
+        distribution_payment_tokens: Map<u64, ContractAddress>,
+        distribution_amounts: Map<u64, u256>,
+        distribution_claimed_totals: Map<u64, u256>,
+        
+        // Holders already paid, per distribution
+        claimed: Map<(u64, ContractAddress), bool>,
+        
+        // Coupon schedules, per token
+        coupon_payment_tokens: Map<ContractAddress, ContractAddress>,
+        coupon_face_values: Map<ContractAddress, u256>,
+        coupon_rates: Map<ContractAddress, u32>,  // Annual rate in basis points
+        coupon_day_counts: Map<ContractAddress, u8>,
+        coupon_start_dates: Map<ContractAddress, u64>,
+        coupon_period_counts: Map<ContractAddress, u32>,
+        coupon_payment_dates: Map<(ContractAddress, u32), u64>,
+        
+        // Coupons per (token, period): record snapshot, amount due and funded distribution
+        coupon_snapshot_ids: Map<(ContractAddress, u32), u64>,
+        coupon_amounts: Map<(ContractAddress, u32), u256>,
+        coupon_distribution_ids: Map<(ContractAddress, u32), u64>,
+    }
+    
+    #[abi(embed_v0)]
+    impl DistributionImpl of super::IDistribution<ContractState> {
+        fn create_distribution(
+            ref self: ContractState,
+            token: ContractAddress,
+            snapshot_id: u64,
+            payment_token: ContractAddress,
+            amount: u256
+        ) -> u64 {
+            // Only token agents fund distributions
+            self._assert_token_agent(token);
+            self._create_distribution(token, snapshot_id, payment_token, amount)
+        }
+        
+        fn claim(ref self: ContractState, distribution_id: u64) -> u256 {
+            assert(self.distribution_amounts.read(distribution_id) > 0, 'Distribution not found');
+            
+            let holder = get_caller_address();
+            assert(!self.claimed.read((distribution_id, holder)), 'Already claimed');
+            
+            let amount = self._pay(distribution_id, holder);
+            assert(amount > 0, 'Nothing to claim');
+            amount
+        }
+        
+        fn batch_claim(ref self: ContractState, distribution_id: u64, holders: Array<ContractAddress>) -> u256 {
+            assert(self.distribution_amounts.read(distribution_id) > 0, 'Distribution not found');
+            
+            // Only token agents pay out on behalf of holders
+            self._assert_token_agent(self.distribution_tokens.read(distribution_id));
+            
+            // Holders already paid are skipped so a batch can be replayed safely
+            let mut total: u256 = 0;
+            let mut i: u32 = 0;
+            loop {
+                if i >= holders.len() {
+                    break;
+                }
+                
+                let holder = *holders.at(i);
+                if !self.claimed.read((distribution_id, holder)) {
+                    total += self._pay(distribution_id, holder);
+                }
+                
+                i += 1;
+            };
+            
+            total
+        }
+        
+        fn get_claimable(self: @ContractState, distribution_id: u64, holder: ContractAddress) -> u256 {
+            if self.distribution_amounts.read(distribution_id) == 0 || self.claimed.read((distribution_id, holder)) {
+                return 0;
+            }
+            self._share(distribution_id, holder)
+        }
+        
+        fn has_claimed(self: @ContractState, distribution_id: u64, holder: ContractAddress) -> bool {
+            self.claimed.read((distribution_id, holder))
+        }
+        
+        fn get_distribution(self: @ContractState, distribution_id: u64) -> (ContractAddress, u64, ContractAddress, u256, u256) {
+            (
+                self.distribution_tokens.read(distribution_id),
+                self.distribution_snapshot_ids.read(distribution_id),
+                self.distribution_payment_tokens.read(distribution_id),
+                self.distribution_amounts.read(distribution_id),
+                self.distribution_claimed_totals.read(distribution_id)
+            )
+        }
+        
+        fn get_distribution_count(self: @ContractState) -> u64 {
+            self.distribution_count_map.read('distribution_count')
+        }
+        
+        fn set_coupon_schedule(
+            ref self: ContractState,
+            token: ContractAddress,
+            payment_token: ContractAddress,
+            face_value: u256,
+            rate_bps: u32,
+            day_count: u8,
+            start_date: u64,
+            payment_dates: Array<u64>
+        ) -> bool {
+            // Only token agents manage coupon schedules, until the first coupon is recorded
+            self._assert_token_agent(token);
+            assert(self.coupon_snapshot_ids.read((token, 0)) == 0, 'Coupons already recorded');
+            assert(face_value > 0, 'Invalid face value');
+            assert(rate_bps > 0, 'Invalid rate');
+            assert(day_count == DAY_COUNT_ACTUAL_365 || day_count == DAY_COUNT_ACTUAL_360, 'Invalid day count');
+            assert(payment_dates.len() > 0, 'No payment dates');
+            
+            // Payment dates must follow the start date in increasing order
+            let mut previous_date = start_date;
+            let mut i: u32 = 0;
+            loop {
+                if i >= payment_dates.len() {
+                    break;
+                }
+                
+                let payment_date = *payment_dates.at(i);
+                assert(payment_date > previous_date, 'Payment dates not increasing');
+                self.coupon_payment_dates.write((token, i), payment_date);
+                previous_date = payment_date;
+                
+                i += 1;
+            };
+            
+            let period_count = payment_dates.len();
+            self.coupon_payment_tokens.write(token, payment_token);
+            self.coupon_face_values.write(token, face_value);
+            self.coupon_rates.write(token, rate_bps);
+            self.coupon_day_counts.write(token, day_count);
+            self.coupon_start_dates.write(token, start_date);
+            self.coupon_period_counts.write(token, period_count);
+            
+            self.emit(CouponScheduleSet { token, payment_token, face_value, rate_bps, day_count, start_date, period_count });
+            true
+        }
+        
+        fn record_coupon(ref self: ContractState, token: ContractAddress, period: u32) -> u256 {
+            self._assert_token_agent(token);
+            assert(period < self.coupon_period_counts.read(token), 'Invalid coupon period');
+            assert(self.coupon_snapshot_ids.read((token, period)) == 0, 'Coupon already recorded');
+            assert(period == 0 || self.coupon_snapshot_ids.read((token, period - 1)) != 0, 'Previous coupon not recorded');
+            
+            let payment_date = self.coupon_payment_dates.read((token, period));
+            assert(starknet::get_block_timestamp() >= payment_date, 'Payment date not reached');
+            
+            // Interest accrued on the supply at the snapshot since the previous payment date
+            let accrual_start = if period == 0 {
+                self.coupon_start_dates.read(token)
+            } else {
+                self.coupon_payment_dates.read((token, period - 1))
+            };
+            let days: u256 = ((payment_date - accrual_start) / SECONDS_PER_DAY).into();
+            let year_days: u256 = if self.coupon_day_counts.read(token) == DAY_COUNT_ACTUAL_360 { 360 } else { 365 };
+            
+            // Snapshot now, after the payment date, so the holders paid are those of the record date
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            let snapshot_id = token_dispatcher.snapshot();
+            let supply = token_dispatcher.total_supply_at(snapshot_id);
+            let principal = supply * self.coupon_face_values.read(token) / TOKEN_UNIT;
+            let rate: u256 = self.coupon_rates.read(token).into();
+            let amount = principal * rate * days / (BASIS_POINTS * year_days);
+            
+            // Nothing due could never be executed, the period stays open for a later record
+            assert(amount > 0, 'No coupon due');
+            
+            self.coupon_snapshot_ids.write((token, period), snapshot_id);
+            self.coupon_amounts.write((token, period), amount);
+            
+            self.emit(CouponRecorded { token, period, snapshot_id, amount });
+            amount
+        }
+        
+        fn execute_coupon(ref self: ContractState, token: ContractAddress, period: u32) -> u64 {
+            self._assert_token_agent(token);
+            let snapshot_id = self.coupon_snapshot_ids.read((token, period));
+            assert(snapshot_id != 0, 'Coupon not recorded');
+            assert(self.coupon_distribution_ids.read((token, period)) == 0, 'Coupon already executed');
+            
+            // The caller funds the coupon, holders then claim it like any distribution
+            let distribution_id = self._create_distribution(
+                token,
+                snapshot_id,
+                self.coupon_payment_tokens.read(token),
+                self.coupon_amounts.read((token, period))
+            );
+            self.coupon_distribution_ids.write((token, period), distribution_id);
+            
+            self.emit(CouponExecuted { token, period, distribution_id });
+            distribution_id
+        }
+        
+        fn get_coupon_schedule(self: @ContractState, token: ContractAddress) -> (ContractAddress, u256, u32, u8, u64, u32) {
+            (
+                self.coupon_payment_tokens.read(token),
+                self.coupon_face_values.read(token),
+                self.coupon_rates.read(token),
+                self.coupon_day_counts.read(token),
+                self.coupon_start_dates.read(token),
+                self.coupon_period_counts.read(token)
+            )
+        }
+        
+        fn get_coupon_payment_date(self: @ContractState, token: ContractAddress, period: u32) -> u64 {
+            self.coupon_payment_dates.read((token, period))
+        }
+        
+        fn get_coupon(self: @ContractState, token: ContractAddress, period: u32) -> (u64, u256, u64) {
+            (
+                self.coupon_snapshot_ids.read((token, period)),
+                self.coupon_amounts.read((token, period)),
+                self.coupon_distribution_ids.read((token, period))
+            )
+        }
+    }
+    
+    // Internal functions implementation
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_token_agent(self: @ContractState, token: ContractAddress) {
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.is_compliance_agent(get_caller_address()), 'Only agents allowed');
+        }
+        
+        fn _create_distribution(
+            ref self: ContractState,
+            token: ContractAddress,
+            snapshot_id: u64,
+            payment_token: ContractAddress,
+            amount: u256
+        ) -> u64 {
+            assert(amount > 0, 'Invalid amount');
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.total_supply_at(snapshot_id) > 0, 'Empty snapshot');
+            
+            // Pull the pool from the caller into this contract
+            payments::collect(payment_token, get_caller_address(), amount);
+            
+            let distribution_id = self.distribution_count_map.read('distribution_count') + 1;
+            self.distribution_count_map.write('distribution_count', distribution_id);
+            
+            self.distribution_tokens.write(distribution_id, token);
+            self.distribution_snapshot_ids.write(distribution_id, snapshot_id);
+            self.distribution_payment_tokens.write(distribution_id, payment_token);
+            self.distribution_amounts.write(distribution_id, amount);
+            
+            self.emit(DistributionCreated { distribution_id, token, snapshot_id, payment_token, amount });
+            distribution_id
+        }
+        
+        fn _share(self: @ContractState, distribution_id: u64, holder: ContractAddress) -> u256 {
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.distribution_tokens.read(distribution_id) };
+            let snapshot_id = self.distribution_snapshot_ids.read(distribution_id);
+            
+            // Rounded down, the remainder stays in the contract
+            let balance = token_dispatcher.balance_of_at(holder, snapshot_id);
+            let supply = token_dispatcher.total_supply_at(snapshot_id);
+            self.distribution_amounts.read(distribution_id) * balance / supply
+        }
+        
+        fn _pay(ref self: ContractState, distribution_id: u64, holder: ContractAddress) -> u256 {
+            let amount = self._share(distribution_id, holder);
+            self.claimed.write((distribution_id, holder), true);
+            
+            if amount > 0 {
+                let claimed_total = self.distribution_claimed_totals.read(distribution_id);
+                self.distribution_claimed_totals.write(distribution_id, claimed_total + amount);
+                
+                let payment_token = self.distribution_payment_tokens.read(distribution_id);
+                payments::pay(payment_token, holder, amount);
+                
+                self.emit(Claimed { distribution_id, holder, amount });
+            }
+            
+            amount
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Delegated Transfer Approval (DVA)
+//!
+//! Transfer manager making transfers of a token wait for designated approvers. The holder
+//! allows this contract to spend the amount and initiates the transfer; the amount is
+//! held on the holder's wallet until every approver (the recipient, a token agent and/or
+//! named addresses, as configured per token by its agents) has approved. The hold is the
+//! manager's own, so settling or cancelling never lifts a freeze placed by another agent.
+//! The last approval executes the transfer through `transfer_from`, so the usual identity
+//! and compliance checks apply. Pending transfers can be rejected by an approver, cancelled
+//! by the holder and, once past their deadline, cancelled by anyone.
+//!
+//! The manager must be an agent of the token with the freeze permission.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::array::ArrayTrait;
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Transfer statuses
+pub const TRANSFER_PENDING: u8 = 1;
+pub const TRANSFER_COMPLETED: u8 = 2;
+pub const TRANSFER_REJECTED: u8 = 3;
+pub const TRANSFER_CANCELLED: u8 = 4;
+
+// Approvers of a token, limited so approvals stay cheap to scan
+pub const MAX_APPROVERS: u32 = 10;
+
+// DVA Transfer Manager Interface
+#[starknet::interface]
+pub trait IDVATransferManager<TContractState> {
+    fn set_approval_criteria(
+        ref self: TContractState,
+        token: ContractAddress,
+        include_recipient_approver: bool,
+        include_agent_approver: bool,
+        additional_approvers: Array<ContractAddress>,
+        validity_period: u64
+    ) -> bool;
+    fn get_approval_criteria(self: @TContractState, token: ContractAddress) -> (bool, bool, Array<ContractAddress>, u64);
+    fn initiate_transfer(ref self: TContractState, token: ContractAddress, recipient: ContractAddress, amount: u256) -> u64;
+    fn approve_transfer(ref self: TContractState, transfer_id: u64) -> bool;
+    fn reject_transfer(ref self: TContractState, transfer_id: u64) -> bool;
+    fn cancel_transfer(ref self: TContractState, transfer_id: u64) -> bool;
+    fn get_transfer(self: @TContractState, transfer_id: u64) -> (ContractAddress, ContractAddress, ContractAddress, u256, u64, u8);
+    fn get_pending_approvers(self: @TContractState, transfer_id: u64) -> Array<ContractAddress>;
+    fn get_transfer_count(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod DVATransferManager {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        ApprovalCriteriaSet: ApprovalCriteriaSet,
+        TransferInitiated: TransferInitiated,
+        TransferApproved: TransferApproved,
+        TransferRejected: TransferRejected,
+        TransferCancelled: TransferCancelled,
+        TransferCompleted: TransferCompleted,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ApprovalCriteriaSet {
+        #[key]
+        token: ContractAddress,
+        include_recipient_approver: bool,
+        include_agent_approver: bool,
+        additional_approver_count: u32,
+        validity_period: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferInitiated {
+        #[key]
+        transfer_id: u64,
+        token: ContractAddress,
+        sender: ContractAddress,
+        recipient: ContractAddress,
+        amount: u256,
+        deadline: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferApproved {
+        #[key]
+        transfer_id: u64,
+        approver: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferRejected {
+        #[key]
+        transfer_id: u64,
+        rejected_by: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferCancelled {
+        #[key]
+        transfer_id: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferCompleted {
+        #[key]
+        transfer_id: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Approval criteria per token
+        recipient_approver: Map<ContractAddress, bool>,
+        agent_approver: Map<ContractAddress, bool>,
+        additional_approver_counts: Map<ContractAddress, u32>,
+        additional_approvers: Map<(ContractAddress, u32), ContractAddress>,
+        validity_periods: Map<ContractAddress, u64>,  // 0 means no deadline
+        
+        // Transfers, by id starting at 1
+        transfer_count_map: Map<felt252, u64>,  // Using 'transfer_count' as key
+        transfer_tokens: Map<u64, ContractAddress>,
+        transfer_senders: Map<u64, ContractAddress>,
+        transfer_recipients: Map<u64, ContractAddress>,
+        transfer_amounts: Map<u64, u256>,
+        transfer_deadlines: Map<u64, u64>,
+        transfer_statuses: Map<u64, u8>,
+        
+        // Approvers snapshotted at initiation, the zero address standing for any token agent
+        approver_counts: Map<u64, u32>,
+        approvers: Map<(u64, u32), ContractAddress>,
+        approved: Map<(u64, u32), bool>,
+        approval_counts: Map<u64, u32>,
+    }
+    
+    #[abi(embed_v0)]
+    impl DVATransferManagerImpl of super::IDVATransferManager<ContractState> {
+        fn set_approval_criteria(
+            ref self: ContractState,
+            token: ContractAddress,
+            include_recipient_approver: bool,
+            include_agent_approver: bool,
+            additional_approvers: Array<ContractAddress>,
+            validity_period: u64
+        ) -> bool {
+            // Only token agents can configure the approvals of their token
+            let caller = get_caller_address();
+            assert(IERC3643TokenDispatcher { contract_address: token }.is_compliance_agent(caller), 'Only token agents allowed');
+            
+            let additional_approver_count = additional_approvers.len();
+            let mut total = additional_approver_count;
+            if include_recipient_approver {
+                total += 1;
+            }
+            if include_agent_approver {
+                total += 1;
+            }
+            assert(total > 0, 'No approvers');
+            assert(total <= MAX_APPROVERS, 'Too many approvers');
+            
+            self.recipient_approver.write(token, include_recipient_approver);
+            self.agent_approver.write(token, include_agent_approver);
+            self.validity_periods.write(token, validity_period);
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= additional_approver_count {
+                    break;
+                }
+                
+                self.additional_approvers.write((token, i), *additional_approvers.at(i));
+                
+                i += 1;
+            };
+            self.additional_approver_counts.write(token, additional_approver_count);
+            
+            self.emit(ApprovalCriteriaSet {
+                token,
+                include_recipient_approver,
+                include_agent_approver,
+                additional_approver_count,
+                validity_period,
+            });
+            true
+        }
+        
+        fn get_approval_criteria(self: @ContractState, token: ContractAddress) -> (bool, bool, Array<ContractAddress>, u64) {
+            let mut additional_approvers = ArrayTrait::<ContractAddress>::new();
+            let additional_approver_count = self.additional_approver_counts.read(token);
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= additional_approver_count {
+                    break;
+                }
+                
+                additional_approvers.append(self.additional_approvers.read((token, i)));
+                
+                i += 1;
+            };
+            
+            (
+                self.recipient_approver.read(token),
+                self.agent_approver.read(token),
+                additional_approvers,
+                self.validity_periods.read(token)
+            )
+        }
+        
+        fn initiate_transfer(ref self: ContractState, token: ContractAddress, recipient: ContractAddress, amount: u256) -> u64 {
+            let sender = get_caller_address();
+            assert(amount > 0, 'Invalid amount');
+            
+            // Tokens without criteria are not managed by this contract
+            let include_recipient = self.recipient_approver.read(token);
+            let include_agent = self.agent_approver.read(token);
+            let additional_approver_count = self.additional_approver_counts.read(token);
+            assert(include_recipient || include_agent || additional_approver_count > 0, 'Token not managed');
+            
+            // The amount stays with the sender but cannot move until the transfer settles
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.allowance(sender, starknet::get_contract_address()) >= amount, 'Insufficient allowance');
+            token_dispatcher.hold_tokens(sender, amount);
+            
+            let transfer_id = self.transfer_count_map.read('transfer_count') + 1;
+            self.transfer_count_map.write('transfer_count', transfer_id);
+            
+            let validity_period = self.validity_periods.read(token);
+            let deadline = if validity_period == 0 { 0 } else { get_block_timestamp() + validity_period };
+            
+            self.transfer_tokens.write(transfer_id, token);
+            self.transfer_senders.write(transfer_id, sender);
+            self.transfer_recipients.write(transfer_id, recipient);
+            self.transfer_amounts.write(transfer_id, amount);
+            self.transfer_deadlines.write(transfer_id, deadline);
+            self.transfer_statuses.write(transfer_id, TRANSFER_PENDING);
+            
+            // Snapshot the approvers, later criteria changes do not affect this transfer
+            let mut approver_count: u32 = 0;
+            if include_recipient {
+                self.approvers.write((transfer_id, approver_count), recipient);
+                approver_count += 1;
+            }
+            if include_agent {
+                let zero_address: ContractAddress = 0.try_into().unwrap();
+                self.approvers.write((transfer_id, approver_count), zero_address);
+                approver_count += 1;
+            }
+            let mut i: u32 = 0;
+            loop {
+                if i >= additional_approver_count {
+                    break;
+                }
+                
+                self.approvers.write((transfer_id, approver_count), self.additional_approvers.read((token, i)));
+                approver_count += 1;
+                
+                i += 1;
+            };
+            self.approver_counts.write(transfer_id, approver_count);
+            
+            self.emit(TransferInitiated { transfer_id, token, sender, recipient, amount, deadline });
+            transfer_id
+        }
+        
+        fn approve_transfer(ref self: ContractState, transfer_id: u64) -> bool {
+            self._assert_pending(transfer_id);
+            
+            let deadline = self.transfer_deadlines.read(transfer_id);
+            assert(deadline == 0 || get_block_timestamp() <= deadline, 'Transfer expired');
+            
+            let caller = get_caller_address();
+            let slot = self._pending_slot_of(transfer_id, caller);
+            assert(slot < self.approver_counts.read(transfer_id), 'Not an approver');
+            
+            self.approved.write((transfer_id, slot), true);
+            let approval_count = self.approval_counts.read(transfer_id) + 1;
+            self.approval_counts.write(transfer_id, approval_count);
+            self.emit(TransferApproved { transfer_id, approver: caller });
+            
+            // The last approval settles the transfer
+            if approval_count == self.approver_counts.read(transfer_id) {
+                self._execute(transfer_id);
+            }
+            
+            true
+        }
+        
+        fn reject_transfer(ref self: ContractState, transfer_id: u64) -> bool {
+            self._assert_pending(transfer_id);
+            
+            let caller = get_caller_address();
+            let slot = self._pending_slot_of(transfer_id, caller);
+            assert(slot < self.approver_counts.read(transfer_id), 'Not an approver');
+            
+            self._release(transfer_id, TRANSFER_REJECTED);
+            self.emit(TransferRejected { transfer_id, rejected_by: caller });
+            true
+        }
+        
+        fn cancel_transfer(ref self: ContractState, transfer_id: u64) -> bool {
+            self._assert_pending(transfer_id);
+            
+            // The sender can always cancel, anyone can clean up an expired transfer
+            let caller = get_caller_address();
+            if caller != self.transfer_senders.read(transfer_id) {
+                let deadline = self.transfer_deadlines.read(transfer_id);
+                assert(deadline != 0 && get_block_timestamp() > deadline, 'Only sender can cancel');
+            }
+            
+            self._release(transfer_id, TRANSFER_CANCELLED);
+            self.emit(TransferCancelled { transfer_id });
+            true
+        }
+        
+        fn get_transfer(self: @ContractState, transfer_id: u64) -> (ContractAddress, ContractAddress, ContractAddress, u256, u64, u8) {
+            (
+                self.transfer_tokens.read(transfer_id),
+                self.transfer_senders.read(transfer_id),
+                self.transfer_recipients.read(transfer_id),
+                self.transfer_amounts.read(transfer_id),
+                self.transfer_deadlines.read(transfer_id),
+                self.transfer_statuses.read(transfer_id)
+            )
+        }
+        
+        fn get_pending_approvers(self: @ContractState, transfer_id: u64) -> Array<ContractAddress> {
+            let mut pending = ArrayTrait::<ContractAddress>::new();
+            let approver_count = self.approver_counts.read(transfer_id);
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= approver_count {
+                    break;
+                }
+                
+                if !self.approved.read((transfer_id, i)) {
+                    pending.append(self.approvers.read((transfer_id, i)));
+                }
+                
+                i += 1;
+            };
+            
+            pending
+        }
+        
+        fn get_transfer_count(self: @ContractState) -> u64 {
+            self.transfer_count_map.read('transfer_count')
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_pending(self: @ContractState, transfer_id: u64) {
+            assert(self.transfer_statuses.read(transfer_id) == TRANSFER_PENDING, 'Transfer not pending');
+        }
+        
+        // First slot still waiting for the caller, or the approver count if there is none
+        fn _pending_slot_of(self: @ContractState, transfer_id: u64, caller: ContractAddress) -> u32 {
+            let token = self.transfer_tokens.read(transfer_id);
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            let approver_count = self.approver_counts.read(transfer_id);
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= approver_count {
+                    break;
+                }
+                
+                if !self.approved.read((transfer_id, i)) {
+                    let approver = self.approvers.read((transfer_id, i));
+                    if approver == caller {
+                        break;
+                    }
+                    if approver == zero_address
+                        && IERC3643TokenDispatcher { contract_address: token }.is_compliance_agent(caller) {
+                        break;
+                    }
+                }
+                
+                i += 1;
+            };
+            
+            i
+        }
+        
+        fn _execute(ref self: ContractState, transfer_id: u64) {
+            let token = self.transfer_tokens.read(transfer_id);
+            let sender = self.transfer_senders.read(transfer_id);
+            let recipient = self.transfer_recipients.read(transfer_id);
+            let amount = self.transfer_amounts.read(transfer_id);
+            
+            // Release the hold, then move the tokens with the full transfer checks
+            self.transfer_statuses.write(transfer_id, TRANSFER_COMPLETED);
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            token_dispatcher.release_held_tokens(sender, amount);
+            token_dispatcher.transfer_from(sender, recipient, amount);
+            
+            self.emit(TransferCompleted { transfer_id });
+        }
+        
+        fn _release(ref self: ContractState, transfer_id: u64, status: u8) {
+            let token = self.transfer_tokens.read(transfer_id);
+            let sender = self.transfer_senders.read(transfer_id);
+            let amount = self.transfer_amounts.read(transfer_id);
+            
+            self.transfer_statuses.write(transfer_id, status);
+            IERC3643TokenDispatcher { contract_address: token }.release_held_tokens(sender, amount);
+        }
+    }
+}
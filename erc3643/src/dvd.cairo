@@ -0,0 +1,293 @@
+//! Delivery versus Delivery (DvD)
+//!
+//! Swaps two tokens atomically, e.g. two security tokens or a security token against
+//! another token. The maker proposes a swap to a taker and allows this contract to spend
+//! the tokens they deliver; the taker allows it to spend theirs and takes the swap. Both
+//! legs go through `transfer_from`, so a security token applies its identity and
+//! compliance checks to its leg and the whole swap reverts if either leg fails.
+//!
+//! The venue operating the contract can charge a fee per token, taken from the amount
+//! delivered in that token and paid to the venue's fee wallet.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::payments;
+
+// Swap statuses
+pub const SWAP_OPEN: u8 = 1;
+pub const SWAP_SETTLED: u8 = 2;
+pub const SWAP_CANCELLED: u8 = 3;
+
+// Fees are expressed in basis points
+pub const BASIS_POINTS: u16 = 10000;
+
+// DvD Transfer Manager Interface
+#[starknet::interface]
+pub trait IDvDTransferManager<TContractState> {
+    fn initiate_swap(
+        ref self: TContractState,
+        maker_token: ContractAddress,
+        maker_amount: u256,
+        taker: ContractAddress,
+        taker_token: ContractAddress,
+        taker_amount: u256,
+        expiry: u64
+    ) -> u64;
+    fn take_swap(ref self: TContractState, swap_id: u64) -> bool;
+    fn cancel_swap(ref self: TContractState, swap_id: u64) -> bool;
+    fn set_fee(ref self: TContractState, token: ContractAddress, fee_bps: u16, fee_wallet: ContractAddress) -> bool;
+    fn get_fee(self: @TContractState, token: ContractAddress) -> (u16, ContractAddress);
+    fn calculate_fee(self: @TContractState, token: ContractAddress, amount: u256) -> u256;
+    fn get_swap(self: @TContractState, swap_id: u64) -> (ContractAddress, ContractAddress, u256, ContractAddress, ContractAddress, u256, u64, u8);
+    fn get_swap_count(self: @TContractState) -> u64;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod DvDTransferManager {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        SwapInitiated: SwapInitiated,
+        SwapSettled: SwapSettled,
+        SwapCancelled: SwapCancelled,
+        FeeSet: FeeSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct SwapInitiated {
+        #[key]
+        swap_id: u64,
+        maker: ContractAddress,
+        maker_token: ContractAddress,
+        maker_amount: u256,
+        taker: ContractAddress,
+        taker_token: ContractAddress,
+        taker_amount: u256,
+        expiry: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct SwapSettled {
+        #[key]
+        swap_id: u64,
+        maker_fee: u256,
+        taker_fee: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct SwapCancelled {
+        #[key]
+        swap_id: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct FeeSet {
+        #[key]
+        token: ContractAddress,
+        fee_bps: u16,
+        fee_wallet: ContractAddress,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Swaps, by id starting at 1
+        swap_count_map: Map<felt252, u64>,  // Using 'swap_count' as key
+        swap_makers: Map<u64, ContractAddress>,
+        swap_maker_tokens: Map<u64, ContractAddress>,
+        swap_maker_amounts: Map<u64, u256>,
+        swap_takers: Map<u64, ContractAddress>,
+        swap_taker_tokens: Map<u64, ContractAddress>,
+        swap_taker_amounts: Map<u64, u256>,
+        swap_expiries: Map<u64, u64>,  // 0 means no expiry
+        swap_statuses: Map<u64, u8>,
+        
+        // Venue fee per token
+        fee_rates: Map<ContractAddress, u16>,
+        fee_wallets: Map<ContractAddress, ContractAddress>,
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+    }
+    
+    #[abi(embed_v0)]
+    impl DvDTransferManagerImpl of super::IDvDTransferManager<ContractState> {
+        fn initiate_swap(
+            ref self: ContractState,
+            maker_token: ContractAddress,
+            maker_amount: u256,
+            taker: ContractAddress,
+            taker_token: ContractAddress,
+            taker_amount: u256,
+            expiry: u64
+        ) -> u64 {
+            let maker = get_caller_address();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(taker != zero_address && taker != maker, 'Invalid taker');
+            assert(maker_token != taker_token, 'Tokens must differ');
+            assert(maker_amount > 0 && taker_amount > 0, 'Invalid amount');
+            assert(expiry == 0 || expiry > get_block_timestamp(), 'Invalid expiry');
+            
+            let swap_id = self.swap_count_map.read('swap_count') + 1;
+            self.swap_count_map.write('swap_count', swap_id);
+            
+            self.swap_makers.write(swap_id, maker);
+            self.swap_maker_tokens.write(swap_id, maker_token);
+            self.swap_maker_amounts.write(swap_id, maker_amount);
+            self.swap_takers.write(swap_id, taker);
+            self.swap_taker_tokens.write(swap_id, taker_token);
+            self.swap_taker_amounts.write(swap_id, taker_amount);
+            self.swap_expiries.write(swap_id, expiry);
+            self.swap_statuses.write(swap_id, SWAP_OPEN);
+            
+            self.emit(SwapInitiated {
+                swap_id,
+                maker,
+                maker_token,
+                maker_amount,
+                taker,
+                taker_token,
+                taker_amount,
+                expiry,
+            });
+            swap_id
+        }
+        
+        fn take_swap(ref self: ContractState, swap_id: u64) -> bool {
+            assert(self.swap_statuses.read(swap_id) == SWAP_OPEN, 'Swap not open');
+            
+            let taker = self.swap_takers.read(swap_id);
+            assert(get_caller_address() == taker, 'Only taker can take');
+            
+            let expiry = self.swap_expiries.read(swap_id);
+            assert(expiry == 0 || get_block_timestamp() <= expiry, 'Swap expired');
+            
+            self.swap_statuses.write(swap_id, SWAP_SETTLED);
+            
+            // Both legs settle or neither does
+            let maker = self.swap_makers.read(swap_id);
+            let maker_fee = self._deliver(
+                self.swap_maker_tokens.read(swap_id), maker, taker, self.swap_maker_amounts.read(swap_id)
+            );
+            let taker_fee = self._deliver(
+                self.swap_taker_tokens.read(swap_id), taker, maker, self.swap_taker_amounts.read(swap_id)
+            );
+            
+            self.emit(SwapSettled { swap_id, maker_fee, taker_fee });
+            true
+        }
+        
+        fn cancel_swap(ref self: ContractState, swap_id: u64) -> bool {
+            assert(self.swap_statuses.read(swap_id) == SWAP_OPEN, 'Swap not open');
+            
+            // Either counterparty can walk away before settlement
+            let caller = get_caller_address();
+            assert(
+                caller == self.swap_makers.read(swap_id) || caller == self.swap_takers.read(swap_id),
+                'Only counterparties can cancel'
+            );
+            
+            self.swap_statuses.write(swap_id, SWAP_CANCELLED);
+            self.emit(SwapCancelled { swap_id });
+            true
+        }
+        
+        fn set_fee(ref self: ContractState, token: ContractAddress, fee_bps: u16, fee_wallet: ContractAddress) -> bool {
+            // Only the venue sets its fees
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(fee_bps <= BASIS_POINTS, 'Invalid fee');
+            assert(fee_bps == 0 || fee_wallet != zero_address, 'Invalid fee wallet');
+            
+            self.fee_rates.write(token, fee_bps);
+            self.fee_wallets.write(token, fee_wallet);
+            
+            self.emit(FeeSet { token, fee_bps, fee_wallet });
+            true
+        }
+        
+        fn get_fee(self: @ContractState, token: ContractAddress) -> (u16, ContractAddress) {
+            (self.fee_rates.read(token), self.fee_wallets.read(token))
+        }
+        
+        fn calculate_fee(self: @ContractState, token: ContractAddress, amount: u256) -> u256 {
+            let fee_bps: u256 = self.fee_rates.read(token).into();
+            amount * fee_bps / BASIS_POINTS.into()
+        }
+        
+        fn get_swap(self: @ContractState, swap_id: u64) -> (ContractAddress, ContractAddress, u256, ContractAddress, ContractAddress, u256, u64, u8) {
+            (
+                self.swap_makers.read(swap_id),
+                self.swap_maker_tokens.read(swap_id),
+                self.swap_maker_amounts.read(swap_id),
+                self.swap_takers.read(swap_id),
+                self.swap_taker_tokens.read(swap_id),
+                self.swap_taker_amounts.read(swap_id),
+                self.swap_expiries.read(swap_id),
+                self.swap_statuses.read(swap_id)
+            )
+        }
+        
+        fn get_swap_count(self: @ContractState) -> u64 {
+            self.swap_count_map.read('swap_count')
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _deliver(
+            ref self: ContractState,
+            token: ContractAddress,
+            from: ContractAddress,
+            to: ContractAddress,
+            amount: u256
+        ) -> u256 {
+            // The venue fee is taken from the delivered amount
+            let fee = self.calculate_fee(token, amount);
+            if fee > 0 {
+                payments::pay_from(token, from, self.fee_wallets.read(token), fee);
+            }
+            payments::pay_from(token, from, to, amount - fee);
+            fee
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Delivery versus Payment (DvP)
+//!
+//! Settles OTC trades of a security token against an ERC20 payment token in a single
+//! transaction. The seller opens a trade for a buyer and allows this contract to spend the
+//! security tokens; the buyer allows it to spend the payment and settles. Both legs go
+//! through `transfer_from`, the security leg with the token's full identity and
+//! compliance checks, so if either leg fails the whole settlement reverts.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+use crate::payments;
+
+// Trade statuses
+pub const TRADE_OPEN: u8 = 1;
+pub const TRADE_SETTLED: u8 = 2;
+pub const TRADE_CANCELLED: u8 = 3;
+
+// DvP Manager Interface
+#[starknet::interface]
+pub trait IDvPManager<TContractState> {
+    fn open_trade(
+        ref self: TContractState,
+        token: ContractAddress,
+        amount: u256,
+        buyer: ContractAddress,
+        payment_token: ContractAddress,
+        price: u256,
+        expiry: u64
+    ) -> u64;
+    fn settle_trade(ref self: TContractState, trade_id: u64) -> bool;
+    fn cancel_trade(ref self: TContractState, trade_id: u64) -> bool;
+    fn get_trade(self: @TContractState, trade_id: u64) -> (ContractAddress, u256, ContractAddress, ContractAddress, ContractAddress, u256, u64, u8);
+    fn get_trade_count(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod DvPManager {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        TradeOpened: TradeOpened,
+        TradeSettled: TradeSettled,
+        TradeCancelled: TradeCancelled,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TradeOpened {
+        #[key]
+        trade_id: u64,
+        token: ContractAddress,
+        amount: u256,
+        seller: ContractAddress,
+        buyer: ContractAddress,
+        payment_token: ContractAddress,
+        price: u256,
+        expiry: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TradeSettled {
+        #[key]
+        trade_id: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TradeCancelled {
+        #[key]
+        trade_id: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Trades, by id starting at 1
+        trade_count_map: Map<felt252, u64>,  // Using 'trade_count' as key
+        trade_tokens: Map<u64, ContractAddress>,
+        trade_amounts: Map<u64, u256>,
+        trade_sellers: Map<u64, ContractAddress>,
+        trade_buyers: Map<u64, ContractAddress>,
+        trade_payment_tokens: Map<u64, ContractAddress>,
+        trade_prices: Map<u64, u256>,
+        trade_expiries: Map<u64, u64>,  // 0 means no expiry
+        trade_statuses: Map<u64, u8>,
+    }
+    
+    #[abi(embed_v0)]
+    impl DvPManagerImpl of super::IDvPManager<ContractState> {
+        fn open_trade(
+            ref self: ContractState,
+            token: ContractAddress,
+            amount: u256,
+            buyer: ContractAddress,
+            payment_token: ContractAddress,
+            price: u256,
+            expiry: u64
+        ) -> u64 {
+            let seller = get_caller_address();
+            assert(amount > 0, 'Invalid amount');
+            assert(expiry == 0 || expiry > get_block_timestamp(), 'Invalid expiry');
+            
+            let trade_id = self.trade_count_map.read('trade_count') + 1;
+            self.trade_count_map.write('trade_count', trade_id);
+            
+            self.trade_tokens.write(trade_id, token);
+            self.trade_amounts.write(trade_id, amount);
+            self.trade_sellers.write(trade_id, seller);
+            self.trade_buyers.write(trade_id, buyer);
+            self.trade_payment_tokens.write(trade_id, payment_token);
+            self.trade_prices.write(trade_id, price);
+            self.trade_expiries.write(trade_id, expiry);
+            self.trade_statuses.write(trade_id, TRADE_OPEN);
+            
+            self.emit(TradeOpened { trade_id, token, amount, seller, buyer, payment_token, price, expiry });
+            trade_id
+        }
+        
+        fn settle_trade(ref self: ContractState, trade_id: u64) -> bool {
+            assert(self.trade_statuses.read(trade_id) == TRADE_OPEN, 'Trade not open');
+            
+            let buyer = self.trade_buyers.read(trade_id);
+            assert(get_caller_address() == buyer, 'Only buyer can settle');
+            
+            let expiry = self.trade_expiries.read(trade_id);
+            assert(expiry == 0 || get_block_timestamp() <= expiry, 'Trade expired');
+            
+            self.trade_statuses.write(trade_id, TRADE_SETTLED);
+            
+            // Delivery, with the security token's identity and compliance checks
+            let seller = self.trade_sellers.read(trade_id);
+            let token = self.trade_tokens.read(trade_id);
+            let delivered = IERC3643TokenDispatcher { contract_address: token }
+                .transfer_from(seller, buyer, self.trade_amounts.read(trade_id));
+            assert(delivered, 'Delivery failed');
+            
+            // Payment, reverting the delivery with it if it fails
+            let payment_token = self.trade_payment_tokens.read(trade_id);
+            payments::pay_from(payment_token, buyer, seller, self.trade_prices.read(trade_id));
+            
+            self.emit(TradeSettled { trade_id });
+            true
+        }
+        
+        fn cancel_trade(ref self: ContractState, trade_id: u64) -> bool {
+            assert(self.trade_statuses.read(trade_id) == TRADE_OPEN, 'Trade not open');
+            
+            // Either counterparty can walk away before settlement
+            let caller = get_caller_address();
+            assert(
+                caller == self.trade_sellers.read(trade_id) || caller == self.trade_buyers.read(trade_id),
+                'Only counterparties can cancel'
+            );
+            
+            self.trade_statuses.write(trade_id, TRADE_CANCELLED);
+            self.emit(TradeCancelled { trade_id });
+            true
+        }
+        
+        fn get_trade(self: @ContractState, trade_id: u64) -> (ContractAddress, u256, ContractAddress, ContractAddress, ContractAddress, u256, u64, u8) {
+            (
+                self.trade_tokens.read(trade_id),
+                self.trade_amounts.read(trade_id),
+                self.trade_sellers.read(trade_id),
+                self.trade_buyers.read(trade_id),
+                self.trade_payment_tokens.read(trade_id),
+                self.trade_prices.read(trade_id),
+                self.trade_expiries.read(trade_id),
+                self.trade_statuses.read(trade_id)
+            )
+        }
+        
+        fn get_trade_count(self: @ContractState) -> u64 {
+            self.trade_count_map.read('trade_count')
+        }
+    }
+}
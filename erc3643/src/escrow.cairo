@@ -0,0 +1,199 @@
+//! Escrow
+//!
+//! Holds tokens pending a condition. The depositor allows this contract to spend the amount
+//! and creates the escrow, which freezes the amount on the depositor's wallet. The escrow is
+//! released to the beneficiary by a token agent or by the attester named at creation (e.g.
+//! an oracle confirming an off-chain event), through `transfer_from` so the transfer passes
+//! compliance at release time. Agents can cancel an escrow at any time and the depositor
+//! once its timeout has passed, which unfreezes the amount.
+//!
+//! The escrow contract must be an agent of the token with the freeze permission.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_contract_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Escrow statuses
+pub const ESCROW_ACTIVE: u8 = 1;
+pub const ESCROW_RELEASED: u8 = 2;
+pub const ESCROW_CANCELLED: u8 = 3;
+
+// Escrow Interface
+#[starknet::interface]
+pub trait IEscrow<TContractState> {
+    fn create_escrow(
+        ref self: TContractState,
+        token: ContractAddress,
+        beneficiary: ContractAddress,
+        amount: u256,
+        timeout: u64,
+        attester: ContractAddress
+    ) -> u64;
+    fn release_escrow(ref self: TContractState, escrow_id: u64) -> bool;
+    fn cancel_escrow(ref self: TContractState, escrow_id: u64) -> bool;
+    fn get_escrow(self: @TContractState, escrow_id: u64) -> (ContractAddress, ContractAddress, ContractAddress, u256, u64, ContractAddress, u8);
+    fn get_escrow_count(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod Escrow {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        EscrowCreated: EscrowCreated,
+        EscrowReleased: EscrowReleased,
+        EscrowCancelled: EscrowCancelled,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct EscrowCreated {
+        #[key]
+        escrow_id: u64,
+        token: ContractAddress,
+        depositor: ContractAddress,
+        beneficiary: ContractAddress,
+        amount: u256,
+        timeout: u64,
+        attester: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct EscrowReleased {
+        #[key]
+        escrow_id: u64,
+        released_by: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct EscrowCancelled {
+        #[key]
+        escrow_id: u64,
+        cancelled_by: ContractAddress,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Escrows, by id starting at 1
+        escrow_count_map: Map<felt252, u64>,  // Using 'escrow_count' as key
+        escrow_tokens: Map<u64, ContractAddress>,
+        escrow_depositors: Map<u64, ContractAddress>,
+        escrow_beneficiaries: Map<u64, ContractAddress>,
+        escrow_amounts: Map<u64, u256>,
+        escrow_timeouts: Map<u64, u64>,
+        escrow_attesters: Map<u64, ContractAddress>,  // Zero address when only agents release
+        escrow_statuses: Map<u64, u8>,
+    }
+    
+    #[abi(embed_v0)]
+    impl EscrowImpl of super::IEscrow<ContractState> {
+        fn create_escrow(
+            ref self: ContractState,
+            token: ContractAddress,
+            beneficiary: ContractAddress,
+            amount: u256,
+            timeout: u64,
+            attester: ContractAddress
+        ) -> u64 {
+            let depositor = get_caller_address();
+            assert(amount > 0, 'Invalid amount');
+            assert(timeout > get_block_timestamp(), 'Invalid timeout');
+            
+            // The amount stays with the depositor, frozen until the escrow settles
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.allowance(depositor, get_contract_address()) >= amount, 'Insufficient allowance');
+            token_dispatcher.freeze_partial_tokens(depositor, amount);
+            
+            let escrow_id = self.escrow_count_map.read('escrow_count') + 1;
+            self.escrow_count_map.write('escrow_count', escrow_id);
+            
+            self.escrow_tokens.write(escrow_id, token);
+            self.escrow_depositors.write(escrow_id, depositor);
+            self.escrow_beneficiaries.write(escrow_id, beneficiary);
+            self.escrow_amounts.write(escrow_id, amount);
+            self.escrow_timeouts.write(escrow_id, timeout);
+            self.escrow_attesters.write(escrow_id, attester);
+            self.escrow_statuses.write(escrow_id, ESCROW_ACTIVE);
+            
+            self.emit(EscrowCreated { escrow_id, token, depositor, beneficiary, amount, timeout, attester });
+            escrow_id
+        }
+        
+        fn release_escrow(ref self: ContractState, escrow_id: u64) -> bool {
+            assert(self.escrow_statuses.read(escrow_id) == ESCROW_ACTIVE, 'Escrow not active');
+            
+            // Token agents or the attester confirm the condition
+            let caller = get_caller_address();
+            let token = self.escrow_tokens.read(escrow_id);
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(
+                caller == self.escrow_attesters.read(escrow_id) || token_dispatcher.is_compliance_agent(caller),
+                'Not allowed to release'
+            );
+            
+            self.escrow_statuses.write(escrow_id, ESCROW_RELEASED);
+            
+            // Unfreeze, then transfer with the usual identity and compliance checks
+            let depositor = self.escrow_depositors.read(escrow_id);
+            let amount = self.escrow_amounts.read(escrow_id);
+            token_dispatcher.unfreeze_partial_tokens(depositor, amount);
+            token_dispatcher.transfer_from(depositor, self.escrow_beneficiaries.read(escrow_id), amount);
+            
+            self.emit(EscrowReleased { escrow_id, released_by: caller });
+            true
+        }
+        
+        fn cancel_escrow(ref self: ContractState, escrow_id: u64) -> bool {
+            assert(self.escrow_statuses.read(escrow_id) == ESCROW_ACTIVE, 'Escrow not active');
+            
+            // Agents can cancel at any time, the depositor gets a refund after the timeout
+            let caller = get_caller_address();
+            let token = self.escrow_tokens.read(escrow_id);
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            let depositor = self.escrow_depositors.read(escrow_id);
+            if !token_dispatcher.is_compliance_agent(caller) {
+                assert(caller == depositor, 'Not allowed to cancel');
+                assert(get_block_timestamp() > self.escrow_timeouts.read(escrow_id), 'Escrow not timed out');
+            }
+            
+            self.escrow_statuses.write(escrow_id, ESCROW_CANCELLED);
+            
+            // Agents may have unfrozen part of the amount in the meantime
+            let amount = self.escrow_amounts.read(escrow_id);
+            let frozen = token_dispatcher.get_frozen_tokens(depositor);
+            let to_unfreeze = if frozen < amount { frozen } else { amount };
+            if to_unfreeze > 0 {
+                token_dispatcher.unfreeze_partial_tokens(depositor, to_unfreeze);
+            }
+            
+            self.emit(EscrowCancelled { escrow_id, cancelled_by: caller });
+            true
+        }
+        
+        fn get_escrow(self: @ContractState, escrow_id: u64) -> (ContractAddress, ContractAddress, ContractAddress, u256, u64, ContractAddress, u8) {
+            (
+                self.escrow_tokens.read(escrow_id),
+                self.escrow_depositors.read(escrow_id),
+                self.escrow_beneficiaries.read(escrow_id),
+                self.escrow_amounts.read(escrow_id),
+                self.escrow_timeouts.read(escrow_id),
+                self.escrow_attesters.read(escrow_id),
+                self.escrow_statuses.read(escrow_id)
+            )
+        }
+        
+        fn get_escrow_count(self: @ContractState) -> u64 {
+            self.escrow_count_map.read('escrow_count')
+        }
+    }
+}
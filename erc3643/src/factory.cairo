@@ -0,0 +1,257 @@
+//! T-REX Factory
+//!
+//! Deploys and wires a complete token suite in a single transaction: claim topics and
+//! trusted issuers registries, identity storage, identity registry, compliance and token,
+//! using the class hashes of the current version of the implementation authority. The
+//! registries are seeded with the suite's claim topics and trusted issuers, the identity
+//! storage is bound to the registry and the compliance to the token, then everything is
+//! handed over to the suite owner. Contract addresses derive from the salt of the suite,
+//! which can only be used once.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress,
+    ClassHash,
+    SyscallResultTrait,
+    get_contract_address,
+    syscalls::deploy_syscall,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::implementation_authority::{
+    IImplementationAuthorityDispatcher,
+    IImplementationAuthorityDispatcherTrait,
+    KIND_TOKEN,
+    KIND_IDENTITY_REGISTRY,
+    KIND_IDENTITY_STORAGE,
+    KIND_COMPLIANCE,
+    KIND_CLAIM_TOPICS_REGISTRY,
+    KIND_TRUSTED_ISSUERS_REGISTRY,
+};
+use crate::claim_topics_registry::{IClaimTopicsRegistryDispatcher, IClaimTopicsRegistryDispatcherTrait};
+use crate::trusted_issuers_registry::{ITrustedIssuersRegistryDispatcher, ITrustedIssuersRegistryDispatcherTrait};
+use crate::identity_storage::{IIdentityStorageDispatcher, IIdentityStorageDispatcherTrait};
+use crate::compliance::{IComplianceDispatcher, IComplianceDispatcherTrait};
+
+// T-REX Factory Interface
+#[starknet::interface]
+pub trait ITREXFactory<TContractState> {
+    fn deploy_suite(
+        ref self: TContractState,
+        salt: felt252,
+        name: felt252,
+        symbol: felt252,
+        suite_owner: ContractAddress,
+        claim_topics: Array<felt252>,
+        trusted_issuers: Array<felt252>,
+        issuer_claim_topics: Array<Array<felt252>>
+    ) -> ContractAddress;
+    fn get_token(self: @TContractState, salt: felt252) -> ContractAddress;
+    fn get_implementation_authority(self: @TContractState) -> ContractAddress;
+    fn set_implementation_authority(ref self: TContractState, implementation_authority: ContractAddress) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod TREXFactory {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        SuiteDeployed: SuiteDeployed,
+        ImplementationAuthoritySet: ImplementationAuthoritySet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct SuiteDeployed {
+        #[key]
+        salt: felt252,
+        #[key]
+        token: ContractAddress,
+        identity_registry: ContractAddress,
+        identity_storage: ContractAddress,
+        compliance: ContractAddress,
+        claim_topics_registry: ContractAddress,
+        trusted_issuers_registry: ContractAddress,
+        suite_owner: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ImplementationAuthoritySet {
+        implementation_authority: ContractAddress,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Source of the class hashes to deploy
+        implementation_authority_map: Map<felt252, ContractAddress>,  // Using 'authority' as key
+        
+        // Token deployed per salt
+        tokens_deployed: Map<felt252, ContractAddress>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        implementation_authority: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.implementation_authority_map.write('authority', implementation_authority);
+    }
+    
+    #[abi(embed_v0)]
+    impl TREXFactoryImpl of super::ITREXFactory<ContractState> {
+        fn deploy_suite(
+            ref self: ContractState,
+            salt: felt252,
+            name: felt252,
+            symbol: felt252,
+            suite_owner: ContractAddress,
+            claim_topics: Array<felt252>,
+            trusted_issuers: Array<felt252>,
+            issuer_claim_topics: Array<Array<felt252>>
+        ) -> ContractAddress {
+            // Only owner can deploy suites
+            self.ownable.assert_only_owner();
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(suite_owner != zero_address, 'Invalid suite owner');
+            assert(self.tokens_deployed.read(salt) == zero_address, 'Salt already used');
+            assert(trusted_issuers.len() == issuer_claim_topics.len(), 'Array length mismatch');
+            
+            // The factory owns the registries, storage and compliance while wiring them
+            let factory = get_contract_address();
+            
+            let claim_topics_registry = self._deploy(KIND_CLAIM_TOPICS_REGISTRY, salt, array![factory.into()]);
+            let ctr_dispatcher = IClaimTopicsRegistryDispatcher { contract_address: claim_topics_registry };
+            for claim_topic in claim_topics {
+                ctr_dispatcher.add_claim_topic(claim_topic);
+            };
+            ctr_dispatcher.transfer_ownership(suite_owner);
+            
+            let trusted_issuers_registry = self._deploy(KIND_TRUSTED_ISSUERS_REGISTRY, salt, array![factory.into()]);
+            let tir_dispatcher = ITrustedIssuersRegistryDispatcher { contract_address: trusted_issuers_registry };
+            let mut i: u32 = 0;
+            for issuer_topics in issuer_claim_topics {
+                tir_dispatcher.add_trusted_issuer(*trusted_issuers.at(i), issuer_topics);
+                i += 1;
+            };
+            tir_dispatcher.transfer_ownership(suite_owner);
+            
+            let identity_storage = self._deploy(KIND_IDENTITY_STORAGE, salt, array![factory.into()]);
+            let identity_registry = self._deploy(
+                KIND_IDENTITY_REGISTRY,
+                salt,
+                array![
+                    suite_owner.into(),
+                    identity_storage.into(),
+                    claim_topics_registry.into(),
+                    trusted_issuers_registry.into()
+                ]
+            );
+            
+            // The storage binds its initial owner as registry, which the factory must not stay
+            let storage_dispatcher = IIdentityStorageDispatcher { contract_address: identity_storage };
+            storage_dispatcher.bind_identity_registry(identity_registry);
+            storage_dispatcher.unbind_identity_registry(factory);
+            storage_dispatcher.transfer_ownership(suite_owner);
+            
+            let compliance = self._deploy(KIND_COMPLIANCE, salt, array![factory.into()]);
+            let token = self._deploy(
+                KIND_TOKEN,
+                salt,
+                array![name, symbol, suite_owner.into(), compliance.into(), identity_registry.into()]
+            );
+            
+            let compliance_dispatcher = IComplianceDispatcher { contract_address: compliance };
+            compliance_dispatcher.bind_token(token);
+            compliance_dispatcher.transfer_ownership(suite_owner);
+            
+            self.tokens_deployed.write(salt, token);
+            
+            self.emit(SuiteDeployed {
+                salt,
+                token,
+                identity_registry,
+                identity_storage,
+                compliance,
+                claim_topics_registry,
+                trusted_issuers_registry,
+                suite_owner,
+            });
+            token
+        }
+        
+        fn get_token(self: @ContractState, salt: felt252) -> ContractAddress {
+            self.tokens_deployed.read(salt)
+        }
+        
+        fn get_implementation_authority(self: @ContractState) -> ContractAddress {
+            self.implementation_authority_map.read('authority')
+        }
+        
+        fn set_implementation_authority(ref self: ContractState, implementation_authority: ContractAddress) -> bool {
+            // Only owner can change where class hashes come from
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(implementation_authority != zero_address, 'Invalid authority address');
+            
+            self.implementation_authority_map.write('authority', implementation_authority);
+            self.emit(ImplementationAuthoritySet { implementation_authority });
+            true
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _deploy(
+            ref self: ContractState,
+            kind: felt252,
+            salt: felt252,
+            calldata: Array<felt252>
+        ) -> ContractAddress {
+            // Class hash of the current version for this kind of contract
+            let authority = IImplementationAuthorityDispatcher {
+                contract_address: self.implementation_authority_map.read('authority')
+            };
+            let class_hash: ClassHash = authority.get_implementation(kind);
+            let class_hash_felt: felt252 = class_hash.into();
+            assert(class_hash_felt != 0, 'Implementation not set');
+            
+            let (address, _) = deploy_syscall(class_hash, salt, calldata.span(), false).unwrap_syscall();
+            address
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! T-REX Gateway
+//!
+//! Front door of a platform's T-REX factory, which it must own. The gateway owner
+//! approves deployers, who can deploy suites for any owner, and can open deployment to
+//! the public, in which case anyone can deploy a suite they own themselves. A deployment
+//! fee, paid in an ERC20 token to the fee collector, can be charged and discounted per
+//! deployer.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::factory::{ITREXFactoryDispatcher, ITREXFactoryDispatcherTrait};
+use crate::payments;
+
+// Fee discounts are expressed in basis points
+pub const BASIS_POINTS: u16 = 10000;
+
+// T-REX Gateway Interface
+#[starknet::interface]
+pub trait ITREXGateway<TContractState> {
+    fn deploy_suite(
+        ref self: TContractState,
+        salt: felt252,
+        name: felt252,
+        symbol: felt252,
+        suite_owner: ContractAddress,
+        claim_topics: Array<felt252>,
+        trusted_issuers: Array<felt252>,
+        issuer_claim_topics: Array<Array<felt252>>
+    ) -> ContractAddress;
+    fn add_deployer(ref self: TContractState, deployer: ContractAddress) -> bool;
+    fn remove_deployer(ref self: TContractState, deployer: ContractAddress) -> bool;
+    fn is_deployer(self: @TContractState, deployer: ContractAddress) -> bool;
+    fn set_public_deployment_status(ref self: TContractState, is_enabled: bool) -> bool;
+    fn get_public_deployment_status(self: @TContractState) -> bool;
+    fn set_deployment_fee(
+        ref self: TContractState,
+        fee: u256,
+        fee_token: ContractAddress,
+        fee_collector: ContractAddress
+    ) -> bool;
+    fn enable_deployment_fee(ref self: TContractState, is_enabled: bool) -> bool;
+    fn is_deployment_fee_enabled(self: @TContractState) -> bool;
+    fn get_deployment_fee(self: @TContractState) -> (u256, ContractAddress, ContractAddress);
+    fn apply_fee_discount(ref self: TContractState, deployer: ContractAddress, discount_bps: u16) -> bool;
+    fn calculate_fee(self: @TContractState, deployer: ContractAddress) -> u256;
+    fn get_factory(self: @TContractState) -> ContractAddress;
+    fn transfer_factory_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod TREXGateway {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        DeployerAdded: DeployerAdded,
+        DeployerRemoved: DeployerRemoved,
+        PublicDeploymentStatusSet: PublicDeploymentStatusSet,
+        DeploymentFeeSet: DeploymentFeeSet,
+        DeploymentFeeEnabled: DeploymentFeeEnabled,
+        FeeDiscountApplied: FeeDiscountApplied,
+        GatewaySuiteDeployed: GatewaySuiteDeployed,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DeployerAdded {
+        #[key]
+        deployer: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DeployerRemoved {
+        #[key]
+        deployer: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PublicDeploymentStatusSet {
+        is_enabled: bool,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DeploymentFeeSet {
+        fee: u256,
+        fee_token: ContractAddress,
+        fee_collector: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DeploymentFeeEnabled {
+        is_enabled: bool,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct FeeDiscountApplied {
+        #[key]
+        deployer: ContractAddress,
+        discount_bps: u16,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct GatewaySuiteDeployed {
+        #[key]
+        deployer: ContractAddress,
+        #[key]
+        token: ContractAddress,
+        fee_paid: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Factory owned by this gateway
+        factory_map: Map<felt252, ContractAddress>,  // Using 'factory' as key
+        
+        // Deployment policy, using 'public_deployment' and 'fee_enabled' as keys
+        status_map: Map<felt252, bool>,
+        deployers: Map<ContractAddress, bool>,
+        
+        // Deployment fee, using 'fee_token' and 'fee_collector' as keys
+        fee_map: Map<felt252, u256>,  // Using 'fee' as key
+        fee_address_map: Map<felt252, ContractAddress>,
+        fee_discounts: Map<ContractAddress, u16>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        factory: ContractAddress,
+        public_deployment: bool
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.factory_map.write('factory', factory);
+        self.status_map.write('public_deployment', public_deployment);
+    }
+    
+    #[abi(embed_v0)]
+    impl TREXGatewayImpl of super::ITREXGateway<ContractState> {
+        fn deploy_suite(
+            ref self: ContractState,
+            salt: felt252,
+            name: felt252,
+            symbol: felt252,
+            suite_owner: ContractAddress,
+            claim_topics: Array<felt252>,
+            trusted_issuers: Array<felt252>,
+            issuer_claim_topics: Array<Array<felt252>>
+        ) -> ContractAddress {
+            // Approved deployers deploy for anyone, the public only for themselves
+            let deployer = get_caller_address();
+            if !self.deployers.read(deployer) {
+                assert(self.status_map.read('public_deployment'), 'Public deployment disabled');
+                assert(suite_owner == deployer, 'Public deployer must own suite');
+            }
+            
+            let fee_paid = self.calculate_fee(deployer);
+            if fee_paid > 0 {
+                payments::pay_from(
+                    self.fee_address_map.read('fee_token'),
+                    deployer,
+                    self.fee_address_map.read('fee_collector'),
+                    fee_paid
+                );
+            }
+            
+            let factory = ITREXFactoryDispatcher { contract_address: self.factory_map.read('factory') };
+            let token = factory.deploy_suite(
+                salt, name, symbol, suite_owner, claim_topics, trusted_issuers, issuer_claim_topics
+            );
+            
+            self.emit(GatewaySuiteDeployed { deployer, token, fee_paid });
+            token
+        }
+        
+        fn add_deployer(ref self: ContractState, deployer: ContractAddress) -> bool {
+            // Only owner can approve deployers
+            self.ownable.assert_only_owner();
+            assert(!self.deployers.read(deployer), 'Deployer already exists');
+            
+            self.deployers.write(deployer, true);
+            self.emit(DeployerAdded { deployer });
+            true
+        }
+        
+        fn remove_deployer(ref self: ContractState, deployer: ContractAddress) -> bool {
+            // Only owner can revoke deployers
+            self.ownable.assert_only_owner();
+            assert(self.deployers.read(deployer), 'Deployer does not exist');
+            
+            self.deployers.write(deployer, false);
+            self.emit(DeployerRemoved { deployer });
+            true
+        }
+        
+        fn is_deployer(self: @ContractState, deployer: ContractAddress) -> bool {
+            self.deployers.read(deployer)
+        }
+        
+        fn set_public_deployment_status(ref self: ContractState, is_enabled: bool) -> bool {
+            // Only owner can open or close public deployment
+            self.ownable.assert_only_owner();
+            
+            self.status_map.write('public_deployment', is_enabled);
+            self.emit(PublicDeploymentStatusSet { is_enabled });
+            true
+        }
+        
+        fn get_public_deployment_status(self: @ContractState) -> bool {
+            self.status_map.read('public_deployment')
+        }
+        
+        fn set_deployment_fee(
+            ref self: ContractState,
+            fee: u256,
+            fee_token: ContractAddress,
+            fee_collector: ContractAddress
+        ) -> bool {
+            // Only owner can set the fee
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(fee_token != zero_address, 'Invalid fee token');
+            assert(fee_collector != zero_address, 'Invalid fee collector');
+            
+            self.fee_map.write('fee', fee);
+            self.fee_address_map.write('fee_token', fee_token);
+            self.fee_address_map.write('fee_collector', fee_collector);
+            
+            self.emit(DeploymentFeeSet { fee, fee_token, fee_collector });
+            true
+        }
+        
+        fn enable_deployment_fee(ref self: ContractState, is_enabled: bool) -> bool {
+            // Only owner can toggle the fee, which must be configured first
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(!is_enabled || self.fee_address_map.read('fee_token') != zero_address, 'Deployment fee not set');
+            
+            self.status_map.write('fee_enabled', is_enabled);
+            self.emit(DeploymentFeeEnabled { is_enabled });
+            true
+        }
+        
+        fn is_deployment_fee_enabled(self: @ContractState) -> bool {
+            self.status_map.read('fee_enabled')
+        }
+        
+        fn get_deployment_fee(self: @ContractState) -> (u256, ContractAddress, ContractAddress) {
+            (
+                self.fee_map.read('fee'),
+                self.fee_address_map.read('fee_token'),
+                self.fee_address_map.read('fee_collector')
+            )
+        }
+        
+        fn apply_fee_discount(ref self: ContractState, deployer: ContractAddress, discount_bps: u16) -> bool {
+            // Only owner can grant discounts
+            self.ownable.assert_only_owner();
+            assert(discount_bps <= BASIS_POINTS, 'Invalid discount');
+            
+            self.fee_discounts.write(deployer, discount_bps);
+            self.emit(FeeDiscountApplied { deployer, discount_bps });
+            true
+        }
+        
+        fn calculate_fee(self: @ContractState, deployer: ContractAddress) -> u256 {
+            if !self.status_map.read('fee_enabled') {
+                return 0;
+            }
+            
+            let fee = self.fee_map.read('fee');
+            let discount: u256 = self.fee_discounts.read(deployer).into();
+            fee - fee * discount / BASIS_POINTS.into()
+        }
+        
+        fn get_factory(self: @ContractState) -> ContractAddress {
+            self.factory_map.read('factory')
+        }
+        
+        fn transfer_factory_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            // Only owner can release the factory, e.g. to retire the gateway
+            self.ownable.assert_only_owner();
+            
+            let factory = ITREXFactoryDispatcher { contract_address: self.factory_map.read('factory') };
+            factory.transfer_ownership(new_owner)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+}
@@ -1,13 +1,22 @@
 // Import OpenZeppelin's components
 use openzeppelin::access::ownable::OwnableComponent;
 use openzeppelin::access::ownable::OwnableComponent::InternalTrait as OwnableInternalTrait;
+use openzeppelin::upgrades::UpgradeableComponent;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     get_caller_address,
     storage::StorageMapReadAccess,
     storage::StorageMapWriteAccess,
 };
 use core::array::ArrayTrait;
+use crate::country::is_valid_country;
+use core::poseidon::poseidon_hash_span;
+use core::keccak::compute_keccak_byte_array;
+use core::integer::u128_byte_reverse;
+use starknet::EthAddress;
+use starknet::eth_signature::verify_eth_signature;
+use starknet::secp256_trait::signature_from_vrs;
 
 // Identity Registry Interface
 #[starknet::interface]
@@ -26,6 +35,22 @@ pub trait IIdentityRegistry<TContractState> {
     
     // Identity management functions
     fn register_identity(ref self: TContractState, user_address: ContractAddress, identity: felt252, country: felt252) -> bool;
+    fn batch_register_identity(
+        ref self: TContractState,
+        user_addresses: Array<ContractAddress>,
+        identities: Array<felt252>,
+        countries: Array<felt252>
+    ) -> bool;
+    fn register_identity_with_claims(
+        ref self: TContractState,
+        user_address: ContractAddress,
+        identity: felt252,
+        country: felt252,
+        claim_topics: Array<felt252>,
+        claim_data: Array<felt252>,
+        issuers: Array<felt252>,
+        signatures: Array<Array<felt252>>
+    ) -> bool;
     fn update_identity(ref self: TContractState, user_address: ContractAddress, identity: felt252) -> bool;
     fn update_country(ref self: TContractState, user_address: ContractAddress, country: felt252) -> bool;
     fn delete_identity(ref self: TContractState, user_address: ContractAddress) -> bool;
@@ -34,9 +59,73 @@ pub trait IIdentityRegistry<TContractState> {
     fn set_trusted_issuers_registry(ref self: TContractState, trusted_issuers_registry: ContractAddress) -> bool;
     fn get_identity(self: @TContractState, user_address: ContractAddress) -> felt252;
     fn get_country(self: @TContractState, user_address: ContractAddress) -> felt252;
+    fn update_investor_category(ref self: TContractState, user_address: ContractAddress, category: u8) -> bool;
+    fn get_investor_category(self: @TContractState, user_address: ContractAddress) -> u8;
+    fn set_kyc_valid_until(ref self: TContractState, user_address: ContractAddress, valid_until: u64) -> bool;
+    fn get_kyc_valid_until(self: @TContractState, user_address: ContractAddress) -> u64;
+    fn set_identity_metadata_uri(ref self: TContractState, identity: felt252, metadata_uri: ByteArray) -> bool;
+    fn get_identity_metadata_uri(self: @TContractState, identity: felt252) -> ByteArray;
     fn is_verified_address(self: @TContractState, user_address: ContractAddress) -> bool;
     fn is_identity_verified(self: @TContractState, identity: felt252) -> bool;
     fn identity_exists(self: @TContractState, identity: felt252) -> bool;
+    
+    // Pause of agent updates, e.g. during an incident
+    fn set_updates_paused(ref self: TContractState, paused: bool) -> bool;
+    fn are_updates_paused(self: @TContractState) -> bool;
+    
+    // Temporary suspension, keeping claims and country data
+    fn suspend_identity(ref self: TContractState, identity: felt252) -> bool;
+    fn unsuspend_identity(ref self: TContractState, identity: felt252) -> bool;
+    fn is_identity_suspended(self: @TContractState, identity: felt252) -> bool;
+    
+    // Cached verification status, trusted by transfers for up to the max age
+    fn refresh_verification(ref self: TContractState, identity: felt252) -> bool;
+    fn get_cached_verification(self: @TContractState, identity: felt252) -> (bool, u64);
+    fn set_verification_max_age(ref self: TContractState, max_age: u64) -> bool;
+    fn get_verification_max_age(self: @TContractState) -> u64;
+    
+    // Claims issued by trusted issuers
+    fn add_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, data: felt252) -> bool;
+    fn add_signed_claim(
+        ref self: TContractState,
+        identity: felt252,
+        claim_topic: felt252,
+        data: felt252,
+        issuer: felt252,
+        signature: Array<felt252>
+    ) -> bool;
+    fn add_eth_signed_claim(
+        ref self: TContractState,
+        identity: felt252,
+        claim_topic: felt252,
+        data: ByteArray,
+        issuer: felt252,
+        v: u32,
+        r: u256,
+        s: u256
+    ) -> bool;
+    fn remove_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> bool;
+    fn get_claim(self: @TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> (bool, felt252);
+    fn claim_hash(self: @TContractState, identity: felt252, claim_topic: felt252, data: felt252, issuer: felt252) -> felt252;
+    fn get_claim_nonce(self: @TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> u64;
+    fn eth_claim_hash(self: @TContractState, identity: felt252, claim_topic: felt252, data: ByteArray) -> u256;
+    
+    // ONCHAINID identity contract whose Ethereum-signed claims apply to an identity
+    fn set_onchain_id(ref self: TContractState, identity: felt252, onchain_id: EthAddress) -> bool;
+    fn get_onchain_id(self: @TContractState, identity: felt252) -> EthAddress;
+    fn revoke_eth_claim(ref self: TContractState, digest: u256) -> bool;
+    fn is_eth_claim_revoked(self: @TContractState, digest: u256) -> bool;
+    
+    // Multi-wallet identity functions
+    fn link_wallet(ref self: TContractState, existing_wallet: ContractAddress, new_wallet: ContractAddress) -> bool;
+    fn unlink_wallet(ref self: TContractState, wallet: ContractAddress) -> bool;
+    fn get_linked_wallets(self: @TContractState, user_address: ContractAddress) -> Array<ContractAddress>;
+    fn rotate_wallet(ref self: TContractState, old_wallet: ContractAddress, new_wallet: ContractAddress) -> bool;
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 // Identity Storage Interface
@@ -48,13 +137,30 @@ trait IIdentityStorageContract<TContractState> {
     fn delete_identity(ref self: TContractState, user_address: ContractAddress) -> bool;
     fn get_identity(self: @TContractState, user_address: ContractAddress) -> felt252;
     fn get_country(self: @TContractState, user_address: ContractAddress) -> felt252;
+    fn get_expiration_date(self: @TContractState, user_address: ContractAddress) -> u64;
+    fn set_expiration_date(ref self: TContractState, user_address: ContractAddress, expiration_date: u64) -> bool;
+    fn get_investor_category(self: @TContractState, user_address: ContractAddress) -> u8;
+    fn update_investor_category(ref self: TContractState, user_address: ContractAddress, category: u8) -> bool;
+    fn add_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, issuer: felt252, data: felt252) -> bool;
+    fn remove_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> bool;
+    fn get_claim(self: @TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> (bool, felt252);
+    fn get_claim_epoch(self: @TContractState, identity: felt252) -> u64;
+    fn set_metadata_uri(ref self: TContractState, identity: felt252, metadata_uri: ByteArray) -> bool;
+    fn get_metadata_uri(self: @TContractState, identity: felt252) -> ByteArray;
     fn get_addresses_by_identity(self: @TContractState, identity: felt252) -> Array<ContractAddress>;
 }
 
+// Issuer account interface (SRC6)
+#[starknet::interface]
+trait IAccountContract<TContractState> {
+    fn is_valid_signature(self: @TContractState, hash: felt252, signature: Array<felt252>) -> felt252;
+}
+
 // Claim Topics Registry Interface
 #[starknet::interface]
 trait IClaimTopicsRegistryContract<TContractState> {
     fn get_claim_topics(self: @TContractState) -> Array<felt252>;
+    fn get_claim_topics_epoch(self: @TContractState) -> u64;
 }
 
 // Trusted Issuers Registry Interface
@@ -64,6 +170,8 @@ trait ITrustedIssuersRegistryContract<TContractState> {
     fn get_issuer_claim_topics(self: @TContractState, issuer: felt252) -> Array<felt252>;
     fn is_trusted_issuer(self: @TContractState, issuer: felt252) -> bool;
     fn has_claim_topic(self: @TContractState, issuer: felt252, claim_topic: felt252) -> bool;
+    fn get_trusted_issuers_for_claim_topic(self: @TContractState, claim_topic: felt252) -> Array<felt252>;
+    fn get_trusted_issuers_epoch(self: @TContractState) -> u64;
 }
 
 #[starknet::contract]
@@ -72,10 +180,12 @@ pub mod IdentityRegistry {
     
     // Component declarations
     component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
     
     // Implement component interfaces
     // We're removing abi(embed_v0) to avoid duplicate entry points in testing
     impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Events
     #[event]
@@ -83,13 +193,30 @@ pub mod IdentityRegistry {
     enum Event {
         #[flat]
         OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
         IdentityRegistered: IdentityRegistered,
         IdentityUpdated: IdentityUpdated,
         CountryUpdated: CountryUpdated,
+        InvestorCategoryUpdated: InvestorCategoryUpdated,
         IdentityRemoved: IdentityRemoved,
         IdentityStorageSet: IdentityStorageSet,
         ClaimTopicsRegistrySet: ClaimTopicsRegistrySet,
         TrustedIssuersRegistrySet: TrustedIssuersRegistrySet,
+        WalletLinked: WalletLinked,
+        WalletUnlinked: WalletUnlinked,
+        WalletRotated: WalletRotated,
+        ClaimAdded: ClaimAdded,
+        ClaimRemoved: ClaimRemoved,
+        OnchainIdSet: OnchainIdSet,
+        EthClaimRevoked: EthClaimRevoked,
+        KycValidUntilUpdated: KycValidUntilUpdated,
+        UpdatesPausedSet: UpdatesPausedSet,
+        IdentitySuspended: IdentitySuspended,
+        IdentityUnsuspended: IdentityUnsuspended,
+        VerificationRefreshed: VerificationRefreshed,
+        VerificationMaxAgeSet: VerificationMaxAgeSet,
+        StorageMigrated: StorageMigrated,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -110,6 +237,12 @@ pub mod IdentityRegistry {
         country: felt252,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct InvestorCategoryUpdated {
+        user_address: ContractAddress,
+        category: u8,
+    }
+    
     #[derive(Drop, starknet::Event)]
     struct IdentityRemoved {
         user_address: ContractAddress,
@@ -130,12 +263,108 @@ pub mod IdentityRegistry {
         trusted_issuers_registry: ContractAddress,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct WalletLinked {
+        identity: felt252,
+        wallet: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct WalletUnlinked {
+        identity: felt252,
+        wallet: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct WalletRotated {
+        identity: felt252,
+        old_wallet: ContractAddress,
+        new_wallet: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ClaimAdded {
+        #[key]
+        identity: felt252,
+        #[key]
+        claim_topic: felt252,
+        issuer: felt252,
+        data: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ClaimRemoved {
+        #[key]
+        identity: felt252,
+        #[key]
+        claim_topic: felt252,
+        issuer: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct OnchainIdSet {
+        #[key]
+        identity: felt252,
+        onchain_id: EthAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct EthClaimRevoked {
+        digest: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct KycValidUntilUpdated {
+        #[key]
+        identity: felt252,
+        valid_until: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct UpdatesPausedSet {
+        paused: bool,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct IdentitySuspended {
+        #[key]
+        identity: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct IdentityUnsuspended {
+        #[key]
+        identity: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VerificationRefreshed {
+        #[key]
+        identity: felt252,
+        verified: bool,
+        verified_at: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VerificationMaxAgeSet {
+        max_age: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
         // Component storage
         #[substorage(v0)]
         ownable: OwnableComponent::Storage,
         
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
         // Custom role management (simplified from AccessControl)
         roles_map: starknet::storage::Map::<(felt252, ContractAddress), bool>, // (role, account) => has_role
         
@@ -143,8 +372,35 @@ pub mod IdentityRegistry {
         identity_storage_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'identity_storage' as key
         claim_topics_registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'claim_topics_registry' as key
         trusted_issuers_registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'trusted_issuers_registry' as key
+        
+        // Whether agent updates are paused
+        updates_paused_map: starknet::storage::Map::<felt252, bool>,  // Using 'paused' as key
+        
+        // Identities suspended by agents, e.g. pending an investigation
+        suspended_map: starknet::storage::Map::<felt252, bool>,
+        
+        // Verification results cached by `refresh_verification`, 0 timestamp when not cached
+        verified_map: starknet::storage::Map::<felt252, bool>,
+        verified_at_map: starknet::storage::Map::<felt252, u64>,
+        verified_epoch_map: starknet::storage::Map::<felt252, felt252>,  // See `_verification_epoch`
+        verification_max_age_map: starknet::storage::Map::<felt252, u64>,  // Using 'max_age' as key, 0 disables the cache
+        
+        // Bumped on every change of a claim, (identity, topic, issuer) => nonce. Signed
+        // claims cover the current nonce, so a removed claim cannot be replayed.
+        claim_nonces: starknet::storage::Map::<(felt252, felt252, felt252), u64>,
+        
+        // Ethereum-signed claims: the ONCHAINID address linked to each identity, the digest
+        // each claim was added with and the digests that can no longer be submitted
+        onchain_ids: starknet::storage::Map::<felt252, EthAddress>,
+        eth_claim_digests: starknet::storage::Map::<(felt252, felt252, felt252), u256>,
+        revoked_eth_claims: starknet::storage::Map::<u256, bool>,
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: starknet::storage::Map::<felt252, u8>,  // Using 'version' as key
     }
     
+    const STORAGE_VERSION: u8 = 1;
+    
     // Constants
     const AGENT_ROLE: felt252 = selector!("AGENT_ROLE");
     const DEFAULT_ADMIN_ROLE: felt252 = 0;
@@ -172,6 +428,9 @@ pub mod IdentityRegistry {
         self.identity_storage_map.write('identity_storage', identity_storage);
         self.claim_topics_registry_map.write('claim_topics_registry', claim_topics_registry);
         self.trusted_issuers_registry_map.write('trusted_issuers_registry', trusted_issuers_registry);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
@@ -234,6 +493,7 @@ pub mod IdentityRegistry {
         fn register_identity(ref self: ContractState, user_address: ContractAddress, identity: felt252, country: felt252) -> bool {
             // Only agent can register identity
             self._assert_only_agent();
+            assert(is_valid_country(country), 'Invalid country code');
             
             // Call identity storage contract
             let identity_storage = self.get_identity_storage();
@@ -246,6 +506,78 @@ pub mod IdentityRegistry {
             true
         }
         
+        fn register_identity_with_claims(
+            ref self: ContractState,
+            user_address: ContractAddress,
+            identity: felt252,
+            country: felt252,
+            claim_topics: Array<felt252>,
+            claim_data: Array<felt252>,
+            issuers: Array<felt252>,
+            signatures: Array<Array<felt252>>
+        ) -> bool {
+            // Agent-only registration, checked by register_identity
+            self.register_identity(user_address, identity, country);
+            
+            assert(claim_topics.len() == claim_data.len(), 'Array length mismatch');
+            assert(claim_topics.len() == issuers.len(), 'Array length mismatch');
+            assert(claim_topics.len() == signatures.len(), 'Array length mismatch');
+            
+            // Each claim must carry its issuer's signature, one bad claim reverts the onboarding
+            let mut i: u32 = 0;
+            loop {
+                if i >= claim_topics.len() {
+                    break;
+                }
+                
+                let claim_topic = *claim_topics.at(i);
+                let data = *claim_data.at(i);
+                let issuer = *issuers.at(i);
+                self._assert_valid_issuer_signature(identity, claim_topic, data, issuer, signatures.at(i).clone());
+                self._add_claim(identity, claim_topic, issuer, data);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn batch_register_identity(
+            ref self: ContractState,
+            user_addresses: Array<ContractAddress>,
+            identities: Array<felt252>,
+            countries: Array<felt252>
+        ) -> bool {
+            // Only agent can register identities
+            self._assert_only_agent();
+            assert(user_addresses.len() == identities.len(), 'Array length mismatch');
+            assert(user_addresses.len() == countries.len(), 'Array length mismatch');
+            
+            let identity_storage = self.get_identity_storage();
+            let storage_dispatcher = super::IIdentityStorageContractDispatcher { contract_address: identity_storage };
+            
+            // All or nothing, one invalid entry reverts the whole batch
+            let mut i: u32 = 0;
+            loop {
+                if i >= user_addresses.len() {
+                    break;
+                }
+                
+                let user_address = *user_addresses.at(i);
+                let identity = *identities.at(i);
+                let country = *countries.at(i);
+                assert(is_valid_country(country), 'Invalid country code');
+                storage_dispatcher.register_identity(user_address, identity, country);
+                
+                // Emit event
+                self.emit(IdentityRegistered { user_address, identity });
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
         fn update_identity(ref self: ContractState, user_address: ContractAddress, identity: felt252) -> bool {
             // Only agent can update identity
             self._assert_only_agent();
@@ -264,6 +596,7 @@ pub mod IdentityRegistry {
         fn update_country(ref self: ContractState, user_address: ContractAddress, country: felt252) -> bool {
             // Only agent can update country
             self._assert_only_agent();
+            assert(is_valid_country(country), 'Invalid country code');
             
             // Call identity storage contract
             let identity_storage = self.get_identity_storage();
@@ -276,6 +609,21 @@ pub mod IdentityRegistry {
             true
         }
         
+        fn update_investor_category(ref self: ContractState, user_address: ContractAddress, category: u8) -> bool {
+            // Only agent can update the investor category
+            self._assert_only_agent();
+            
+            // Call identity storage contract
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .update_investor_category(user_address, category);
+            
+            // Emit event
+            self.emit(InvestorCategoryUpdated { user_address, category });
+            
+            true
+        }
+        
         fn delete_identity(ref self: ContractState, user_address: ContractAddress) -> bool {
             // Only agent can delete identity
             self._assert_only_agent();
@@ -342,6 +690,62 @@ pub mod IdentityRegistry {
                 .get_country(user_address)
         }
         
+        fn set_kyc_valid_until(ref self: ContractState, user_address: ContractAddress, valid_until: u64) -> bool {
+            // Only agent can record a re-verification
+            self._assert_only_agent();
+            
+            let identity_storage = self.get_identity_storage();
+            let storage_dispatcher = super::IIdentityStorageContractDispatcher { contract_address: identity_storage };
+            let identity = storage_dispatcher.get_identity(user_address);
+            assert(identity != 0, 'Identity does not exist');
+            
+            // KYC belongs to the investor, update every linked wallet
+            let wallets = storage_dispatcher.get_addresses_by_identity(identity);
+            let mut i: u32 = 0;
+            loop {
+                if i >= wallets.len() {
+                    break;
+                }
+                
+                storage_dispatcher.set_expiration_date(*wallets.at(i), valid_until);
+                
+                i += 1;
+            };
+            
+            // Emit event
+            self.emit(KycValidUntilUpdated { identity, valid_until });
+            
+            true
+        }
+        
+        fn set_identity_metadata_uri(ref self: ContractState, identity: felt252, metadata_uri: ByteArray) -> bool {
+            // Only agent can point to KYC documents
+            self._assert_only_agent();
+            
+            // Call identity storage contract, which emits the update
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .set_metadata_uri(identity, metadata_uri)
+        }
+        
+        fn get_identity_metadata_uri(self: @ContractState, identity: felt252) -> ByteArray {
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_metadata_uri(identity)
+        }
+        
+        fn get_kyc_valid_until(self: @ContractState, user_address: ContractAddress) -> u64 {
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_expiration_date(user_address)
+        }
+        
+        fn get_investor_category(self: @ContractState, user_address: ContractAddress) -> u8 {
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_investor_category(user_address)
+        }
+        
         fn is_verified_address(self: @ContractState, user_address: ContractAddress) -> bool {
             // Get identity for the address
             let identity = self.get_identity(user_address);
@@ -351,7 +755,27 @@ pub mod IdentityRegistry {
                 return false;
             }
             
-            // Check if identity has valid claims
+            // Lapsed KYC, 0 means no expiry
+            let valid_until = self.get_kyc_valid_until(user_address);
+            if valid_until != 0 && valid_until <= starknet::get_block_timestamp() {
+                return false;
+            }
+            
+            if self.suspended_map.read(identity) {
+                return false;
+            }
+            
+            // Trust a fresh cached result computed against the current topics, issuers and
+            // claims, otherwise check the claims
+            let max_age = self.verification_max_age_map.read('max_age');
+            let verified_at = self.verified_at_map.read(identity);
+            if max_age != 0
+                && verified_at != 0
+                && starknet::get_block_timestamp() - verified_at <= max_age
+                && self.verified_epoch_map.read(identity) == self._verification_epoch(identity) {
+                return self.verified_map.read(identity);
+            }
+            
             self.is_identity_verified(identity)
         }
         
@@ -361,6 +785,11 @@ pub mod IdentityRegistry {
                 return false;
             }
             
+            // Suspended identities keep their claims but fail verification
+            if self.suspended_map.read(identity) {
+                return false;
+            }
+            
             // Get required claim topics
             let claim_topics_registry = self.get_claim_topics_registry();
             let required_claim_topics = super::IClaimTopicsRegistryContractDispatcher { contract_address: claim_topics_registry }
@@ -371,62 +800,121 @@ pub mod IdentityRegistry {
                 return true;
             }
             
-            // Get trusted issuers
+            // Every required topic needs a claim from an issuer trusted for that topic
             let trusted_issuers_registry = self.get_trusted_issuers_registry();
-            let trusted_issuers = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry }
-                .get_trusted_issuers();
-            
-            if trusted_issuers.len() == 0 {
-                // If no trusted issuers, identity is not verified
-                return false;
-            }
-            
-            // For each trusted issuer, check if they have issued claims for all required topics
-            let mut i: usize = 0;
-            let issuers_len = trusted_issuers.len();
+            let identity_storage = self.get_identity_storage();
+            let mut j: usize = 0;
+            let required_topics_len = required_claim_topics.len();
             
             loop {
-                if i >= issuers_len {
+                if j >= required_topics_len {
                     break;
                 }
                 
-                let issuer = *trusted_issuers.at(i);
-                // We need to check each required topic individually, so we don't need to store all issuer claim topics
-                // Using underscore prefix to indicate intentionally unused variable
-                let _issuer_claim_topics = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry }
-                    .get_issuer_claim_topics(issuer);
+                let topic = *required_claim_topics.at(j);
+                let mut has_claim = false;
+                let mut i: usize = 0;
                 
-                // Check if issuer has all required claim topics
-                let mut has_all_topics = true;
-                let mut j: usize = 0;
-                let required_topics_len = required_claim_topics.len();
+                // Only the issuers trusted for this topic can cover it
+                let topic_issuers = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry }
+                    .get_trusted_issuers_for_claim_topic(topic);
+                let issuers_len = topic_issuers.len();
                 
                 loop {
-                    if j >= required_topics_len {
+                    if i >= issuers_len {
                         break;
                     }
                     
-                    let topic = *required_claim_topics.at(j);
-                    let has_topic = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry }
-                        .has_claim_topic(issuer, topic);
-                    
-                    if !has_topic {
-                        has_all_topics = false;
+                    let issuer = *topic_issuers.at(i);
+                    let (claim_exists, _) = super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                        .get_claim(identity, topic, issuer);
+                    if claim_exists {
+                        has_claim = true;
                         break;
                     }
                     
-                    j += 1;
+                    i += 1;
                 };
                 
-                if has_all_topics {
-                    return true; // Found an issuer with all required topics
+                if !has_claim {
+                    return false; // Missing claim for a required topic
                 }
                 
-                i += 1;
+                j += 1;
             };
             
-            // No trusted issuer has all required claim topics for this identity
-            false
+            // All required topics are covered by trusted claims
+            true
+        }
+        
+        fn set_updates_paused(ref self: ContractState, paused: bool) -> bool {
+            // Only owner can pause registry updates
+            self.ownable.assert_only_owner();
+            
+            self.updates_paused_map.write('paused', paused);
+            self.emit(UpdatesPausedSet { paused });
+            true
+        }
+        
+        fn are_updates_paused(self: @ContractState) -> bool {
+            self.updates_paused_map.read('paused')
+        }
+        
+        fn suspend_identity(ref self: ContractState, identity: felt252) -> bool {
+            // Only agent can suspend identities
+            self._assert_only_agent();
+            
+            assert(self.identity_exists(identity), 'Identity does not exist');
+            assert(!self.suspended_map.read(identity), 'Identity already suspended');
+            
+            self.suspended_map.write(identity, true);
+            self.emit(IdentitySuspended { identity });
+            true
+        }
+        
+        fn unsuspend_identity(ref self: ContractState, identity: felt252) -> bool {
+            // Only agent can reinstate identities
+            self._assert_only_agent();
+            
+            assert(self.suspended_map.read(identity), 'Identity not suspended');
+            
+            self.suspended_map.write(identity, false);
+            self._invalidate_verification(identity);
+            self.emit(IdentityUnsuspended { identity });
+            true
+        }
+        
+        fn is_identity_suspended(self: @ContractState, identity: felt252) -> bool {
+            self.suspended_map.read(identity)
+        }
+        
+        fn refresh_verification(ref self: ContractState, identity: felt252) -> bool {
+            // Anyone can pay for the full check, the result only depends on the claims
+            let verified = self.is_identity_verified(identity);
+            let verified_at = starknet::get_block_timestamp();
+            self.verified_map.write(identity, verified);
+            self.verified_at_map.write(identity, verified_at);
+            self.verified_epoch_map.write(identity, self._verification_epoch(identity));
+            
+            self.emit(VerificationRefreshed { identity, verified, verified_at });
+            verified
+        }
+        
+        fn get_cached_verification(self: @ContractState, identity: felt252) -> (bool, u64) {
+            (self.verified_map.read(identity), self.verified_at_map.read(identity))
+        }
+        
+        fn set_verification_max_age(ref self: ContractState, max_age: u64) -> bool {
+            // Only owner can choose how stale a cached result may be
+            self.ownable.assert_only_owner();
+            
+            self.verification_max_age_map.write('max_age', max_age);
+            self.emit(VerificationMaxAgeSet { max_age });
+            true
+        }
+        
+        fn get_verification_max_age(self: @ContractState) -> u64 {
+            self.verification_max_age_map.read('max_age')
         }
         
         fn identity_exists(self: @ContractState, identity: felt252) -> bool {
@@ -437,6 +925,248 @@ pub mod IdentityRegistry {
                 
             addresses.len() > 0
         }
+        
+        fn add_claim(ref self: ContractState, identity: felt252, claim_topic: felt252, data: felt252) -> bool {
+            // The caller account is the issuer
+            let issuer: felt252 = get_caller_address().into();
+            self._add_claim(identity, claim_topic, issuer, data);
+            true
+        }
+        
+        fn add_signed_claim(
+            ref self: ContractState,
+            identity: felt252,
+            claim_topic: felt252,
+            data: felt252,
+            issuer: felt252,
+            signature: Array<felt252>
+        ) -> bool {
+            // Anyone (typically the identity owner) can submit a claim signed by the issuer account
+            self._assert_valid_issuer_signature(identity, claim_topic, data, issuer, signature);
+            
+            self._add_claim(identity, claim_topic, issuer, data);
+            true
+        }
+        
+        fn add_eth_signed_claim(
+            ref self: ContractState,
+            identity: felt252,
+            claim_topic: felt252,
+            data: ByteArray,
+            issuer: felt252,
+            v: u32,
+            r: u256,
+            s: u256
+        ) -> bool {
+            // The issuer is an Ethereum address (e.g. an ONCHAINID claim issuer key) registered
+            // as trusted issuer; its secp256k1 signature is checked against that address
+            let eth_issuer: EthAddress = issuer.try_into().unwrap();
+            let digest = self.eth_claim_hash(identity, claim_topic, data.clone());
+            assert(!self.revoked_eth_claims.read(digest), 'Claim signature revoked');
+            verify_eth_signature(digest, signature_from_vrs(v, r, s), eth_issuer);
+            
+            // The claim data bytes are stored as their hash
+            let mut serialized_data = ArrayTrait::<felt252>::new();
+            data.serialize(ref serialized_data);
+            self._add_claim(identity, claim_topic, issuer, poseidon_hash_span(serialized_data.span()));
+            self.eth_claim_digests.write((identity, claim_topic, issuer), digest);
+            true
+        }
+        
+        fn remove_claim(ref self: ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> bool {
+            // Issuers revoke their own claims, agents can revoke any claim
+            let caller = get_caller_address();
+            let caller_felt: felt252 = caller.into();
+            assert(caller_felt == issuer || self.has_role(AGENT_ROLE, caller), 'Not claim issuer or agent');
+            
+            // Call identity storage contract
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .remove_claim(identity, claim_topic, issuer);
+            self._bump_claim_nonce(identity, claim_topic, issuer);
+            self._invalidate_verification(identity);
+            
+            // An Ethereum-signed claim cannot be re-added with the same signature
+            let digest = self.eth_claim_digests.read((identity, claim_topic, issuer));
+            if digest != 0 {
+                self.eth_claim_digests.write((identity, claim_topic, issuer), 0);
+                self._revoke_eth_claim(digest);
+            }
+            
+            // Emit event
+            self.emit(ClaimRemoved { identity, claim_topic, issuer });
+            
+            true
+        }
+        
+        fn get_claim(self: @ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> (bool, felt252) {
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_claim(identity, claim_topic, issuer)
+        }
+        
+        fn claim_hash(self: @ContractState, identity: felt252, claim_topic: felt252, data: felt252, issuer: felt252) -> felt252 {
+            // Bound to this registry and chain so signatures cannot be replayed elsewhere, and
+            // to the claim nonce so they cannot be replayed here once the claim changed
+            let chain_id = starknet::get_tx_info().unbox().chain_id;
+            let registry: felt252 = starknet::get_contract_address().into();
+            let nonce = self.claim_nonces.read((identity, claim_topic, issuer));
+            poseidon_hash_span(
+                array!['ERC3643 Claim', chain_id, registry, identity, claim_topic, data, issuer, nonce.into()].span()
+            )
+        }
+        
+        fn get_claim_nonce(self: @ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> u64 {
+            self.claim_nonces.read((identity, claim_topic, issuer))
+        }
+        
+        fn eth_claim_hash(self: @ContractState, identity: felt252, claim_topic: felt252, data: ByteArray) -> u256 {
+            // ONCHAINID claim digest: EIP-191 personal message over
+            // keccak256(abi.encode(address identity, uint256 topic, bytes data)), so existing
+            // attestations can be ported. The digest has no chain or registry field; claims are
+            // bound to this registry by the ONCHAINID address its agents linked to the identity.
+            let onchain_id = self.onchain_ids.read(identity);
+            let onchain_id_felt: felt252 = onchain_id.into();
+            assert(onchain_id_felt != 0, 'ONCHAINID not set');
+            
+            let mut encoded: ByteArray = Default::default();
+            self._append_abi_word(ref encoded, onchain_id_felt.into());
+            self._append_abi_word(ref encoded, claim_topic.into());
+            
+            // Dynamic `bytes`: offset of the tail, then length and right-padded content
+            self._append_abi_word(ref encoded, 0x60);
+            self._append_abi_word(ref encoded, data.len().into());
+            encoded.append(@data);
+            let mut padding = (32 - data.len() % 32) % 32;
+            while padding > 0 {
+                encoded.append_byte(0);
+                padding -= 1;
+            };
+            let inner = self._keccak_be(compute_keccak_byte_array(@encoded));
+            
+            let mut message: ByteArray = "\x19Ethereum Signed Message:\n32";
+            message.append_word(inner.high.into(), 16);
+            message.append_word(inner.low.into(), 16);
+            self._keccak_be(compute_keccak_byte_array(@message))
+        }
+        
+        fn set_onchain_id(ref self: ContractState, identity: felt252, onchain_id: EthAddress) -> bool {
+            // Only agent can link an ONCHAINID identity contract
+            self._assert_only_agent();
+            assert(self.identity_exists(identity), 'Identity does not exist');
+            
+            self.onchain_ids.write(identity, onchain_id);
+            self.emit(OnchainIdSet { identity, onchain_id });
+            true
+        }
+        
+        fn get_onchain_id(self: @ContractState, identity: felt252) -> EthAddress {
+            self.onchain_ids.read(identity)
+        }
+        
+        fn revoke_eth_claim(ref self: ContractState, digest: u256) -> bool {
+            // Agents revoke signed claims on the issuer's request, before or after submission
+            self._assert_only_agent();
+            
+            self._revoke_eth_claim(digest);
+            true
+        }
+        
+        fn is_eth_claim_revoked(self: @ContractState, digest: u256) -> bool {
+            self.revoked_eth_claims.read(digest)
+        }
+        
+        fn link_wallet(ref self: ContractState, existing_wallet: ContractAddress, new_wallet: ContractAddress) -> bool {
+            // Only agent can link wallets
+            self._assert_only_agent();
+            
+            let identity = self._copy_wallet_record(existing_wallet, new_wallet);
+            
+            // Emit event
+            self.emit(WalletLinked { identity, wallet: new_wallet });
+            
+            true
+        }
+        
+        fn unlink_wallet(ref self: ContractState, wallet: ContractAddress) -> bool {
+            // Only agent can unlink wallets
+            self._assert_only_agent();
+            
+            let identity_storage = self.get_identity_storage();
+            let storage_dispatcher = super::IIdentityStorageContractDispatcher { contract_address: identity_storage };
+            
+            let identity = storage_dispatcher.get_identity(wallet);
+            assert(identity != 0, 'Identity does not exist');
+            
+            // The last wallet of an identity must be removed with delete_identity
+            let wallets = storage_dispatcher.get_addresses_by_identity(identity);
+            assert(wallets.len() > 1, 'Cannot unlink last wallet');
+            
+            storage_dispatcher.delete_identity(wallet);
+            
+            // Emit event
+            self.emit(WalletUnlinked { identity, wallet });
+            
+            true
+        }
+        
+        fn rotate_wallet(ref self: ContractState, old_wallet: ContractAddress, new_wallet: ContractAddress) -> bool {
+            // The wallet owner rotates its own key, agents do it after an off-chain proof
+            let caller = get_caller_address();
+            assert(caller == old_wallet || self.has_role(AGENT_ROLE, caller), 'Not wallet owner or agent');
+            assert(!self.updates_paused_map.read('paused'), 'Registry updates paused');
+            
+            // Token balances are not moved and the old wallet is no longer verified afterwards.
+            // To carry a balance over, link the new wallet, move the tokens (holder transfer or
+            // agent forced transfer) and then unlink the old wallet instead of rotating.
+            // Claims are attached to the identity and carry over unchanged
+            let identity = self._copy_wallet_record(old_wallet, new_wallet);
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .delete_identity(old_wallet);
+            
+            // Emit event
+            self.emit(WalletRotated { identity, old_wallet, new_wallet });
+            
+            true
+        }
+        
+        fn get_linked_wallets(self: @ContractState, user_address: ContractAddress) -> Array<ContractAddress> {
+            let identity = self.get_identity(user_address);
+            if identity == 0 {
+                return ArrayTrait::new();
+            }
+            
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_addresses_by_identity(identity)
+        }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the implementation
+            self.ownable.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.ownable.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, 'Storage already migrated');
+            
+            // No layout changes yet between version 0 and 1, only record the version
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     // Internal functions
@@ -446,6 +1176,122 @@ pub mod IdentityRegistry {
             // Check if the caller has the AGENT_ROLE
             let caller = get_caller_address();
             assert(self.has_role(AGENT_ROLE, caller), 'Only agents allowed');
+            assert(!self.updates_paused_map.read('paused'), 'Registry updates paused');
+        }
+        
+        fn _assert_valid_issuer_signature(
+            self: @ContractState,
+            identity: felt252,
+            claim_topic: felt252,
+            data: felt252,
+            issuer: felt252,
+            signature: Array<felt252>
+        ) {
+            let hash = self.claim_hash(identity, claim_topic, data, issuer);
+            let issuer_account: ContractAddress = issuer.try_into().unwrap();
+            let result = super::IAccountContractDispatcher { contract_address: issuer_account }
+                .is_valid_signature(hash, signature);
+            
+            // SRC6 accounts return 'VALID', older account contracts return 1
+            assert(result == starknet::VALIDATED || result == 1, 'Invalid issuer signature');
+        }
+        
+        fn _bump_claim_nonce(ref self: ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) {
+            // Signatures over the previous state of the claim are no longer accepted
+            let nonce = self.claim_nonces.read((identity, claim_topic, issuer));
+            self.claim_nonces.write((identity, claim_topic, issuer), nonce + 1);
+        }
+        
+        fn _verification_epoch(self: @ContractState, identity: felt252) -> felt252 {
+            // Changes whenever a registry is replaced or the topics, the issuers or the
+            // identity's claims change, wherever the change was made
+            let claim_topics_registry = self.get_claim_topics_registry();
+            let trusted_issuers_registry = self.get_trusted_issuers_registry();
+            let identity_storage = self.get_identity_storage();
+            let topics_epoch = super::IClaimTopicsRegistryContractDispatcher { contract_address: claim_topics_registry }
+                .get_claim_topics_epoch();
+            let issuers_epoch = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry }
+                .get_trusted_issuers_epoch();
+            let claim_epoch = super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .get_claim_epoch(identity);
+            
+            let data = array![
+                claim_topics_registry.into(),
+                topics_epoch.into(),
+                trusted_issuers_registry.into(),
+                issuers_epoch.into(),
+                identity_storage.into(),
+                claim_epoch.into()
+            ];
+            poseidon_hash_span(data.span())
+        }
+        
+        fn _invalidate_verification(ref self: ContractState, identity: felt252) {
+            // Claim changes take effect immediately, the next check recomputes
+            self.verified_at_map.write(identity, 0);
+        }
+        
+        fn _add_claim(ref self: ContractState, identity: felt252, claim_topic: felt252, issuer: felt252, data: felt252) {
+            // The issuer must be trusted for the topic
+            self._assert_trusted_for_topic(issuer, claim_topic);
+            
+            // Call identity storage contract
+            let identity_storage = self.get_identity_storage();
+            super::IIdentityStorageContractDispatcher { contract_address: identity_storage }
+                .add_claim(identity, claim_topic, issuer, data);
+            self._bump_claim_nonce(identity, claim_topic, issuer);
+            self._invalidate_verification(identity);
+            
+            // Emit event
+            self.emit(ClaimAdded { identity, claim_topic, issuer, data });
+        }
+        
+        fn _copy_wallet_record(ref self: ContractState, existing_wallet: ContractAddress, new_wallet: ContractAddress) -> felt252 {
+            let identity_storage = self.get_identity_storage();
+            let storage_dispatcher = super::IIdentityStorageContractDispatcher { contract_address: identity_storage };
+            
+            // The new wallet inherits the identity and country of the existing one
+            let identity = storage_dispatcher.get_identity(existing_wallet);
+            assert(identity != 0, 'Identity does not exist');
+            let country = storage_dispatcher.get_country(existing_wallet);
+            storage_dispatcher.register_identity(new_wallet, identity, country);
+            
+            // Carry over the expiration date so both wallets lapse together
+            let expiration_date = storage_dispatcher.get_expiration_date(existing_wallet);
+            if expiration_date != 0 {
+                storage_dispatcher.set_expiration_date(new_wallet, expiration_date);
+            }
+            
+            // Same investor, same category
+            let category = storage_dispatcher.get_investor_category(existing_wallet);
+            if category != 0 {
+                storage_dispatcher.update_investor_category(new_wallet, category);
+            }
+            
+            identity
+        }
+        
+        fn _revoke_eth_claim(ref self: ContractState, digest: u256) {
+            self.revoked_eth_claims.write(digest, true);
+            self.emit(EthClaimRevoked { digest });
+        }
+        
+        fn _append_abi_word(self: @ContractState, ref encoded: ByteArray, word: u256) {
+            // 32-byte big-endian word of the Solidity ABI encoding
+            encoded.append_word(word.high.into(), 16);
+            encoded.append_word(word.low.into(), 16);
+        }
+        
+        fn _keccak_be(self: @ContractState, hash: u256) -> u256 {
+            // Cairo keccak outputs are little-endian, Ethereum digests are big-endian
+            u256 { low: u128_byte_reverse(hash.high), high: u128_byte_reverse(hash.low) }
+        }
+        
+        fn _assert_trusted_for_topic(self: @ContractState, issuer: felt252, claim_topic: felt252) {
+            let trusted_issuers_registry = self.get_trusted_issuers_registry();
+            let dispatcher = super::ITrustedIssuersRegistryContractDispatcher { contract_address: trusted_issuers_registry };
+            assert(dispatcher.is_trusted_issuer(issuer), 'Issuer not trusted');
+            assert(dispatcher.has_claim_topic(issuer, claim_topic), 'Issuer not trusted for topic');
         }
         
         // Helper functions to read contract addresses from storage
@@ -1,13 +1,20 @@
 // Import OpenZeppelin's components
 use openzeppelin::access::ownable::OwnableComponent;
+use openzeppelin::upgrades::UpgradeableComponent;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     get_caller_address,
     storage::StorageMapReadAccess,
     storage::StorageMapWriteAccess,
 };
 use core::array::ArrayTrait;
 
+// Investor categories, retail unless set otherwise
+pub const CATEGORY_RETAIL: u8 = 0;
+pub const CATEGORY_ACCREDITED: u8 = 1;
+pub const CATEGORY_INSTITUTIONAL: u8 = 2;
+
 // Identity Storage Interface
 #[starknet::interface]
 pub trait IIdentityStorage<TContractState> {
@@ -25,7 +32,26 @@ pub trait IIdentityStorage<TContractState> {
     fn get_country(self: @TContractState, user_address: ContractAddress) -> felt252;
     fn get_expiration_date(self: @TContractState, user_address: ContractAddress) -> u64;
     fn set_expiration_date(ref self: TContractState, user_address: ContractAddress, expiration_date: u64) -> bool;
+    fn get_investor_category(self: @TContractState, user_address: ContractAddress) -> u8;
+    fn update_investor_category(ref self: TContractState, user_address: ContractAddress, category: u8) -> bool;
+    fn add_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, issuer: felt252, data: felt252) -> bool;
+    fn remove_claim(ref self: TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> bool;
+    fn get_claim(self: @TContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> (bool, felt252);
+    fn get_claim_epoch(self: @TContractState, identity: felt252) -> u64;
+    fn set_metadata_uri(ref self: TContractState, identity: felt252, metadata_uri: ByteArray) -> bool;
+    fn get_metadata_uri(self: @TContractState, identity: felt252) -> ByteArray;
     fn get_addresses_by_identity(self: @TContractState, identity: felt252) -> Array<ContractAddress>;
+    
+    // Identity registries sharing this storage
+    fn bind_identity_registry(ref self: TContractState, identity_registry: ContractAddress) -> bool;
+    fn unbind_identity_registry(ref self: TContractState, identity_registry: ContractAddress) -> bool;
+    fn is_identity_registry_bound(self: @TContractState, identity_registry: ContractAddress) -> bool;
+    fn get_bound_identity_registries(self: @TContractState) -> Array<ContractAddress>;
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 #[starknet::contract]
@@ -34,11 +60,13 @@ pub mod IdentityStorage {
     
     // Component declarations
     component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
     
     // Implement component interfaces
     // We're removing abi(embed_v0) to avoid duplicate entry points in testing
     impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
     impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Events
     #[event]
@@ -46,11 +74,20 @@ pub mod IdentityStorage {
     enum Event {
         #[flat]
         OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
         IdentityRegistered: IdentityRegistered,
         IdentityUpdated: IdentityUpdated,
         CountryUpdated: CountryUpdated,
         IdentityRemoved: IdentityRemoved,
         ExpirationDateUpdated: ExpirationDateUpdated,
+        InvestorCategoryUpdated: InvestorCategoryUpdated,
+        ClaimAdded: ClaimAdded,
+        ClaimRemoved: ClaimRemoved,
+        MetadataUriUpdated: MetadataUriUpdated,
+        IdentityRegistryBound: IdentityRegistryBound,
+        IdentityRegistryUnbound: IdentityRegistryUnbound,
+        StorageMigrated: StorageMigrated,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -83,32 +120,101 @@ pub mod IdentityStorage {
         expiration_date: u64,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct InvestorCategoryUpdated {
+        user_address: ContractAddress,
+        category: u8,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ClaimAdded {
+        identity: felt252,
+        claim_topic: felt252,
+        issuer: felt252,
+        data: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ClaimRemoved {
+        identity: felt252,
+        claim_topic: felt252,
+        issuer: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MetadataUriUpdated {
+        identity: felt252,
+        metadata_uri: ByteArray,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct IdentityRegistryBound {
+        identity_registry: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct IdentityRegistryUnbound {
+        identity_registry: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
         // Component storage
         #[substorage(v0)]
         ownable: OwnableComponent::Storage,
         
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
         // Identity storage - using consistent map naming convention
         identity_by_address_map: starknet::storage::Map::<ContractAddress, felt252>,
         country_by_address_map: starknet::storage::Map::<ContractAddress, felt252>,
         expiration_by_address_map: starknet::storage::Map::<ContractAddress, u64>,
+        category_by_address_map: starknet::storage::Map::<ContractAddress, u8>,
         
         // Mapping from identity to its addresses (manages as counters and individual entries)
         address_count_by_identity_map: starknet::storage::Map::<felt252, u32>,
         address_by_identity_index_map: starknet::storage::Map::<(felt252, u32), ContractAddress>,
         
-        // Registry contract that can manage this storage
+        // Claims attached to identities, by (identity, claim topic, issuer)
+        claim_exists_map: starknet::storage::Map::<(felt252, felt252, felt252), bool>,
+        claim_data_map: starknet::storage::Map::<(felt252, felt252, felt252), felt252>,
+        claim_epochs: starknet::storage::Map::<felt252, u64>,  // Bumped on every claim change of the identity
+        
+        // Pointer to the identity's encrypted off-chain KYC documents
+        metadata_uri_by_identity_map: starknet::storage::Map::<felt252, ByteArray>,
+        
+        // Single registry of version 1 layouts, moved to the bound registries by `migrate`
         registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'registry' as key
+        
+        // Registry contracts that can manage this storage, one per token sharing the KYC data
+        registry_count_map: starknet::storage::Map::<felt252, u32>,  // Using 'registry_count' as key
+        registries: starknet::storage::Map::<u32, ContractAddress>,
+        registry_indices: starknet::storage::Map::<ContractAddress, u32>,  // 1-based, 0 when not bound
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: starknet::storage::Map::<felt252, u8>,  // Using 'version' as key
     }
+    
+    // Version 2 binds a list of registries, version 1 kept a single one in `registry_map`
+    const STORAGE_VERSION: u8 = 2;
 
     #[constructor]
     fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
         // Initialize Ownable component
         self.ownable.initializer(initial_owner);
         
-        // Set registry as the owner initially
-        self.registry_map.write('registry', initial_owner);
+        // Bind the owner as registry initially
+        self._bind_registry(initial_owner);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
@@ -204,10 +310,11 @@ pub mod IdentityStorage {
             // Remove address from identity's list
             self._remove_address_from_identity(existing_identity, user_address);
             
-            // Delete identity, country and expiration date
+            // Delete identity, country, expiration date and category
             self.identity_by_address_map.write(user_address, 0);
             self.country_by_address_map.write(user_address, 0);
             self.expiration_by_address_map.write(user_address, 0);
+            self.category_by_address_map.write(user_address, CATEGORY_RETAIL);
             
             // Emit event
             self.emit(IdentityRemoved { user_address });
@@ -244,6 +351,93 @@ pub mod IdentityStorage {
             true
         }
         
+        fn get_investor_category(self: @ContractState, user_address: ContractAddress) -> u8 {
+            self.category_by_address_map.read(user_address)
+        }
+        
+        fn update_investor_category(ref self: ContractState, user_address: ContractAddress, category: u8) -> bool {
+            // Only registry contract can update categories
+            self._assert_only_registry();
+            assert(category <= CATEGORY_INSTITUTIONAL, 'Invalid investor category');
+            
+            // Check if identity exists
+            let existing_identity = self.identity_by_address_map.read(user_address);
+            assert(existing_identity != 0, 'Identity does not exist');
+            
+            // Update the category
+            self.category_by_address_map.write(user_address, category);
+            
+            // Emit event
+            self.emit(InvestorCategoryUpdated { user_address, category });
+            
+            true
+        }
+        
+        fn add_claim(ref self: ContractState, identity: felt252, claim_topic: felt252, issuer: felt252, data: felt252) -> bool {
+            // Only registry contract can add claims
+            self._assert_only_registry();
+            
+            // Check if identity exists
+            assert(self.address_count_by_identity_map.read(identity) > 0, 'Identity does not exist');
+            
+            // Adding again replaces the claim data
+            self.claim_exists_map.write((identity, claim_topic, issuer), true);
+            self.claim_data_map.write((identity, claim_topic, issuer), data);
+            self.claim_epochs.write(identity, self.claim_epochs.read(identity) + 1);
+            
+            // Emit event
+            self.emit(ClaimAdded { identity, claim_topic, issuer, data });
+            
+            true
+        }
+        
+        fn remove_claim(ref self: ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> bool {
+            // Only registry contract can remove claims
+            self._assert_only_registry();
+            
+            // Check if claim exists
+            assert(self.claim_exists_map.read((identity, claim_topic, issuer)), 'Claim does not exist');
+            
+            self.claim_exists_map.write((identity, claim_topic, issuer), false);
+            self.claim_data_map.write((identity, claim_topic, issuer), 0);
+            self.claim_epochs.write(identity, self.claim_epochs.read(identity) + 1);
+            
+            // Emit event
+            self.emit(ClaimRemoved { identity, claim_topic, issuer });
+            
+            true
+        }
+        
+        fn get_claim(self: @ContractState, identity: felt252, claim_topic: felt252, issuer: felt252) -> (bool, felt252) {
+            (
+                self.claim_exists_map.read((identity, claim_topic, issuer)),
+                self.claim_data_map.read((identity, claim_topic, issuer))
+            )
+        }
+        
+        fn get_claim_epoch(self: @ContractState, identity: felt252) -> u64 {
+            self.claim_epochs.read(identity)
+        }
+        
+        fn set_metadata_uri(ref self: ContractState, identity: felt252, metadata_uri: ByteArray) -> bool {
+            // Only registry contract can set metadata
+            self._assert_only_registry();
+            
+            // Check if identity exists
+            assert(self.address_count_by_identity_map.read(identity) > 0, 'Identity does not exist');
+            
+            self.metadata_uri_by_identity_map.write(identity, metadata_uri.clone());
+            
+            // Emit event
+            self.emit(MetadataUriUpdated { identity, metadata_uri });
+            
+            true
+        }
+        
+        fn get_metadata_uri(self: @ContractState, identity: felt252) -> ByteArray {
+            self.metadata_uri_by_identity_map.read(identity)
+        }
+        
         fn get_addresses_by_identity(self: @ContractState, identity: felt252) -> Array<ContractAddress> {
             let mut addresses = ArrayTrait::<ContractAddress>::new();
             let count = self.address_count_by_identity_map.read(identity);
@@ -263,6 +457,99 @@ pub mod IdentityStorage {
             
             addresses
         }
+        
+        fn bind_identity_registry(ref self: ContractState, identity_registry: ContractAddress) -> bool {
+            // Only owner can let a registry manage the storage
+            self.ownable.assert_only_owner();
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(identity_registry != zero_address, 'Invalid registry address');
+            assert(self.registry_indices.read(identity_registry) == 0, 'Registry already bound');
+            
+            self._bind_registry(identity_registry);
+            true
+        }
+        
+        fn unbind_identity_registry(ref self: ContractState, identity_registry: ContractAddress) -> bool {
+            // Only owner can release a registry
+            self.ownable.assert_only_owner();
+            
+            let index = self.registry_indices.read(identity_registry);
+            assert(index != 0, 'Registry not bound');
+            
+            // Move the last registry into the freed slot
+            let registry_count = self.registry_count_map.read('registry_count');
+            let last_index = registry_count - 1;
+            if index - 1 != last_index {
+                let last_registry = self.registries.read(last_index);
+                self.registries.write(index - 1, last_registry);
+                self.registry_indices.write(last_registry, index);
+            }
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            self.registries.write(last_index, zero_address);
+            self.registry_indices.write(identity_registry, 0);
+            self.registry_count_map.write('registry_count', last_index);
+            
+            self.emit(IdentityRegistryUnbound { identity_registry });
+            true
+        }
+        
+        fn is_identity_registry_bound(self: @ContractState, identity_registry: ContractAddress) -> bool {
+            self.registry_indices.read(identity_registry) != 0
+        }
+        
+        fn get_bound_identity_registries(self: @ContractState) -> Array<ContractAddress> {
+            let mut bound_registries = ArrayTrait::<ContractAddress>::new();
+            let registry_count = self.registry_count_map.read('registry_count');
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= registry_count {
+                    break;
+                }
+                
+                bound_registries.append(self.registries.read(i));
+                
+                i += 1;
+            };
+            
+            bound_registries
+        }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the implementation
+            self.ownable.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.ownable.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, 'Storage already migrated');
+            
+            // Version 2 replaced the single registry with a list of bound registries
+            if from_version < 2 {
+                let registry = self.registry_map.read('registry');
+                let zero_address: ContractAddress = 0.try_into().unwrap();
+                if registry != zero_address && self.registry_indices.read(registry) == 0 {
+                    self._bind_registry(registry);
+                }
+            }
+            
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     // Internal functions
@@ -270,7 +557,16 @@ pub mod IdentityStorage {
     impl InternalFunctions of InternalTrait {
         fn _assert_only_registry(self: @ContractState) {
             let caller = get_caller_address();
-            assert(caller == self.registry_map.read('registry'), 'Only registry can call');
+            assert(self.registry_indices.read(caller) != 0, 'Only registry can call');
+        }
+        
+        fn _bind_registry(ref self: ContractState, identity_registry: ContractAddress) {
+            let registry_count = self.registry_count_map.read('registry_count');
+            self.registries.write(registry_count, identity_registry);
+            self.registry_indices.write(identity_registry, registry_count + 1);
+            self.registry_count_map.write('registry_count', registry_count + 1);
+            
+            self.emit(IdentityRegistryBound { identity_registry });
         }
         
         fn _add_address_to_identity(ref self: ContractState, identity: felt252, address: ContractAddress) {
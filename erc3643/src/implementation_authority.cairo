@@ -0,0 +1,209 @@
+//! Implementation Authority
+//!
+//! Registry of the class hashes making up each release of the token suite (token,
+//! identity registry, identity storage, compliance, claim topics and trusted issuers
+//! registries), as in T-REX. The authority owner adds versions and signals the one suites
+//! should run; each token owner upgrades the contracts of their suite to the class hashes
+//! of that version and acknowledges it, so issuers and the operator can see which suites
+//! are behind.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress,
+    ClassHash,
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Contract kinds of a suite
+pub const KIND_TOKEN: felt252 = 'token';
+pub const KIND_IDENTITY_REGISTRY: felt252 = 'identity_registry';
+pub const KIND_IDENTITY_STORAGE: felt252 = 'identity_storage';
+pub const KIND_COMPLIANCE: felt252 = 'compliance';
+pub const KIND_CLAIM_TOPICS_REGISTRY: felt252 = 'claim_topics_registry';
+pub const KIND_TRUSTED_ISSUERS_REGISTRY: felt252 = 'trusted_issuers_registry';
+
+// Implementation Authority Interface
+#[starknet::interface]
+pub trait IImplementationAuthority<TContractState> {
+    fn add_version(
+        ref self: TContractState,
+        version: felt252,
+        token: ClassHash,
+        identity_registry: ClassHash,
+        identity_storage: ClassHash,
+        compliance: ClassHash,
+        claim_topics_registry: ClassHash,
+        trusted_issuers_registry: ClassHash
+    ) -> bool;
+    fn use_version(ref self: TContractState, version: felt252) -> bool;
+    fn acknowledge_version(ref self: TContractState, token: ContractAddress) -> bool;
+    fn get_current_version(self: @TContractState) -> felt252;
+    fn get_implementation(self: @TContractState, kind: felt252) -> ClassHash;
+    fn get_version_implementation(self: @TContractState, version: felt252, kind: felt252) -> ClassHash;
+    fn get_suite_version(self: @TContractState, token: ContractAddress) -> felt252;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod ImplementationAuthority {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        VersionAdded: VersionAdded,
+        VersionUpdated: VersionUpdated,
+        VersionAcknowledged: VersionAcknowledged,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VersionAdded {
+        #[key]
+        version: felt252,
+        token: ClassHash,
+        identity_registry: ClassHash,
+        identity_storage: ClassHash,
+        compliance: ClassHash,
+        claim_topics_registry: ClassHash,
+        trusted_issuers_registry: ClassHash,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VersionUpdated {
+        #[key]
+        version: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VersionAcknowledged {
+        #[key]
+        token: ContractAddress,
+        version: felt252,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Class hashes per (version, contract kind)
+        implementations: Map<(felt252, felt252), ClassHash>,
+        versions: Map<felt252, bool>,
+        
+        // Version suites should run
+        current_version_map: Map<felt252, felt252>,  // Using 'version' as key
+        
+        // Last version acknowledged by each suite, identified by its token
+        suite_versions: Map<ContractAddress, felt252>,
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+    }
+    
+    #[abi(embed_v0)]
+    impl ImplementationAuthorityImpl of super::IImplementationAuthority<ContractState> {
+        fn add_version(
+            ref self: ContractState,
+            version: felt252,
+            token: ClassHash,
+            identity_registry: ClassHash,
+            identity_storage: ClassHash,
+            compliance: ClassHash,
+            claim_topics_registry: ClassHash,
+            trusted_issuers_registry: ClassHash
+        ) -> bool {
+            // Only owner can publish versions, which cannot be changed afterwards
+            self.ownable.assert_only_owner();
+            assert(version != 0, 'Invalid version');
+            assert(!self.versions.read(version), 'Version already exists');
+            
+            self.implementations.write((version, KIND_TOKEN), token);
+            self.implementations.write((version, KIND_IDENTITY_REGISTRY), identity_registry);
+            self.implementations.write((version, KIND_IDENTITY_STORAGE), identity_storage);
+            self.implementations.write((version, KIND_COMPLIANCE), compliance);
+            self.implementations.write((version, KIND_CLAIM_TOPICS_REGISTRY), claim_topics_registry);
+            self.implementations.write((version, KIND_TRUSTED_ISSUERS_REGISTRY), trusted_issuers_registry);
+            self.versions.write(version, true);
+            
+            self.emit(VersionAdded {
+                version,
+                token,
+                identity_registry,
+                identity_storage,
+                compliance,
+                claim_topics_registry,
+                trusted_issuers_registry,
+            });
+            true
+        }
+        
+        fn use_version(ref self: ContractState, version: felt252) -> bool {
+            // Only owner can signal the version suites should upgrade to
+            self.ownable.assert_only_owner();
+            assert(self.versions.read(version), 'Version not found');
+            
+            self.current_version_map.write('version', version);
+            self.emit(VersionUpdated { version });
+            true
+        }
+        
+        fn acknowledge_version(ref self: ContractState, token: ContractAddress) -> bool {
+            // The token owner confirms their suite runs the current version
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(get_caller_address() == token_dispatcher.owner(), 'Only token owner allowed');
+            
+            let version = self.current_version_map.read('version');
+            assert(version != 0, 'No current version');
+            
+            self.suite_versions.write(token, version);
+            self.emit(VersionAcknowledged { token, version });
+            true
+        }
+        
+        fn get_current_version(self: @ContractState) -> felt252 {
+            self.current_version_map.read('version')
+        }
+        
+        fn get_implementation(self: @ContractState, kind: felt252) -> ClassHash {
+            self.implementations.read((self.current_version_map.read('version'), kind))
+        }
+        
+        fn get_version_implementation(self: @ContractState, version: felt252, kind: felt252) -> ClassHash {
+            self.implementations.read((version, kind))
+        }
+        
+        fn get_suite_version(self: @ContractState, token: ContractAddress) -> felt252 {
+            self.suite_versions.read(token)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+}
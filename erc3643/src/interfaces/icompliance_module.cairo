@@ -0,0 +1,23 @@
+//! Compliance Module Interface
+//!
+//! A compliance module is a standalone contract holding one transfer rule and its
+//! parameters. The Compliance contract evaluates every registered module in
+//! `check_compliance` and forwards the token's balance changes to each module so
+//! stateful rules (holder counts, volume limits, lockups) can keep their counters.
+use starknet::ContractAddress;
+
+#[starknet::interface]
+pub trait IComplianceModule<TContractState> {
+    // Identifies the kind of rule implemented by the module
+    fn name(self: @TContractState) -> felt252;
+    // Compliance contract allowed to call the module actions
+    fn get_compliance(self: @TContractState) -> ContractAddress;
+    // Hash of the module parameters, part of the compliance configuration hash
+    fn config_hash(self: @TContractState) -> felt252;
+    // Read-only evaluation, `from` is the zero address for mints
+    fn module_check(self: @TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool;
+    // State updates after the token moved, minted or burned tokens
+    fn module_transfer_action(ref self: TContractState, from: ContractAddress, to: ContractAddress, amount: u256);
+    fn module_mint_action(ref self: TContractState, to: ContractAddress, amount: u256);
+    fn module_burn_action(ref self: TContractState, from: ContractAddress, amount: u256);
+}
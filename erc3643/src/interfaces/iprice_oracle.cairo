@@ -0,0 +1,41 @@
+// Price Oracle Interface
+//
+// Price feeds (Pragma/Pyth style) used to express compliance limits in fiat value rather
+// than token units. A feed reports a price with its decimals, the confidence interval
+// around it and the time it was published.
+use starknet::{ContractAddress, get_block_timestamp};
+
+#[starknet::interface]
+pub trait IPriceOracle<TContractState> {
+    // (price, confidence, decimals, publish time) of the feed
+    fn get_price(self: @TContractState, feed_id: felt252) -> (u128, u128, u8, u64);
+}
+
+// Price and its decimals, None when the price is missing, older than `max_age` seconds
+// or its confidence interval is wider than `max_confidence_bps` of the price
+pub fn checked_price(
+    oracle: ContractAddress,
+    feed_id: felt252,
+    max_age: u64,
+    max_confidence_bps: u32
+) -> Option<(u256, u8)> {
+    let (price, confidence, decimals, publish_time) = IPriceOracleDispatcher { contract_address: oracle }
+        .get_price(feed_id);
+    if price == 0 {
+        return Option::None;
+    }
+    
+    let now = get_block_timestamp();
+    if publish_time > now || now - publish_time > max_age {
+        return Option::None;
+    }
+    
+    let price: u256 = price.into();
+    let confidence: u256 = confidence.into();
+    let max_confidence_bps: u256 = max_confidence_bps.into();
+    if confidence * 10000 > price * max_confidence_bps {
+        return Option::None;
+    }
+    
+    Option::Some((price, decimals))
+}
@@ -12,6 +12,7 @@ pub trait ITrustedIssuersRegistry<TContractState> {
     fn get_issuer_claim_topics(self: @TContractState, issuer: felt252) -> Array<felt252>;
     fn is_trusted_issuer(self: @TContractState, issuer: felt252) -> bool;
     fn has_claim_topic(self: @TContractState, issuer: felt252, claim_topic: felt252) -> bool;
+    fn get_trusted_issuers_for_claim_topic(self: @TContractState, claim_topic: felt252) -> Array<felt252>;
 }
 
 #[starknet::interface]
@@ -25,4 +26,5 @@ pub trait ITrustedIssuersRegistryCamelCase<TContractState> {
     fn getIssuerClaimTopics(self: @TContractState, issuer: felt252) -> Array<felt252>;
     fn isTrustedIssuer(self: @TContractState, issuer: felt252) -> bool;
     fn hasClaimTopic(self: @TContractState, issuer: felt252, claim_topic: felt252) -> bool;
+    fn getTrustedIssuersForClaimTopic(self: @TContractState, claim_topic: felt252) -> Array<felt252>;
 }
\ No newline at end of file
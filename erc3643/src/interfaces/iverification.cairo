@@ -0,0 +1,16 @@
+// Verification Interface
+//
+// Minimal view of an Identity Registry for contracts that only need to know whether a
+// wallet is verified, e.g. permissioned pools built on top of the identity layer.
+// Every IdentityRegistry exposes this entry point.
+use starknet::ContractAddress;
+
+#[starknet::interface]
+pub trait IVerification<TContractState> {
+    fn is_verified_address(self: @TContractState, user_address: ContractAddress) -> bool;
+}
+
+// Whether the wallet is verified in the given identity registry
+pub fn is_verified(identity_registry: ContractAddress, user_address: ContractAddress) -> bool {
+    IVerificationDispatcher { contract_address: identity_registry }.is_verified_address(user_address)
+}
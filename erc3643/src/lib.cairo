@@ -11,6 +11,51 @@ pub mod claim_topics_registry;
 pub mod trusted_issuers_registry;
 pub mod identity_storage;
 
+// Country code validation and region groupings
+pub mod country;
+
+// Delegated transfer approvals
+pub mod dva;
+
+// ERC20 payment legs shared by the settlement contracts
+pub mod payments;
+
+// Delivery versus payment settlement
+pub mod dvp;
+
+// Delivery versus delivery swaps between tokens
+pub mod dvd;
+
+// Conditional settlements
+pub mod escrow;
+
+// Pro-rata payouts against balance snapshots
+pub mod distribution;
+
+// Redemption windows
+pub mod redemption;
+
+// Issuer buyback programs
+pub mod buyback;
+
+// Plain ERC20 mirror of a security token
+pub mod wrapper;
+
+// L1 <-> L2 bridging of the token
+pub mod bridge;
+
+// Governance voting weight from balance snapshots
+pub mod voting;
+
+// Class hashes of each suite release
+pub mod implementation_authority;
+
+// One-transaction deployment of token suites
+pub mod factory;
+
+// Deployer allow-list and fees in front of the factory
+pub mod gateway;
+
 // New component-based architecture
 pub mod interfaces {
     pub mod ierc3643;
@@ -19,6 +64,30 @@ pub mod interfaces {
     pub mod icompliance;
     pub mod iclaim_topics_registry;
     pub mod itrusted_issuers_registry;
+    pub mod icompliance_module;
+    pub mod iverification;
+    pub mod iprice_oracle;
+}
+
+// Compliance modules
+pub mod modules {
+    pub mod common;
+    pub mod country_restrict;
+    pub mod max_holders;
+    pub mod country_holder_limit;
+    pub mod daily_transfer_limit;
+    pub mod time_transfer_limits;
+    pub mod lockup;
+    pub mod vesting;
+    pub mod exchange_limits;
+    pub mod transfer_fees;
+    pub mod transfer_allowlist;
+    pub mod holding_period;
+    pub mod investor_category_caps;
+    pub mod blackout;
+    pub mod velocity_limit;
+    pub mod whole_units;
+    pub mod investor_value_cap;
 }
 
 pub mod components {
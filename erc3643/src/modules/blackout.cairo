@@ -0,0 +1,240 @@
+//! Blackout Module
+//!
+//! Compliance module rejecting transfers during blackout windows, e.g. around earnings
+//! releases or corporate actions. Windows are `[start, end)` timestamp ranges managed by
+//! the owner. Agent forced transfers stay possible, the compliance skipping this module
+//! in `check_forced_transfer`; mints are not restricted.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+
+// Blackout Module Interface
+#[starknet::interface]
+pub trait IBlackoutModule<TContractState> {
+    fn add_blackout_window(ref self: TContractState, start: u64, end: u64) -> bool;
+    fn remove_blackout_window(ref self: TContractState, start: u64, end: u64) -> bool;
+    fn get_blackout_windows(self: @TContractState) -> Array<(u64, u64)>;
+    fn is_blackout_active(self: @TContractState) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod BlackoutModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        BlackoutWindowAdded: BlackoutWindowAdded,
+        BlackoutWindowRemoved: BlackoutWindowRemoved,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BlackoutWindowAdded {
+        start: u64,
+        end: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BlackoutWindowRemoved {
+        start: u64,
+        end: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Blackout windows
+        window_count_map: Map<felt252, u32>,  // Using 'count' as key
+        window_starts: Map<u32, u64>,
+        window_ends: Map<u32, u64>,
+        window_indices: Map<(u64, u64), u32>,  // 1-based, 0 if not set
+    }
+    
+    // Maximum number of windows, keeps the check loop affordable
+    const MAX_WINDOWS: u32 = 20;
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'BlackoutModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            // Windows in storage order
+            let count = self.window_count_map.read('count');
+            let mut data = array![count.into()];
+            let mut i: u32 = 0;
+            while i < count {
+                data.append(self.window_starts.read(i).into());
+                data.append(self.window_ends.read(i).into());
+                i += 1;
+            };
+            core::poseidon::poseidon_hash_span(data.span())
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not restricted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            !self._is_blackout_active()
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl BlackoutModuleImpl of super::IBlackoutModule<ContractState> {
+        fn add_blackout_window(ref self: ContractState, start: u64, end: u64) -> bool {
+            // Only owner can manage windows
+            self.ownable.assert_only_owner();
+            assert(start < end, 'Invalid window');
+            assert(self.window_indices.read((start, end)) == 0, 'Window already exists');
+            
+            let count = self.window_count_map.read('count');
+            assert(count < MAX_WINDOWS, 'Too many windows');
+            
+            self.window_starts.write(count, start);
+            self.window_ends.write(count, end);
+            self.window_indices.write((start, end), count + 1);
+            self.window_count_map.write('count', count + 1);
+            
+            self.emit(BlackoutWindowAdded { start, end });
+            true
+        }
+        
+        fn remove_blackout_window(ref self: ContractState, start: u64, end: u64) -> bool {
+            // Only owner can manage windows
+            self.ownable.assert_only_owner();
+            
+            let index = self.window_indices.read((start, end));
+            assert(index != 0, 'Window not found');
+            
+            // Move the last window into the freed slot
+            let count = self.window_count_map.read('count');
+            let last_index = count - 1;
+            if index - 1 != last_index {
+                let last_start = self.window_starts.read(last_index);
+                let last_end = self.window_ends.read(last_index);
+                self.window_starts.write(index - 1, last_start);
+                self.window_ends.write(index - 1, last_end);
+                self.window_indices.write((last_start, last_end), index);
+            }
+            
+            self.window_starts.write(last_index, 0);
+            self.window_ends.write(last_index, 0);
+            self.window_indices.write((start, end), 0);
+            self.window_count_map.write('count', last_index);
+            
+            self.emit(BlackoutWindowRemoved { start, end });
+            true
+        }
+        
+        fn get_blackout_windows(self: @ContractState) -> Array<(u64, u64)> {
+            let mut windows = ArrayTrait::new();
+            let count = self.window_count_map.read('count');
+            let mut i: u32 = 0;
+            while i < count {
+                windows.append((self.window_starts.read(i), self.window_ends.read(i)));
+                i += 1;
+            };
+            windows
+        }
+        
+        fn is_blackout_active(self: @ContractState) -> bool {
+            self._is_blackout_active()
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _is_blackout_active(self: @ContractState) -> bool {
+            let now = get_block_timestamp();
+            let count = self.window_count_map.read('count');
+            let mut i: u32 = 0;
+            let mut active = false;
+            while i < count {
+                if self.window_starts.read(i) <= now && now < self.window_ends.read(i) {
+                    active = true;
+                    break;
+                }
+                i += 1;
+            };
+            active
+        }
+    }
+}
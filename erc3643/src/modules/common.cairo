@@ -0,0 +1,185 @@
+//! Shared lookups for compliance modules
+//!
+//! Modules only know the Compliance contract they were deployed for. Investor data is
+//! reached through it: Compliance -> bound token -> identity registry.
+
+use starknet::ContractAddress;
+use core::poseidon::poseidon_hash_span;
+
+use crate::compliance::{IComplianceDispatcher, IComplianceDispatcherTrait};
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+use crate::identity_registry::{IIdentityRegistryDispatcher, IIdentityRegistryDispatcherTrait};
+
+// Token bound to the compliance contract
+pub fn token_of(compliance: ContractAddress) -> ContractAddress {
+    IComplianceDispatcher { contract_address: compliance }.get_token()
+}
+
+// Identity registry used by the bound token
+pub fn identity_registry_of(compliance: ContractAddress) -> ContractAddress {
+    let token = token_of(compliance);
+    IERC3643TokenDispatcher { contract_address: token }.identity_registry()
+}
+
+// Country of the investor owning the wallet, 0 if not registered
+pub fn investor_country(compliance: ContractAddress, user_address: ContractAddress) -> felt252 {
+    let identity_registry = identity_registry_of(compliance);
+    IIdentityRegistryDispatcher { contract_address: identity_registry }.get_country(user_address)
+}
+
+// Identity of the investor owning the wallet, 0 if not registered.
+// Linked wallets share the same identity, so modules counting investors key on it.
+pub fn investor_identity(compliance: ContractAddress, user_address: ContractAddress) -> felt252 {
+    let identity_registry = identity_registry_of(compliance);
+    IIdentityRegistryDispatcher { contract_address: identity_registry }.get_identity(user_address)
+}
+
+// Token balance of the wallet
+pub fn wallet_balance(compliance: ContractAddress, user_address: ContractAddress) -> u256 {
+    let token = token_of(compliance);
+    IERC3643TokenDispatcher { contract_address: token }.balance_of(user_address)
+}
+
+// Whether the address is an agent of the bound token
+pub fn is_token_agent(compliance: ContractAddress, address: ContractAddress) -> bool {
+    let token = token_of(compliance);
+    IERC3643TokenDispatcher { contract_address: token }.is_compliance_agent(address)
+}
+
+// Investor category of the wallet (see `identity_storage`), retail if not set
+pub fn investor_category(compliance: ContractAddress, user_address: ContractAddress) -> u8 {
+    let identity_registry = identity_registry_of(compliance);
+    IIdentityRegistryDispatcher { contract_address: identity_registry }.get_investor_category(user_address)
+}
+
+// Decimals of the bound token
+pub fn token_decimals(compliance: ContractAddress) -> u8 {
+    let token = token_of(compliance);
+    IERC3643TokenDispatcher { contract_address: token }.decimals()
+}
+
+// Configuration hash after a parameter change, chaining the change onto the previous
+// hash. Used by modules whose parameters live in maps and cannot be hashed as a whole.
+pub fn chain_config_hash(previous: felt252, change: Span<felt252>) -> felt252 {
+    let mut data = array![previous];
+    data.append_span(change);
+    poseidon_hash_span(data.span())
+}
+
+// Balances aggregated per investor, for modules counting or capping investors.
+// A wallet's balance is tracked under the key it had when first credited (its identity,
+// or the wallet itself if unregistered) until it is emptied, so identity changes in the
+// registry (wallet rotation, unlinking, deletion) never leave a debit without a balance.
+#[starknet::component]
+pub mod InvestorBalancesComponent {
+    use starknet::{
+        ContractAddress,
+        storage::StorageMapReadAccess,
+        storage::StorageMapWriteAccess,
+        storage::Map,
+    };
+    use super::{investor_identity, wallet_balance};
+    
+    #[storage]
+    pub struct Storage {
+        // Aggregated balance per investor key
+        pub investor_balances: Map<felt252, u256>,
+        // Key each holding wallet is tracked under, 0 when none is saved
+        pub wallet_keys: Map<ContractAddress, felt252>,
+    }
+    
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    pub enum Event {}
+    
+    #[generate_trait]
+    pub impl InternalImpl<TContractState, +HasComponent<TContractState>> of InternalTrait<TContractState> {
+        fn investor_key(
+            self: @ComponentState<TContractState>,
+            compliance: ContractAddress,
+            user_address: ContractAddress
+        ) -> felt252 {
+            let saved_key = self.wallet_keys.read(user_address);
+            if saved_key != 0 {
+                return saved_key;
+            }
+            
+            // Wallets without identity (e.g. the owner receiving a recovery) count on their own
+            let identity = investor_identity(compliance, user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn key_balance(self: @ComponentState<TContractState>, key: felt252) -> u256 {
+            self.investor_balances.read(key)
+        }
+        
+        fn investor_balance(
+            self: @ComponentState<TContractState>,
+            compliance: ContractAddress,
+            user_address: ContractAddress
+        ) -> u256 {
+            self.investor_balances.read(self.investor_key(compliance, user_address))
+        }
+        
+        // Returns the key credited and whether the investor just started holding
+        fn credit(
+            ref self: ComponentState<TContractState>,
+            compliance: ContractAddress,
+            user_address: ContractAddress,
+            amount: u256
+        ) -> (felt252, bool) {
+            let key = self.investor_key(compliance, user_address);
+            if amount == 0 {
+                return (key, false);
+            }
+            
+            self.wallet_keys.write(user_address, key);
+            let balance = self.investor_balances.read(key);
+            self.investor_balances.write(key, balance + amount);
+            (key, balance == 0)
+        }
+        
+        // Returns the key debited and whether the investor just stopped holding
+        fn debit(
+            ref self: ComponentState<TContractState>,
+            compliance: ContractAddress,
+            user_address: ContractAddress,
+            amount: u256
+        ) -> (felt252, bool) {
+            let key = self.investor_key(compliance, user_address);
+            
+            // Holdings from before the module was registered are not tracked, never underflow
+            let balance = self.investor_balances.read(key);
+            let new_balance = if balance > amount { balance - amount } else { 0 };
+            self.investor_balances.write(key, new_balance);
+            
+            // Hooks run after the token moved, an emptied wallet is keyed afresh next time
+            if wallet_balance(compliance, user_address) == 0 {
+                self.wallet_keys.write(user_address, 0);
+            }
+            (key, balance > 0 && new_balance == 0)
+        }
+        
+        // Sets the balance of the wallet's investor, returning the key and whether the
+        // investor was and is now holding
+        fn preset(
+            ref self: ComponentState<TContractState>,
+            compliance: ContractAddress,
+            user_address: ContractAddress,
+            balance: u256
+        ) -> (felt252, bool, bool) {
+            let key = self.investor_key(compliance, user_address);
+            let previous_balance = self.investor_balances.read(key);
+            
+            self.investor_balances.write(key, balance);
+            if balance > 0 {
+                self.wallet_keys.write(user_address, key);
+            }
+            (key, previous_balance > 0, balance > 0)
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Country Holder Limit Module
+//!
+//! Compliance module capping the number of investors per country. Investors are
+//! counted per identity under the country they had when they first received tokens,
+//! so a later country update cannot leave a counter out of sync. A cap of 0 means the
+//! country is not limited. Register the module before the first mint, or have agents
+//! preset the balances of the existing holders.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::array::ArrayTrait;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{InvestorBalancesComponent, investor_country, is_token_agent, chain_config_hash};
+
+// Country Holder Limit Module Interface
+#[starknet::interface]
+pub trait ICountryHolderLimitModule<TContractState> {
+    fn set_country_cap(ref self: TContractState, country: felt252, cap: u32) -> bool;
+    fn batch_set_country_caps(ref self: TContractState, countries: Array<felt252>, caps: Array<u32>) -> bool;
+    fn get_country_cap(self: @TContractState, country: felt252) -> u32;
+    fn get_country_holder_count(self: @TContractState, country: felt252) -> u32;
+    fn get_investor_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn preset_investor_balance(ref self: TContractState, user_address: ContractAddress, balance: u256) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod CountryHolderLimitModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: InvestorBalancesComponent, storage: balances, event: InvestorBalancesEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    impl InvestorBalancesInternalImpl = InvestorBalancesComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        InvestorBalancesEvent: InvestorBalancesComponent::Event,
+        CountryCapSet: CountryCapSet,
+        InvestorBalancePreset: InvestorBalancePreset,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CountryCapSet {
+        country: felt252,
+        cap: u32,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct InvestorBalancePreset {
+        #[key]
+        investor_key: felt252,
+        balance: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Aggregated balance per investor, keeping the `investor_balances` slot of earlier releases
+        #[substorage(v0)]
+        balances: InvestorBalancesComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Per-country limits and current holder counts
+        country_caps: Map<felt252, u32>,
+        country_holder_counts: Map<felt252, u32>,
+        
+        // Chained hash of the cap changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+        
+        // Country each holding investor is counted under
+        investor_countries: Map<felt252, felt252>,
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, initial_owner: ContractAddress, compliance: ContractAddress) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'CountryHolderLimitModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let compliance = self.compliance_map.read('compliance');
+            let to_key = self.balances.investor_key(compliance, to);
+            
+            // Existing holders and empty transfers never increase a count
+            if amount == 0 || self.balances.key_balance(to_key) > 0 {
+                return true;
+            }
+            
+            let country = investor_country(compliance, to);
+            let cap = self.country_caps.read(country);
+            if cap == 0 {
+                return true;
+            }
+            
+            let mut holder_count = self.country_holder_counts.read(country);
+            
+            // A sender from the same country moving its whole position out frees its slot
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address {
+                let from_key = self.balances.investor_key(compliance, from);
+                if from_key != to_key
+                    && self.balances.key_balance(from_key) == amount
+                    && self.investor_countries.read(from_key) == country {
+                    holder_count -= 1;
+                }
+            }
+            
+            holder_count + 1 <= cap
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Moves between wallets of the same investor leave the counts unchanged
+            self._decrease(from, amount);
+            self._increase(to, amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            self._increase(to, amount);
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            self._decrease(from, amount);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl CountryHolderLimitModuleImpl of super::ICountryHolderLimitModule<ContractState> {
+        fn set_country_cap(ref self: ContractState, country: felt252, cap: u32) -> bool {
+            // Only owner can change caps
+            self.ownable.assert_only_owner();
+            
+            self._set_country_cap(country, cap);
+            true
+        }
+        
+        fn batch_set_country_caps(ref self: ContractState, countries: Array<felt252>, caps: Array<u32>) -> bool {
+            // Only owner can change caps
+            self.ownable.assert_only_owner();
+            assert(countries.len() == caps.len(), 'Array length mismatch');
+            
+            let mut i: usize = 0;
+            loop {
+                if i >= countries.len() {
+                    break;
+                }
+                
+                self._set_country_cap(*countries.at(i), *caps.at(i));
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn get_country_cap(self: @ContractState, country: felt252) -> u32 {
+            self.country_caps.read(country)
+        }
+        
+        fn get_country_holder_count(self: @ContractState, country: felt252) -> u32 {
+            self.country_holder_counts.read(country)
+        }
+        
+        fn get_investor_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self.balances.investor_balance(self.compliance_map.read('compliance'), user_address)
+        }
+        
+        fn preset_investor_balance(ref self: ContractState, user_address: ContractAddress, balance: u256) -> bool {
+            // Token agents seed the holdings that predate the module
+            let compliance = self.compliance_map.read('compliance');
+            assert(is_token_agent(compliance, get_caller_address()), 'Only token agent');
+            
+            let (investor_key, was_holding, is_holding) = self.balances.preset(compliance, user_address, balance);
+            if !was_holding && is_holding {
+                self._count_holder(investor_key, user_address);
+            } else if was_holding && !is_holding {
+                self._uncount_holder(investor_key);
+            }
+            
+            self.emit(InvestorBalancePreset { investor_key, balance });
+            true
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _set_country_cap(ref self: ContractState, country: felt252, cap: u32) {
+            self.country_caps.write(country, cap);
+            self._record_config_change(array!['country_cap', country, cap.into()]);
+            self.emit(CountryCapSet { country, cap });
+        }
+        
+        fn _increase(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            let compliance = self.compliance_map.read('compliance');
+            let (key, started_holding) = self.balances.credit(compliance, user_address, amount);
+            if started_holding {
+                self._count_holder(key, user_address);
+            }
+        }
+        
+        fn _decrease(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            let compliance = self.compliance_map.read('compliance');
+            let (key, stopped_holding) = self.balances.debit(compliance, user_address, amount);
+            if stopped_holding {
+                self._uncount_holder(key);
+            }
+        }
+        
+        fn _count_holder(ref self: ContractState, key: felt252, user_address: ContractAddress) {
+            // Record the country the investor is counted under
+            let country = investor_country(self.compliance_map.read('compliance'), user_address);
+            self.investor_countries.write(key, country);
+            let holder_count = self.country_holder_counts.read(country);
+            self.country_holder_counts.write(country, holder_count + 1);
+        }
+        
+        fn _uncount_holder(ref self: ContractState, key: felt252) {
+            // Counted under the recorded country, whatever the registry says now
+            let country = self.investor_countries.read(key);
+            let holder_count = self.country_holder_counts.read(country);
+            if holder_count > 0 {
+                self.country_holder_counts.write(country, holder_count - 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Country Restrict Module
+//!
+//! Compliance module blocking transfers to or from investors whose country is on a
+//! restricted list, e.g. sanctioned jurisdictions.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::array::ArrayTrait;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{investor_country, chain_config_hash};
+
+// Country Restrict Module Interface
+#[starknet::interface]
+pub trait ICountryRestrictModule<TContractState> {
+    fn add_country_restriction(ref self: TContractState, country: felt252) -> bool;
+    fn remove_country_restriction(ref self: TContractState, country: felt252) -> bool;
+    fn batch_restrict_countries(ref self: TContractState, countries: Array<felt252>) -> bool;
+    fn batch_unrestrict_countries(ref self: TContractState, countries: Array<felt252>) -> bool;
+    fn is_country_restricted(self: @TContractState, country: felt252) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod CountryRestrictModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        AddedRestrictedCountry: AddedRestrictedCountry,
+        RemovedRestrictedCountry: RemovedRestrictedCountry,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct AddedRestrictedCountry {
+        country: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct RemovedRestrictedCountry {
+        country: felt252,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Restricted countries
+        restricted_countries: Map<felt252, bool>,
+        
+        // Chained hash of the restriction changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, initial_owner: ContractAddress, compliance: ContractAddress) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'CountryRestrictModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let compliance = self.compliance_map.read('compliance');
+            
+            // Mints have no sender to check
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address {
+                if self.restricted_countries.read(investor_country(compliance, from)) {
+                    return false;
+                }
+            }
+            
+            !self.restricted_countries.read(investor_country(compliance, to))
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl CountryRestrictModuleImpl of super::ICountryRestrictModule<ContractState> {
+        fn add_country_restriction(ref self: ContractState, country: felt252) -> bool {
+            // Only owner can restrict countries
+            self.ownable.assert_only_owner();
+            
+            assert(!self.restricted_countries.read(country), 'Country already restricted');
+            self._restrict(country);
+            true
+        }
+        
+        fn remove_country_restriction(ref self: ContractState, country: felt252) -> bool {
+            // Only owner can unrestrict countries
+            self.ownable.assert_only_owner();
+            
+            assert(self.restricted_countries.read(country), 'Country not restricted');
+            self._unrestrict(country);
+            true
+        }
+        
+        fn batch_restrict_countries(ref self: ContractState, countries: Array<felt252>) -> bool {
+            // Only owner can restrict countries
+            self.ownable.assert_only_owner();
+            
+            let mut i: usize = 0;
+            loop {
+                if i >= countries.len() {
+                    break;
+                }
+                
+                let country = *countries.at(i);
+                assert(!self.restricted_countries.read(country), 'Country already restricted');
+                self._restrict(country);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn batch_unrestrict_countries(ref self: ContractState, countries: Array<felt252>) -> bool {
+            // Only owner can unrestrict countries
+            self.ownable.assert_only_owner();
+            
+            let mut i: usize = 0;
+            loop {
+                if i >= countries.len() {
+                    break;
+                }
+                
+                let country = *countries.at(i);
+                assert(self.restricted_countries.read(country), 'Country not restricted');
+                self._unrestrict(country);
+                
+                i += 1;
+            };
+            
+            true
+        }
+        
+        fn is_country_restricted(self: @ContractState, country: felt252) -> bool {
+            self.restricted_countries.read(country)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _restrict(ref self: ContractState, country: felt252) {
+            self.restricted_countries.write(country, true);
+            self._record_config_change(array!['restrict', country]);
+            self.emit(AddedRestrictedCountry { country });
+        }
+        
+        fn _unrestrict(ref self: ContractState, country: felt252) {
+            self.restricted_countries.write(country, false);
+            self._record_config_change(array!['unrestrict', country]);
+            self.emit(RemovedRestrictedCountry { country });
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Daily Transfer Limit Module
+//!
+//! Compliance module capping the volume an investor can send per calendar day (UTC,
+//! derived from the block timestamp). Volumes are aggregated per identity so linked
+//! wallets share one allowance. A default limit applies to every investor and can be
+//! overridden per identity; a limit of 0 means unlimited. Mints are not limited.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{investor_identity, chain_config_hash};
+
+// Daily Transfer Limit Module Interface
+#[starknet::interface]
+pub trait IDailyTransferLimitModule<TContractState> {
+    fn set_default_limit(ref self: TContractState, limit: u256) -> bool;
+    fn set_investor_limit(ref self: TContractState, identity: felt252, limit: u256) -> bool;
+    fn remove_investor_limit(ref self: TContractState, identity: felt252) -> bool;
+    fn get_default_limit(self: @TContractState) -> u256;
+    fn get_limit(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn get_spent_today(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod DailyTransferLimitModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        DefaultLimitSet: DefaultLimitSet,
+        InvestorLimitSet: InvestorLimitSet,
+        InvestorLimitRemoved: InvestorLimitRemoved,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DefaultLimitSet {
+        limit: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct InvestorLimitSet {
+        identity: felt252,
+        limit: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct InvestorLimitRemoved {
+        identity: felt252,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Limits
+        default_limit_map: Map<felt252, u256>,  // Using 'default_limit' as key
+        investor_limits: Map<felt252, u256>,
+        investor_has_limit: Map<felt252, bool>,
+        
+        // Chained hash of the limit changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+        
+        // Per-investor counter, reset when the day changes
+        investor_days: Map<felt252, u64>,
+        investor_spent: Map<felt252, u256>,
+    }
+    
+    // Length of the counting window
+    const SECONDS_PER_DAY: u64 = 86400;
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        default_limit: u256
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.default_limit_map.write('default_limit', default_limit);
+        self._record_config_change(array!['default_limit', default_limit.low.into(), default_limit.high.into()]);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'DailyTransferLimitModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not limited
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            let from_key = self._investor_key(from);
+            let limit = self._limit_for(from_key);
+            if limit == 0 {
+                return true;
+            }
+            
+            self._spent_today(from_key) + amount <= limit
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let from_key = self._investor_key(from);
+            let spent = self._spent_today(from_key);
+            self.investor_days.write(from_key, self._current_day());
+            self.investor_spent.write(from_key, spent + amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Mints are not limited
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Burns are not limited
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl DailyTransferLimitModuleImpl of super::IDailyTransferLimitModule<ContractState> {
+        fn set_default_limit(ref self: ContractState, limit: u256) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            
+            self.default_limit_map.write('default_limit', limit);
+            self._record_config_change(array!['default_limit', limit.low.into(), limit.high.into()]);
+            self.emit(DefaultLimitSet { limit });
+            true
+        }
+        
+        fn set_investor_limit(ref self: ContractState, identity: felt252, limit: u256) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            
+            self.investor_limits.write(identity, limit);
+            self.investor_has_limit.write(identity, true);
+            self._record_config_change(array!['investor_limit', identity, limit.low.into(), limit.high.into()]);
+            self.emit(InvestorLimitSet { identity, limit });
+            true
+        }
+        
+        fn remove_investor_limit(ref self: ContractState, identity: felt252) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            
+            self.investor_limits.write(identity, 0);
+            self.investor_has_limit.write(identity, false);
+            self._record_config_change(array!['remove_investor_limit', identity]);
+            self.emit(InvestorLimitRemoved { identity });
+            true
+        }
+        
+        fn get_default_limit(self: @ContractState) -> u256 {
+            self.default_limit_map.read('default_limit')
+        }
+        
+        fn get_limit(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self._limit_for(self._investor_key(user_address))
+        }
+        
+        fn get_spent_today(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self._spent_today(self._investor_key(user_address))
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _investor_key(self: @ContractState, user_address: ContractAddress) -> felt252 {
+            // Wallets without identity are limited on their own
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn _limit_for(self: @ContractState, key: felt252) -> u256 {
+            if self.investor_has_limit.read(key) {
+                self.investor_limits.read(key)
+            } else {
+                self.default_limit_map.read('default_limit')
+            }
+        }
+        
+        fn _current_day(self: @ContractState) -> u64 {
+            get_block_timestamp() / SECONDS_PER_DAY
+        }
+        
+        fn _spent_today(self: @ContractState, key: felt252) -> u256 {
+            // The counter of a previous day no longer applies
+            if self.investor_days.read(key) != self._current_day() {
+                return 0;
+            }
+            self.investor_spent.read(key)
+        }
+    }
+}
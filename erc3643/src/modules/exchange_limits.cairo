@@ -0,0 +1,313 @@
+//! Exchange Limits Module
+//!
+//! Compliance module modelled after the T-REX TimeExchangeLimits module. The owner tags
+//! wallets as exchange wallets and gives each one a time window with a deposit limit
+//! (investor -> exchange) and a withdrawal limit (exchange -> investor). Volumes are
+//! aggregated per identity so linked wallets share one allowance. A limit of 0 means
+//! unlimited.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{investor_identity, chain_config_hash};
+
+// Exchange Limits Module Interface
+#[starknet::interface]
+pub trait IExchangeLimitsModule<TContractState> {
+    fn tag_exchange(ref self: TContractState, exchange: ContractAddress) -> bool;
+    fn untag_exchange(ref self: TContractState, exchange: ContractAddress) -> bool;
+    fn set_exchange_limits(
+        ref self: TContractState,
+        exchange: ContractAddress,
+        period: u64,
+        deposit_limit: u256,
+        withdrawal_limit: u256
+    ) -> bool;
+    fn is_exchange(self: @TContractState, wallet: ContractAddress) -> bool;
+    fn get_exchange_limits(self: @TContractState, exchange: ContractAddress) -> (u64, u256, u256);
+    fn get_deposited(self: @TContractState, user_address: ContractAddress, exchange: ContractAddress) -> u256;
+    fn get_withdrawn(self: @TContractState, user_address: ContractAddress, exchange: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod ExchangeLimitsModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        ExchangeTagged: ExchangeTagged,
+        ExchangeUntagged: ExchangeUntagged,
+        ExchangeLimitsSet: ExchangeLimitsSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ExchangeTagged {
+        #[key]
+        exchange: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ExchangeUntagged {
+        #[key]
+        exchange: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ExchangeLimitsSet {
+        #[key]
+        exchange: ContractAddress,
+        period: u64,
+        deposit_limit: u256,
+        withdrawal_limit: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Exchange wallets and their limits
+        exchanges: Map<ContractAddress, bool>,
+        exchange_periods: Map<ContractAddress, u64>,
+        deposit_limits: Map<ContractAddress, u256>,
+        withdrawal_limits: Map<ContractAddress, u256>,
+        
+        // Chained hash of the exchange changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+        
+        // Per-investor counters, by (investor, exchange, 'deposit' or 'withdrawal')
+        window_starts: Map<(felt252, ContractAddress, felt252), u64>,
+        window_volumes: Map<(felt252, ContractAddress, felt252), u256>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'ExchangeLimitsModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            // Deposit into an exchange
+            if self.exchanges.read(to) && !self.exchanges.read(from) {
+                let limit = self.deposit_limits.read(to);
+                if limit != 0 && self._volume(self._investor_key(from), to, 'deposit') + amount > limit {
+                    return false;
+                }
+            }
+            
+            // Withdrawal from an exchange
+            if self.exchanges.read(from) && !self.exchanges.read(to) {
+                let limit = self.withdrawal_limits.read(from);
+                if limit != 0 && self._volume(self._investor_key(to), from, 'withdrawal') + amount > limit {
+                    return false;
+                }
+            }
+            
+            true
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            if self.exchanges.read(to) && !self.exchanges.read(from) {
+                let from_key = self._investor_key(from);
+                self._record(from_key, to, 'deposit', amount);
+            }
+            
+            if self.exchanges.read(from) && !self.exchanges.read(to) {
+                let to_key = self._investor_key(to);
+                self._record(to_key, from, 'withdrawal', amount);
+            }
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Mints are not limited
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Burns are not limited
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl ExchangeLimitsModuleImpl of super::IExchangeLimitsModule<ContractState> {
+        fn tag_exchange(ref self: ContractState, exchange: ContractAddress) -> bool {
+            // Only owner can tag exchanges
+            self.ownable.assert_only_owner();
+            assert(!self.exchanges.read(exchange), 'Exchange already tagged');
+            
+            self.exchanges.write(exchange, true);
+            self._record_config_change(array!['tag_exchange', exchange.into()]);
+            self.emit(ExchangeTagged { exchange });
+            true
+        }
+        
+        fn untag_exchange(ref self: ContractState, exchange: ContractAddress) -> bool {
+            // Only owner can untag exchanges
+            self.ownable.assert_only_owner();
+            assert(self.exchanges.read(exchange), 'Exchange not tagged');
+            
+            self.exchanges.write(exchange, false);
+            self._record_config_change(array!['untag_exchange', exchange.into()]);
+            self.emit(ExchangeUntagged { exchange });
+            true
+        }
+        
+        fn set_exchange_limits(
+            ref self: ContractState,
+            exchange: ContractAddress,
+            period: u64,
+            deposit_limit: u256,
+            withdrawal_limit: u256
+        ) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            assert(self.exchanges.read(exchange), 'Exchange not tagged');
+            assert(period > 0, 'Invalid period');
+            
+            self.exchange_periods.write(exchange, period);
+            self.deposit_limits.write(exchange, deposit_limit);
+            self.withdrawal_limits.write(exchange, withdrawal_limit);
+            self._record_config_change(
+                array![
+                    'exchange_limits',
+                    exchange.into(),
+                    period.into(),
+                    deposit_limit.low.into(),
+                    deposit_limit.high.into(),
+                    withdrawal_limit.low.into(),
+                    withdrawal_limit.high.into()
+                ]
+            );
+            
+            self.emit(ExchangeLimitsSet { exchange, period, deposit_limit, withdrawal_limit });
+            true
+        }
+        
+        fn is_exchange(self: @ContractState, wallet: ContractAddress) -> bool {
+            self.exchanges.read(wallet)
+        }
+        
+        fn get_exchange_limits(self: @ContractState, exchange: ContractAddress) -> (u64, u256, u256) {
+            (
+                self.exchange_periods.read(exchange),
+                self.deposit_limits.read(exchange),
+                self.withdrawal_limits.read(exchange)
+            )
+        }
+        
+        fn get_deposited(self: @ContractState, user_address: ContractAddress, exchange: ContractAddress) -> u256 {
+            self._volume(self._investor_key(user_address), exchange, 'deposit')
+        }
+        
+        fn get_withdrawn(self: @ContractState, user_address: ContractAddress, exchange: ContractAddress) -> u256 {
+            self._volume(self._investor_key(user_address), exchange, 'withdrawal')
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _investor_key(self: @ContractState, user_address: ContractAddress) -> felt252 {
+            // Wallets without identity are limited on their own
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn _volume(self: @ContractState, key: felt252, exchange: ContractAddress, direction: felt252) -> u256 {
+            // Volume of an elapsed window no longer applies
+            let window_start = self.window_starts.read((key, exchange, direction));
+            if get_block_timestamp() >= window_start + self.exchange_periods.read(exchange) {
+                return 0;
+            }
+            self.window_volumes.read((key, exchange, direction))
+        }
+        
+        fn _record(ref self: ContractState, key: felt252, exchange: ContractAddress, direction: felt252, amount: u256) {
+            let volume = self._volume(key, exchange, direction);
+            
+            // Open a new window when the previous one has elapsed
+            if volume == 0 {
+                self.window_starts.write((key, exchange, direction), get_block_timestamp());
+            }
+            self.window_volumes.write((key, exchange, direction), volume + amount);
+        }
+    }
+}
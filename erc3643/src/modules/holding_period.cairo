@@ -0,0 +1,236 @@
+//! Holding Period Module
+//!
+//! Compliance module enforcing a minimum holding period (e.g. one year under Rule 144).
+//! Every acquisition opens a lot on the receiving wallet with its timestamp; transfers
+//! may only spend lots held for at least the holding period. Lots are consumed oldest
+//! first, so partially spent lots keep their original acquisition time.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+
+// Holding Period Module Interface
+#[starknet::interface]
+pub trait IHoldingPeriodModule<TContractState> {
+    fn set_holding_period(ref self: TContractState, holding_period: u64) -> bool;
+    fn get_holding_period(self: @TContractState) -> u64;
+    fn get_transferable_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn get_lot_count(self: @TContractState, user_address: ContractAddress) -> u32;
+    fn get_lot(self: @TContractState, user_address: ContractAddress, index: u32) -> (u256, u64);
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod HoldingPeriodModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        HoldingPeriodSet: HoldingPeriodSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct HoldingPeriodSet {
+        holding_period: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Minimum holding period in seconds
+        holding_period_map: Map<felt252, u64>,  // Using 'period' as key
+        
+        // Acquisition lots per wallet, as a queue from head (oldest) to tail
+        lot_heads: Map<ContractAddress, u32>,
+        lot_tails: Map<ContractAddress, u32>,
+        lot_amounts: Map<(ContractAddress, u32), u256>,
+        lot_times: Map<(ContractAddress, u32), u64>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        holding_period: u64
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.holding_period_map.write('period', holding_period);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'HoldingPeriodModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            core::poseidon::poseidon_hash_span(array![self.holding_period_map.read('period').into()].span())
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not restricted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            self._transferable_balance(from) >= amount
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            self._consume_lots(from, amount);
+            self._add_lot(to, amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            self._add_lot(to, amount);
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            self._consume_lots(from, amount);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl HoldingPeriodModuleImpl of super::IHoldingPeriodModule<ContractState> {
+        fn set_holding_period(ref self: ContractState, holding_period: u64) -> bool {
+            // Only owner can change the holding period
+            self.ownable.assert_only_owner();
+            
+            self.holding_period_map.write('period', holding_period);
+            self.emit(HoldingPeriodSet { holding_period });
+            true
+        }
+        
+        fn get_holding_period(self: @ContractState) -> u64 {
+            self.holding_period_map.read('period')
+        }
+        
+        fn get_transferable_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self._transferable_balance(user_address)
+        }
+        
+        fn get_lot_count(self: @ContractState, user_address: ContractAddress) -> u32 {
+            self.lot_tails.read(user_address) - self.lot_heads.read(user_address)
+        }
+        
+        fn get_lot(self: @ContractState, user_address: ContractAddress, index: u32) -> (u256, u64) {
+            let position = self.lot_heads.read(user_address) + index;
+            assert(position < self.lot_tails.read(user_address), 'Lot not found');
+            (self.lot_amounts.read((user_address, position)), self.lot_times.read((user_address, position)))
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _transferable_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            let now = get_block_timestamp();
+            let holding_period = self.holding_period_map.read('period');
+            let tail = self.lot_tails.read(user_address);
+            let mut i = self.lot_heads.read(user_address);
+            let mut transferable: u256 = 0;
+            while i < tail {
+                // Lots are ordered by acquisition time, later ones are younger
+                if self.lot_times.read((user_address, i)) + holding_period > now {
+                    break;
+                }
+                transferable += self.lot_amounts.read((user_address, i));
+                i += 1;
+            };
+            transferable
+        }
+        
+        fn _add_lot(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            if amount == 0 {
+                return;
+            }
+            
+            // Acquisitions in the same block share one lot
+            let now = get_block_timestamp();
+            let head = self.lot_heads.read(user_address);
+            let tail = self.lot_tails.read(user_address);
+            if tail > head && self.lot_times.read((user_address, tail - 1)) == now {
+                let last_amount = self.lot_amounts.read((user_address, tail - 1));
+                self.lot_amounts.write((user_address, tail - 1), last_amount + amount);
+                return;
+            }
+            
+            self.lot_amounts.write((user_address, tail), amount);
+            self.lot_times.write((user_address, tail), now);
+            self.lot_tails.write(user_address, tail + 1);
+        }
+        
+        fn _consume_lots(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            // Spend oldest lots first, forced transfers may also reach young lots
+            let tail = self.lot_tails.read(user_address);
+            let mut head = self.lot_heads.read(user_address);
+            let mut remaining = amount;
+            while remaining > 0 && head < tail {
+                let lot_amount = self.lot_amounts.read((user_address, head));
+                if lot_amount > remaining {
+                    self.lot_amounts.write((user_address, head), lot_amount - remaining);
+                    remaining = 0;
+                } else {
+                    remaining -= lot_amount;
+                    self.lot_amounts.write((user_address, head), 0);
+                    self.lot_times.write((user_address, head), 0);
+                    head += 1;
+                }
+            };
+            self.lot_heads.write(user_address, head);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Investor Category Caps Module
+//!
+//! Compliance module capping the balance an investor may hold depending on its
+//! accreditation category (retail, accredited, institutional) recorded in the identity
+//! storage, e.g. Reg CF limits for retail investors. Balances are aggregated per
+//! identity so linked wallets share one cap. A cap of 0 means unlimited.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::identity_storage::CATEGORY_INSTITUTIONAL;
+use crate::modules::common::{investor_identity, investor_category};
+
+// Investor Category Caps Module Interface
+#[starknet::interface]
+pub trait IInvestorCategoryCapsModule<TContractState> {
+    fn set_category_cap(ref self: TContractState, category: u8, max_balance: u256) -> bool;
+    fn get_category_cap(self: @TContractState, category: u8) -> u256;
+    fn get_investor_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod InvestorCategoryCapsModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        CategoryCapSet: CategoryCapSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CategoryCapSet {
+        category: u8,
+        max_balance: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Maximum balance per investor category
+        category_caps: Map<u8, u256>,
+        
+        // Balance per investor (identity, or wallet if unregistered)
+        investor_balances: Map<felt252, u256>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'InvestorCategoryCapsModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            // Cap of every category, retail to institutional
+            let mut data = ArrayTrait::<felt252>::new();
+            let mut category: u8 = 0;
+            while category <= CATEGORY_INSTITUTIONAL {
+                let cap = self.category_caps.read(category);
+                data.append(cap.low.into());
+                data.append(cap.high.into());
+                category += 1;
+            };
+            core::poseidon::poseidon_hash_span(data.span())
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let to_key = self._investor_key(to);
+            
+            // Moves between wallets of the same investor change nothing
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address && self._investor_key(from) == to_key {
+                return true;
+            }
+            
+            let cap = self.category_caps.read(investor_category(self.compliance_map.read('compliance'), to));
+            if cap == 0 {
+                return true;
+            }
+            
+            self.investor_balances.read(to_key) + amount <= cap
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Moves between wallets of the same investor change nothing
+            let from_key = self._investor_key(from);
+            let to_key = self._investor_key(to);
+            if from_key == to_key {
+                return;
+            }
+            
+            self._decrease(from_key, amount);
+            self._increase(to_key, amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let to_key = self._investor_key(to);
+            self._increase(to_key, amount);
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let from_key = self._investor_key(from);
+            self._decrease(from_key, amount);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl InvestorCategoryCapsModuleImpl of super::IInvestorCategoryCapsModule<ContractState> {
+        fn set_category_cap(ref self: ContractState, category: u8, max_balance: u256) -> bool {
+            // Only owner can change caps
+            self.ownable.assert_only_owner();
+            assert(category <= CATEGORY_INSTITUTIONAL, 'Invalid investor category');
+            
+            self.category_caps.write(category, max_balance);
+            self.emit(CategoryCapSet { category, max_balance });
+            true
+        }
+        
+        fn get_category_cap(self: @ContractState, category: u8) -> u256 {
+            self.category_caps.read(category)
+        }
+        
+        fn get_investor_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self.investor_balances.read(self._investor_key(user_address))
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _investor_key(self: @ContractState, user_address: ContractAddress) -> felt252 {
+            // Wallets without identity are counted on their own
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn _increase(ref self: ContractState, key: felt252, amount: u256) {
+            let balance = self.investor_balances.read(key);
+            self.investor_balances.write(key, balance + amount);
+        }
+        
+        fn _decrease(ref self: ContractState, key: felt252, amount: u256) {
+            let balance = self.investor_balances.read(key);
+            assert(balance >= amount, 'Investor balance too low');
+            self.investor_balances.write(key, balance - amount);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Investor Value Cap Module
+//!
+//! Compliance module capping the fiat value an investor may hold (e.g. at most EUR 100k
+//! per investor), valued with a price oracle feed of the token. The cap is expressed in
+//! the units of the feed, i.e. with its decimals. Transfers and mints to an investor fail
+//! while the price is stale or its confidence interval is too wide. Balances are
+//! aggregated per identity so linked wallets share one cap. A cap of 0 means unlimited.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::interfaces::iprice_oracle::checked_price;
+use crate::modules::common::{investor_identity, token_decimals};
+
+// Investor Value Cap Module Interface
+#[starknet::interface]
+pub trait IInvestorValueCapModule<TContractState> {
+    fn set_price_feed(
+        ref self: TContractState,
+        oracle: ContractAddress,
+        feed_id: felt252,
+        max_age: u64,
+        max_confidence_bps: u32
+    ) -> bool;
+    fn get_price_feed(self: @TContractState) -> (ContractAddress, felt252, u64, u32);
+    fn set_max_investor_value(ref self: TContractState, max_value: u256) -> bool;
+    fn get_max_investor_value(self: @TContractState) -> u256;
+    fn get_investor_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod InvestorValueCapModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        PriceFeedSet: PriceFeedSet,
+        MaxInvestorValueSet: MaxInvestorValueSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PriceFeedSet {
+        oracle: ContractAddress,
+        feed_id: felt252,
+        max_age: u64,
+        max_confidence_bps: u32,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MaxInvestorValueSet {
+        max_value: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Price feed of the token, using 'oracle', 'feed_id', 'max_age' and 'max_confidence' as keys
+        oracle_map: Map<felt252, ContractAddress>,
+        feed_config_map: Map<felt252, felt252>,
+        
+        // Maximum value per investor, in the units of the price feed
+        max_value_map: Map<felt252, u256>,  // Using 'max_value' as key
+        
+        // Balance per investor (identity, or wallet if unregistered)
+        investor_balances: Map<felt252, u256>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'InvestorValueCapModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            let max_value = self.max_value_map.read('max_value');
+            core::poseidon::poseidon_hash_span(
+                array![
+                    self.oracle_map.read('oracle').into(),
+                    self.feed_config_map.read('feed_id'),
+                    self.feed_config_map.read('max_age'),
+                    self.feed_config_map.read('max_confidence'),
+                    max_value.low.into(),
+                    max_value.high.into()
+                ].span()
+            )
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let to_key = self._investor_key(to);
+            
+            // Moves between wallets of the same investor change nothing
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address && self._investor_key(from) == to_key {
+                return true;
+            }
+            
+            let max_value = self.max_value_map.read('max_value');
+            if max_value == 0 {
+                return true;
+            }
+            
+            // Fail closed when the price cannot be trusted
+            let (oracle, feed_id, max_age, max_confidence_bps) = self.get_price_feed();
+            match checked_price(oracle, feed_id, max_age, max_confidence_bps) {
+                Option::Some((price, _)) => {
+                    let holding = self.investor_balances.read(to_key) + amount;
+                    holding * price / self._unit() <= max_value
+                },
+                Option::None => false,
+            }
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Moves between wallets of the same investor change nothing
+            let from_key = self._investor_key(from);
+            let to_key = self._investor_key(to);
+            if from_key == to_key {
+                return;
+            }
+            
+            self._decrease(from_key, amount);
+            self._increase(to_key, amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let to_key = self._investor_key(to);
+            self._increase(to_key, amount);
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let from_key = self._investor_key(from);
+            self._decrease(from_key, amount);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl InvestorValueCapModuleImpl of super::IInvestorValueCapModule<ContractState> {
+        fn set_price_feed(
+            ref self: ContractState,
+            oracle: ContractAddress,
+            feed_id: felt252,
+            max_age: u64,
+            max_confidence_bps: u32
+        ) -> bool {
+            // Only owner can change the price feed
+            self.ownable.assert_only_owner();
+            assert(max_age > 0, 'Invalid max age');
+            assert(max_confidence_bps <= 10000, 'Invalid max confidence');
+            
+            self.oracle_map.write('oracle', oracle);
+            self.feed_config_map.write('feed_id', feed_id);
+            self.feed_config_map.write('max_age', max_age.into());
+            self.feed_config_map.write('max_confidence', max_confidence_bps.into());
+            
+            self.emit(PriceFeedSet { oracle, feed_id, max_age, max_confidence_bps });
+            true
+        }
+        
+        fn get_price_feed(self: @ContractState) -> (ContractAddress, felt252, u64, u32) {
+            (
+                self.oracle_map.read('oracle'),
+                self.feed_config_map.read('feed_id'),
+                self.feed_config_map.read('max_age').try_into().unwrap(),
+                self.feed_config_map.read('max_confidence').try_into().unwrap()
+            )
+        }
+        
+        fn set_max_investor_value(ref self: ContractState, max_value: u256) -> bool {
+            // Only owner can change the cap, which needs a price feed
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(max_value == 0 || self.oracle_map.read('oracle') != zero_address, 'Price feed not set');
+            
+            self.max_value_map.write('max_value', max_value);
+            self.emit(MaxInvestorValueSet { max_value });
+            true
+        }
+        
+        fn get_max_investor_value(self: @ContractState) -> u256 {
+            self.max_value_map.read('max_value')
+        }
+        
+        fn get_investor_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self.investor_balances.read(self._investor_key(user_address))
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _investor_key(self: @ContractState, user_address: ContractAddress) -> felt252 {
+            // Wallets without identity are counted on their own
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn _unit(self: @ContractState) -> u256 {
+            // 10^decimals of the bound token
+            let decimals = token_decimals(self.compliance_map.read('compliance'));
+            let mut unit: u256 = 1;
+            let mut i: u8 = 0;
+            while i < decimals {
+                unit *= 10;
+                i += 1;
+            };
+            unit
+        }
+        
+        fn _increase(ref self: ContractState, key: felt252, amount: u256) {
+            let balance = self.investor_balances.read(key);
+            self.investor_balances.write(key, balance + amount);
+        }
+        
+        fn _decrease(ref self: ContractState, key: felt252, amount: u256) {
+            let balance = self.investor_balances.read(key);
+            assert(balance >= amount, 'Investor balance too low');
+            self.investor_balances.write(key, balance - amount);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! Lockup Module
+//!
+//! Compliance module locking tokens received at primary issuance. Every mint opens a
+//! lot on the receiving wallet, locked until a release timestamp: the investor release
+//! time if the owner set one for the identity, otherwise the release time of the current
+//! issuance batch. Transfers may only spend the unlocked part of the balance.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{investor_identity, wallet_balance, chain_config_hash};
+
+// Lockup Module Interface
+#[starknet::interface]
+pub trait ILockupModule<TContractState> {
+    fn set_batch_release_time(ref self: TContractState, release_time: u64) -> bool;
+    fn set_investor_release_time(ref self: TContractState, identity: felt252, release_time: u64) -> bool;
+    fn get_batch_release_time(self: @TContractState) -> u64;
+    fn get_investor_release_time(self: @TContractState, identity: felt252) -> u64;
+    fn get_locked_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn get_unlocked_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod LockupModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        BatchReleaseTimeSet: BatchReleaseTimeSet,
+        InvestorReleaseTimeSet: InvestorReleaseTimeSet,
+        TokensLocked: TokensLocked,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct BatchReleaseTimeSet {
+        release_time: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct InvestorReleaseTimeSet {
+        identity: felt252,
+        release_time: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TokensLocked {
+        #[key]
+        wallet: ContractAddress,
+        amount: u256,
+        release_time: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Release times
+        batch_release_map: Map<felt252, u64>,  // Using 'batch' as key
+        investor_release_times: Map<felt252, u64>,
+        
+        // Chained hash of the release time changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+        
+        // Locked lots per wallet
+        lot_counts: Map<ContractAddress, u32>,
+        lot_amounts: Map<(ContractAddress, u32), u256>,
+        lot_releases: Map<(ContractAddress, u32), u64>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        batch_release_time: u64
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.batch_release_map.write('batch', batch_release_time);
+        self._record_config_change(array!['batch_release', batch_release_time.into()]);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'LockupModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not restricted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            self._unlocked_balance(from) >= amount
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Forced transfers may have taken locked tokens
+            self._settle_lots(from);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let release_time = self._release_time_for(to);
+            if amount == 0 || release_time <= get_block_timestamp() {
+                return;
+            }
+            
+            let count = self.lot_counts.read(to);
+            self.lot_amounts.write((to, count), amount);
+            self.lot_releases.write((to, count), release_time);
+            self.lot_counts.write(to, count + 1);
+            
+            self.emit(TokensLocked { wallet: to, amount, release_time });
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Burns may have taken locked tokens
+            self._settle_lots(from);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl LockupModuleImpl of super::ILockupModule<ContractState> {
+        fn set_batch_release_time(ref self: ContractState, release_time: u64) -> bool {
+            // Only owner can change release times
+            self.ownable.assert_only_owner();
+            
+            self.batch_release_map.write('batch', release_time);
+            self._record_config_change(array!['batch_release', release_time.into()]);
+            self.emit(BatchReleaseTimeSet { release_time });
+            true
+        }
+        
+        fn set_investor_release_time(ref self: ContractState, identity: felt252, release_time: u64) -> bool {
+            // Only owner can change release times
+            self.ownable.assert_only_owner();
+            
+            self.investor_release_times.write(identity, release_time);
+            self._record_config_change(array!['investor_release', identity, release_time.into()]);
+            self.emit(InvestorReleaseTimeSet { identity, release_time });
+            true
+        }
+        
+        fn get_batch_release_time(self: @ContractState) -> u64 {
+            self.batch_release_map.read('batch')
+        }
+        
+        fn get_investor_release_time(self: @ContractState, identity: felt252) -> u64 {
+            self.investor_release_times.read(identity)
+        }
+        
+        fn get_locked_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self._locked_balance(user_address)
+        }
+        
+        fn get_unlocked_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self._unlocked_balance(user_address)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _release_time_for(self: @ContractState, user_address: ContractAddress) -> u64 {
+            // Investor release time takes precedence over the batch one
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                let investor_release = self.investor_release_times.read(identity);
+                if investor_release != 0 {
+                    return investor_release;
+                }
+            }
+            self.batch_release_map.read('batch')
+        }
+        
+        fn _locked_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            let now = get_block_timestamp();
+            let count = self.lot_counts.read(user_address);
+            let mut locked: u256 = 0;
+            let mut i: u32 = 0;
+            while i < count {
+                if self.lot_releases.read((user_address, i)) > now {
+                    locked += self.lot_amounts.read((user_address, i));
+                }
+                i += 1;
+            };
+            locked
+        }
+        
+        fn _unlocked_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            let balance = wallet_balance(self.compliance_map.read('compliance'), user_address);
+            let locked = self._locked_balance(user_address);
+            if balance > locked {
+                balance - locked
+            } else {
+                0
+            }
+        }
+        
+        fn _settle_lots(ref self: ContractState, user_address: ContractAddress) {
+            // Drop released lots and shrink the latest ones so locks never exceed the balance
+            let now = get_block_timestamp();
+            let mut remaining = wallet_balance(self.compliance_map.read('compliance'), user_address);
+            let count = self.lot_counts.read(user_address);
+            let mut kept: u32 = 0;
+            let mut i: u32 = 0;
+            while i < count {
+                let amount = self.lot_amounts.read((user_address, i));
+                let release_time = self.lot_releases.read((user_address, i));
+                if release_time > now && remaining > 0 {
+                    let kept_amount = if amount > remaining { remaining } else { amount };
+                    remaining -= kept_amount;
+                    self.lot_amounts.write((user_address, kept), kept_amount);
+                    self.lot_releases.write((user_address, kept), release_time);
+                    kept += 1;
+                }
+                i += 1;
+            };
+            
+            // Clear the slots left behind
+            let mut j = kept;
+            while j < count {
+                self.lot_amounts.write((user_address, j), 0);
+                self.lot_releases.write((user_address, j), 0);
+                j += 1;
+            };
+            self.lot_counts.write(user_address, kept);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Max Holders Module
+//!
+//! Compliance module capping the number of investors holding a non-zero balance.
+//! Investors are counted per identity, so wallets linked to the same identity count
+//! once. Balances are tracked from the token's action hooks, so the module should be
+//! registered before the first mint; when it is added to a live token, agents preset the
+//! balances of the existing holders.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{InvestorBalancesComponent, is_token_agent};
+
+// Max Holders Module Interface
+#[starknet::interface]
+pub trait IMaxHoldersModule<TContractState> {
+    fn set_max_holders(ref self: TContractState, max_holders: u32) -> bool;
+    fn get_max_holders(self: @TContractState) -> u32;
+    fn get_holder_count(self: @TContractState) -> u32;
+    fn get_investor_balance(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn preset_investor_balance(ref self: TContractState, user_address: ContractAddress, balance: u256) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod MaxHoldersModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    component!(path: InvestorBalancesComponent, storage: balances, event: InvestorBalancesEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    impl InvestorBalancesInternalImpl = InvestorBalancesComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        #[flat]
+        InvestorBalancesEvent: InvestorBalancesComponent::Event,
+        MaxHoldersSet: MaxHoldersSet,
+        InvestorBalancePreset: InvestorBalancePreset,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MaxHoldersSet {
+        max_holders: u32,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct InvestorBalancePreset {
+        #[key]
+        investor_key: felt252,
+        balance: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Aggregated balance per investor, keeping the `investor_balances` slot of earlier releases
+        #[substorage(v0)]
+        balances: InvestorBalancesComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Limit and current number of holders
+        max_holders_map: Map<felt252, u32>,  // Using 'max_holders' as key
+        holder_count_map: Map<felt252, u32>,  // Using 'holder_count' as key
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        max_holders: u32
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.max_holders_map.write('max_holders', max_holders);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'MaxHoldersModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            core::poseidon::poseidon_hash_span(array![self.max_holders_map.read('max_holders').into()].span())
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let compliance = self.compliance_map.read('compliance');
+            let to_key = self.balances.investor_key(compliance, to);
+            
+            // Existing holders and empty transfers never increase the count
+            if amount == 0 || self.balances.key_balance(to_key) > 0 {
+                return true;
+            }
+            
+            let mut holder_count = self.holder_count_map.read('holder_count');
+            
+            // A sender moving its whole position out frees its slot
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address {
+                let from_key = self.balances.investor_key(compliance, from);
+                if from_key != to_key && self.balances.key_balance(from_key) == amount {
+                    holder_count -= 1;
+                }
+            }
+            
+            holder_count + 1 <= self.max_holders_map.read('max_holders')
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            // Moves between wallets of the same investor leave the count unchanged
+            self._decrease(from, amount);
+            self._increase(to, amount);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            self._increase(to, amount);
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            self._decrease(from, amount);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl MaxHoldersModuleImpl of super::IMaxHoldersModule<ContractState> {
+        fn set_max_holders(ref self: ContractState, max_holders: u32) -> bool {
+            // Only owner can change the limit
+            self.ownable.assert_only_owner();
+            
+            self.max_holders_map.write('max_holders', max_holders);
+            self.emit(MaxHoldersSet { max_holders });
+            true
+        }
+        
+        fn get_max_holders(self: @ContractState) -> u32 {
+            self.max_holders_map.read('max_holders')
+        }
+        
+        fn get_holder_count(self: @ContractState) -> u32 {
+            self.holder_count_map.read('holder_count')
+        }
+        
+        fn get_investor_balance(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self.balances.investor_balance(self.compliance_map.read('compliance'), user_address)
+        }
+        
+        fn preset_investor_balance(ref self: ContractState, user_address: ContractAddress, balance: u256) -> bool {
+            // Token agents seed the holdings that predate the module
+            let compliance = self.compliance_map.read('compliance');
+            assert(is_token_agent(compliance, get_caller_address()), 'Only token agent');
+            
+            let (investor_key, was_holding, is_holding) = self.balances.preset(compliance, user_address, balance);
+            let holder_count = self.holder_count_map.read('holder_count');
+            if !was_holding && is_holding {
+                self.holder_count_map.write('holder_count', holder_count + 1);
+            } else if was_holding && !is_holding {
+                self.holder_count_map.write('holder_count', holder_count - 1);
+            }
+            
+            self.emit(InvestorBalancePreset { investor_key, balance });
+            true
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _increase(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            let compliance = self.compliance_map.read('compliance');
+            let (_, started_holding) = self.balances.credit(compliance, user_address, amount);
+            if started_holding {
+                let holder_count = self.holder_count_map.read('holder_count');
+                self.holder_count_map.write('holder_count', holder_count + 1);
+            }
+        }
+        
+        fn _decrease(ref self: ContractState, user_address: ContractAddress, amount: u256) {
+            let compliance = self.compliance_map.read('compliance');
+            let (_, stopped_holding) = self.balances.debit(compliance, user_address, amount);
+            if stopped_holding {
+                let holder_count = self.holder_count_map.read('holder_count');
+                self.holder_count_map.write('holder_count', holder_count - 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Time Transfer Limits Module
+//!
+//! Generalization of the daily limit: the owner configures up to `MAX_LIMITS` limits,
+//! each capping the volume an investor can send within a period of a given length
+//! (e.g. `WEEK`, `MONTH`, `QUARTER`). Each investor window starts with its first
+//! transfer after the previous window has elapsed. Volumes are aggregated per identity
+//! so linked wallets share one allowance. Mints and burns are not limited.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::investor_identity;
+
+// Common period lengths, in seconds
+pub const DAY: u64 = 86400;
+pub const WEEK: u64 = 604800;
+pub const MONTH: u64 = 2592000;  // 30 days
+pub const QUARTER: u64 = 7776000;  // 90 days
+
+// Time Transfer Limits Module Interface
+#[starknet::interface]
+pub trait ITimeTransferLimitsModule<TContractState> {
+    fn set_time_transfer_limit(ref self: TContractState, period: u64, limit: u256) -> bool;
+    fn remove_time_transfer_limit(ref self: TContractState, period: u64) -> bool;
+    fn get_time_transfer_limits(self: @TContractState) -> Array<(u64, u256)>;
+    fn get_spent(self: @TContractState, user_address: ContractAddress, period: u64) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod TimeTransferLimitsModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        TimeTransferLimitUpdated: TimeTransferLimitUpdated,
+        TimeTransferLimitRemoved: TimeTransferLimitRemoved,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TimeTransferLimitUpdated {
+        period: u64,
+        limit: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TimeTransferLimitRemoved {
+        period: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Configured limits
+        limit_count_map: Map<felt252, u32>,  // Using 'count' as key
+        limit_periods: Map<u32, u64>,  // Index to period mapping
+        limit_values: Map<u32, u256>,  // Index to limit mapping
+        period_indexes: Map<u64, u32>,  // Period to index mapping (1-based, 0 if not set)
+        
+        // Per-investor counters, by (investor, period)
+        window_starts: Map<(felt252, u64), u64>,
+        window_spent: Map<(felt252, u64), u256>,
+    }
+    
+    // Maximum number of simultaneous limits
+    const MAX_LIMITS: u32 = 4;
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'TimeTransferLimitsModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            // Limits in storage order
+            let count = self.limit_count_map.read('count');
+            let mut data = array![count.into()];
+            let mut i: u32 = 0;
+            while i < count {
+                let limit = self.limit_values.read(i);
+                data.append(self.limit_periods.read(i).into());
+                data.append(limit.low.into());
+                data.append(limit.high.into());
+                i += 1;
+            };
+            core::poseidon::poseidon_hash_span(data.span())
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not limited
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            let from_key = self._investor_key(from);
+            let count = self.limit_count_map.read('count');
+            let mut i: u32 = 0;
+            let mut allowed = true;
+            while i < count {
+                let period = self.limit_periods.read(i);
+                if self._spent_in_window(from_key, period) + amount > self.limit_values.read(i) {
+                    allowed = false;
+                    break;
+                }
+                i += 1;
+            };
+            
+            allowed
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let from_key = self._investor_key(from);
+            let now = get_block_timestamp();
+            let count = self.limit_count_map.read('count');
+            let mut i: u32 = 0;
+            while i < count {
+                let period = self.limit_periods.read(i);
+                let spent = self._spent_in_window(from_key, period);
+                
+                // Open a new window when the previous one has elapsed
+                if spent == 0 {
+                    self.window_starts.write((from_key, period), now);
+                }
+                self.window_spent.write((from_key, period), spent + amount);
+                i += 1;
+            };
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Mints are not limited
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Burns are not limited
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl TimeTransferLimitsModuleImpl of super::ITimeTransferLimitsModule<ContractState> {
+        fn set_time_transfer_limit(ref self: ContractState, period: u64, limit: u256) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            assert(period > 0, 'Invalid period');
+            
+            let index = self.period_indexes.read(period);
+            if index != 0 {
+                // Update existing limit
+                self.limit_values.write(index - 1, limit);
+            } else {
+                let count = self.limit_count_map.read('count');
+                assert(count < MAX_LIMITS, 'Too many limits');
+                
+                self.limit_periods.write(count, period);
+                self.limit_values.write(count, limit);
+                self.period_indexes.write(period, count + 1);
+                self.limit_count_map.write('count', count + 1);
+            }
+            
+            self.emit(TimeTransferLimitUpdated { period, limit });
+            true
+        }
+        
+        fn remove_time_transfer_limit(ref self: ContractState, period: u64) -> bool {
+            // Only owner can change limits
+            self.ownable.assert_only_owner();
+            
+            let index = self.period_indexes.read(period);
+            assert(index != 0, 'Limit not found');
+            
+            // Move the last limit into the freed slot
+            let count = self.limit_count_map.read('count');
+            let last_index = count - 1;
+            if index - 1 != last_index {
+                let last_period = self.limit_periods.read(last_index);
+                self.limit_periods.write(index - 1, last_period);
+                self.limit_values.write(index - 1, self.limit_values.read(last_index));
+                self.period_indexes.write(last_period, index);
+            }
+            
+            self.limit_periods.write(last_index, 0);
+            self.limit_values.write(last_index, 0);
+            self.period_indexes.write(period, 0);
+            self.limit_count_map.write('count', last_index);
+            
+            self.emit(TimeTransferLimitRemoved { period });
+            true
+        }
+        
+        fn get_time_transfer_limits(self: @ContractState) -> Array<(u64, u256)> {
+            let mut limits = ArrayTrait::new();
+            let count = self.limit_count_map.read('count');
+            let mut i: u32 = 0;
+            while i < count {
+                limits.append((self.limit_periods.read(i), self.limit_values.read(i)));
+                i += 1;
+            };
+            limits
+        }
+        
+        fn get_spent(self: @ContractState, user_address: ContractAddress, period: u64) -> u256 {
+            self._spent_in_window(self._investor_key(user_address), period)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _investor_key(self: @ContractState, user_address: ContractAddress) -> felt252 {
+            // Wallets without identity are limited on their own
+            let identity = investor_identity(self.compliance_map.read('compliance'), user_address);
+            if identity != 0 {
+                identity
+            } else {
+                user_address.into()
+            }
+        }
+        
+        fn _spent_in_window(self: @ContractState, key: felt252, period: u64) -> u256 {
+            // Volume of an elapsed window no longer applies
+            let window_start = self.window_starts.read((key, period));
+            if get_block_timestamp() >= window_start + period {
+                return 0;
+            }
+            self.window_spent.read((key, period))
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Transfer Allow-list Module
+//!
+//! Compliance module restricting secondary trading: a transfer is only allowed if the
+//! (from, to) pair is explicitly allowed or if the recipient is an allowed counterparty
+//! (e.g. the issuer or a designated market maker). Mints and burns are not restricted.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::chain_config_hash;
+
+// Transfer Allow-list Module Interface
+#[starknet::interface]
+pub trait ITransferAllowlistModule<TContractState> {
+    fn allow_pair(ref self: TContractState, from: ContractAddress, to: ContractAddress) -> bool;
+    fn disallow_pair(ref self: TContractState, from: ContractAddress, to: ContractAddress) -> bool;
+    fn allow_counterparty(ref self: TContractState, counterparty: ContractAddress) -> bool;
+    fn disallow_counterparty(ref self: TContractState, counterparty: ContractAddress) -> bool;
+    fn is_pair_allowed(self: @TContractState, from: ContractAddress, to: ContractAddress) -> bool;
+    fn is_counterparty_allowed(self: @TContractState, counterparty: ContractAddress) -> bool;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod TransferAllowlistModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        PairAllowed: PairAllowed,
+        PairDisallowed: PairDisallowed,
+        CounterpartyAllowed: CounterpartyAllowed,
+        CounterpartyDisallowed: CounterpartyDisallowed,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PairAllowed {
+        #[key]
+        from: ContractAddress,
+        #[key]
+        to: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PairDisallowed {
+        #[key]
+        from: ContractAddress,
+        #[key]
+        to: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CounterpartyAllowed {
+        #[key]
+        counterparty: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct CounterpartyDisallowed {
+        #[key]
+        counterparty: ContractAddress,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Allow-lists
+        allowed_pairs: Map<(ContractAddress, ContractAddress), bool>,
+        allowed_counterparties: Map<ContractAddress, bool>,
+        
+        // Chained hash of the allow-list changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'TransferAllowlistModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not restricted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            self.allowed_counterparties.read(to) || self.allowed_pairs.read((from, to))
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Stateless module
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl TransferAllowlistModuleImpl of super::ITransferAllowlistModule<ContractState> {
+        fn allow_pair(ref self: ContractState, from: ContractAddress, to: ContractAddress) -> bool {
+            // Only owner can change the allow-list
+            self.ownable.assert_only_owner();
+            assert(!self.allowed_pairs.read((from, to)), 'Pair already allowed');
+            
+            self.allowed_pairs.write((from, to), true);
+            self._record_config_change(array!['allow_pair', from.into(), to.into()]);
+            self.emit(PairAllowed { from, to });
+            true
+        }
+        
+        fn disallow_pair(ref self: ContractState, from: ContractAddress, to: ContractAddress) -> bool {
+            // Only owner can change the allow-list
+            self.ownable.assert_only_owner();
+            assert(self.allowed_pairs.read((from, to)), 'Pair not allowed');
+            
+            self.allowed_pairs.write((from, to), false);
+            self._record_config_change(array!['disallow_pair', from.into(), to.into()]);
+            self.emit(PairDisallowed { from, to });
+            true
+        }
+        
+        fn allow_counterparty(ref self: ContractState, counterparty: ContractAddress) -> bool {
+            // Only owner can change the allow-list
+            self.ownable.assert_only_owner();
+            assert(!self.allowed_counterparties.read(counterparty), 'Counterparty already allowed');
+            
+            self.allowed_counterparties.write(counterparty, true);
+            self._record_config_change(array!['allow_counterparty', counterparty.into()]);
+            self.emit(CounterpartyAllowed { counterparty });
+            true
+        }
+        
+        fn disallow_counterparty(ref self: ContractState, counterparty: ContractAddress) -> bool {
+            // Only owner can change the allow-list
+            self.ownable.assert_only_owner();
+            assert(self.allowed_counterparties.read(counterparty), 'Counterparty not allowed');
+            
+            self.allowed_counterparties.write(counterparty, false);
+            self._record_config_change(array!['disallow_counterparty', counterparty.into()]);
+            self.emit(CounterpartyDisallowed { counterparty });
+            true
+        }
+        
+        fn is_pair_allowed(self: @ContractState, from: ContractAddress, to: ContractAddress) -> bool {
+            self.allowed_pairs.read((from, to))
+        }
+        
+        fn is_counterparty_allowed(self: @ContractState, counterparty: ContractAddress) -> bool {
+            self.allowed_counterparties.read(counterparty)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+    }
+}
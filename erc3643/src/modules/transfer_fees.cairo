@@ -0,0 +1,256 @@
+//! Transfer Fees Module
+//!
+//! Fee schedule of a token's transfers: `amount * fee_bps / 10000 + flat_fee`, capped at
+//! the transferred amount. The token reads it once set with `set_transfer_fees` and splits
+//! each transfer itself, the recipient receiving the amount minus the fee and the treasury
+//! the fee, so compliance modules see two plain balance changes. Transfers involving token
+//! agents, exempt wallets or the treasury are free. Mints, burns and forced transfers are
+//! free. The module can stay registered with the compliance, its hooks do nothing.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+use core::num::traits::{CheckedAdd, CheckedMul};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{is_token_agent, chain_config_hash};
+
+// Basis points denominator
+pub const BPS_DENOMINATOR: u16 = 10000;
+
+// Transfer Fees Module Interface
+#[starknet::interface]
+pub trait ITransferFeesModule<TContractState> {
+    fn set_fee(ref self: TContractState, fee_bps: u16, flat_fee: u256) -> bool;
+    fn set_treasury(ref self: TContractState, treasury: ContractAddress) -> bool;
+    fn set_fee_exempt(ref self: TContractState, wallet: ContractAddress, exempt: bool) -> bool;
+    fn get_fee(self: @TContractState) -> (u16, u256);
+    fn get_treasury(self: @TContractState) -> ContractAddress;
+    fn is_fee_exempt(self: @TContractState, wallet: ContractAddress) -> bool;
+    fn is_transfer_exempt(self: @TContractState, from: ContractAddress, to: ContractAddress) -> bool;
+    fn compute_fee(self: @TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod TransferFeesModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        FeeSet: FeeSet,
+        TreasurySet: TreasurySet,
+        FeeExemptionSet: FeeExemptionSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct FeeSet {
+        fee_bps: u16,
+        flat_fee: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TreasurySet {
+        treasury: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct FeeExemptionSet {
+        #[key]
+        wallet: ContractAddress,
+        exempt: bool,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Fee configuration
+        fee_bps_map: Map<felt252, u16>,  // Using 'bps' as key
+        flat_fee_map: Map<felt252, u256>,  // Using 'flat' as key
+        treasury_map: Map<felt252, ContractAddress>,  // Using 'treasury' as key
+        fee_exempt: Map<ContractAddress, bool>,
+        
+        // Chained hash of the fee changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        treasury: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.treasury_map.write('treasury', treasury);
+        self._record_config_change(array!['treasury', treasury.into()]);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'TransferFeesModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Fees never block a transfer, they are capped at the amount
+            true
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // The token takes the fee while transferring
+            self._assert_only_compliance();
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Mints are free
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Burns are free
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl TransferFeesModuleImpl of super::ITransferFeesModule<ContractState> {
+        fn set_fee(ref self: ContractState, fee_bps: u16, flat_fee: u256) -> bool {
+            // Only owner can change fees
+            self.ownable.assert_only_owner();
+            assert(fee_bps <= BPS_DENOMINATOR, 'Fee exceeds 100%');
+            
+            self.fee_bps_map.write('bps', fee_bps);
+            self.flat_fee_map.write('flat', flat_fee);
+            self._record_config_change(array!['fee', fee_bps.into(), flat_fee.low.into(), flat_fee.high.into()]);
+            self.emit(FeeSet { fee_bps, flat_fee });
+            true
+        }
+        
+        fn set_treasury(ref self: ContractState, treasury: ContractAddress) -> bool {
+            // Only owner can change the treasury
+            self.ownable.assert_only_owner();
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            assert(treasury != zero_address, 'Invalid treasury');
+            
+            self.treasury_map.write('treasury', treasury);
+            self._record_config_change(array!['treasury', treasury.into()]);
+            self.emit(TreasurySet { treasury });
+            true
+        }
+        
+        fn set_fee_exempt(ref self: ContractState, wallet: ContractAddress, exempt: bool) -> bool {
+            // Only owner can change exemptions
+            self.ownable.assert_only_owner();
+            
+            self.fee_exempt.write(wallet, exempt);
+            self._record_config_change(array!['fee_exempt', wallet.into(), exempt.into()]);
+            self.emit(FeeExemptionSet { wallet, exempt });
+            true
+        }
+        
+        fn get_fee(self: @ContractState) -> (u16, u256) {
+            (self.fee_bps_map.read('bps'), self.flat_fee_map.read('flat'))
+        }
+        
+        fn get_treasury(self: @ContractState) -> ContractAddress {
+            self.treasury_map.read('treasury')
+        }
+        
+        fn is_fee_exempt(self: @ContractState, wallet: ContractAddress) -> bool {
+            self.fee_exempt.read(wallet)
+        }
+        
+        fn is_transfer_exempt(self: @ContractState, from: ContractAddress, to: ContractAddress) -> bool {
+            self._is_exempt(from) || self._is_exempt(to)
+        }
+        
+        fn compute_fee(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u256 {
+            self._compute_fee(from, to, amount)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _is_exempt(self: @ContractState, wallet: ContractAddress) -> bool {
+            if wallet == self.treasury_map.read('treasury') || self.fee_exempt.read(wallet) {
+                return true;
+            }
+            is_token_agent(self.compliance_map.read('compliance'), wallet)
+        }
+        
+        fn _compute_fee(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u256 {
+            if amount == 0 || self._is_exempt(from) || self._is_exempt(to) {
+                return 0;
+            }
+            
+            // Same computation as the token, overflowing amounts are rejected there too
+            let fee_bps: u256 = self.fee_bps_map.read('bps').into();
+            let denominator: u256 = BPS_DENOMINATOR.into();
+            let proportional_fee = amount.checked_mul(fee_bps).expect('Arithmetic overflow') / denominator;
+            let fee = proportional_fee.checked_add(self.flat_fee_map.read('flat')).expect('Arithmetic overflow');
+            if fee > amount {
+                amount
+            } else {
+                fee
+            }
+        }
+    }
+}
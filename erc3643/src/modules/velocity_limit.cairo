@@ -0,0 +1,193 @@
+//! Velocity Limit Module
+//!
+//! Compliance module capping the number of transfers a wallet may send within a time
+//! window, to mitigate wash trading and layering on secondary venues. Each wallet window
+//! starts with its first transfer after the previous window has elapsed. A maximum of 0
+//! disables the limit. Mints and burns are not counted.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+
+// Velocity Limit Module Interface
+#[starknet::interface]
+pub trait IVelocityLimitModule<TContractState> {
+    fn set_velocity_limit(ref self: TContractState, window: u64, max_transfers: u32) -> bool;
+    fn get_velocity_limit(self: @TContractState) -> (u64, u32);
+    fn get_transfer_count(self: @TContractState, wallet: ContractAddress) -> u32;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod VelocityLimitModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        VelocityLimitSet: VelocityLimitSet,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VelocityLimitSet {
+        window: u64,
+        max_transfers: u32,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Limit configuration
+        window_map: Map<felt252, u64>,  // Using 'window' as key
+        max_transfers_map: Map<felt252, u32>,  // Using 'max_transfers' as key
+        
+        // Per-wallet counters
+        window_starts: Map<ContractAddress, u64>,
+        transfer_counts: Map<ContractAddress, u32>,
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress,
+        window: u64,
+        max_transfers: u32
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+        self.window_map.write('window', window);
+        self.max_transfers_map.write('max_transfers', max_transfers);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'VelocityLimitModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            core::poseidon::poseidon_hash_span(
+                array![self.window_map.read('window').into(), self.max_transfers_map.read('max_transfers').into()].span()
+            )
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not counted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            let max_transfers = self.max_transfers_map.read('max_transfers');
+            if max_transfers == 0 {
+                return true;
+            }
+            
+            self._transfer_count(from) < max_transfers
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            
+            let count = self._transfer_count(from);
+            
+            // Open a new window when the previous one has elapsed
+            if count == 0 {
+                self.window_starts.write(from, get_block_timestamp());
+            }
+            self.transfer_counts.write(from, count + 1);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Mints are not counted
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            // Burns are not counted
+            self._assert_only_compliance();
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl VelocityLimitModuleImpl of super::IVelocityLimitModule<ContractState> {
+        fn set_velocity_limit(ref self: ContractState, window: u64, max_transfers: u32) -> bool {
+            // Only owner can change the limit
+            self.ownable.assert_only_owner();
+            assert(window > 0, 'Invalid window');
+            
+            self.window_map.write('window', window);
+            self.max_transfers_map.write('max_transfers', max_transfers);
+            self.emit(VelocityLimitSet { window, max_transfers });
+            true
+        }
+        
+        fn get_velocity_limit(self: @ContractState) -> (u64, u32) {
+            (self.window_map.read('window'), self.max_transfers_map.read('max_transfers'))
+        }
+        
+        fn get_transfer_count(self: @ContractState, wallet: ContractAddress) -> u32 {
+            self._transfer_count(wallet)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _transfer_count(self: @ContractState, wallet: ContractAddress) -> u32 {
+            // Transfers of an elapsed window no longer count
+            let window_start = self.window_starts.read(wallet);
+            if get_block_timestamp() >= window_start + self.window_map.read('window') {
+                return 0;
+            }
+            self.transfer_counts.read(wallet)
+        }
+    }
+}
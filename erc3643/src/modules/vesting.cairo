@@ -0,0 +1,348 @@
+//! Vesting Module
+//!
+//! Compliance module enforcing vesting schedules (cliff + linear release) on wallets.
+//! Token agents create, amend and revoke schedules. Unvested tokens cannot be
+//! transferred. Revoking stops vesting: the unvested part stays locked until an agent
+//! reclaims it with a forced transfer.
+
+// Import OpenZeppelin's components
+use openzeppelin::access::ownable::OwnableComponent;
+use starknet::{
+    ContractAddress, 
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+use core::traits::Into;
+
+use crate::interfaces::icompliance_module::IComplianceModule;
+use crate::modules::common::{wallet_balance, is_token_agent, chain_config_hash};
+
+// Vesting Module Interface
+#[starknet::interface]
+pub trait IVestingModule<TContractState> {
+    fn create_vesting(
+        ref self: TContractState,
+        wallet: ContractAddress,
+        total_amount: u256,
+        start: u64,
+        cliff: u64,
+        duration: u64
+    ) -> bool;
+    fn amend_vesting(
+        ref self: TContractState,
+        wallet: ContractAddress,
+        total_amount: u256,
+        start: u64,
+        cliff: u64,
+        duration: u64
+    ) -> bool;
+    fn revoke_vesting(ref self: TContractState, wallet: ContractAddress) -> bool;
+    fn get_vesting(self: @TContractState, wallet: ContractAddress) -> (u256, u64, u64, u64, u64);
+    fn get_vested_amount(self: @TContractState, wallet: ContractAddress) -> u256;
+    fn get_locked_amount(self: @TContractState, wallet: ContractAddress) -> u256;
+    fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
+    fn owner(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod VestingModule {
+    use super::*;
+    
+    // Component declarations
+    component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
+    
+    // Implement component interfaces
+    impl OwnableImpl = OwnableComponent::OwnableImpl<ContractState>;
+    impl OwnableInternalImpl = OwnableComponent::InternalImpl<ContractState>;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        OwnableEvent: OwnableComponent::Event,
+        VestingCreated: VestingCreated,
+        VestingAmended: VestingAmended,
+        VestingRevoked: VestingRevoked,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VestingCreated {
+        #[key]
+        wallet: ContractAddress,
+        total_amount: u256,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VestingAmended {
+        #[key]
+        wallet: ContractAddress,
+        total_amount: u256,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VestingRevoked {
+        #[key]
+        wallet: ContractAddress,
+        unvested_amount: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        ownable: OwnableComponent::Storage,
+        
+        // Compliance contract this module is deployed for
+        compliance_map: Map<felt252, ContractAddress>,  // Using 'compliance' as key
+        
+        // Vesting schedules per wallet
+        vesting_totals: Map<ContractAddress, u256>,
+        vesting_starts: Map<ContractAddress, u64>,
+        vesting_cliffs: Map<ContractAddress, u64>,  // Seconds after start
+        vesting_durations: Map<ContractAddress, u64>,  // 0 if no schedule
+        vesting_revoked_at: Map<ContractAddress, u64>,  // 0 if not revoked
+        vesting_reclaimed: Map<ContractAddress, u256>,  // Unvested tokens taken by agents
+        
+        // Chained hash of the schedule changes, see `chain_config_hash`
+        config_hash_map: Map<felt252, felt252>,  // Using 'config_hash' as key
+    }
+    
+    #[constructor]
+    fn constructor(
+        ref self: ContractState,
+        initial_owner: ContractAddress,
+        compliance: ContractAddress
+    ) {
+        // Initialize Ownable component
+        self.ownable.initializer(initial_owner);
+        
+        self.compliance_map.write('compliance', compliance);
+    }
+    
+    #[abi(embed_v0)]
+    impl ComplianceModuleImpl of IComplianceModule<ContractState> {
+        fn name(self: @ContractState) -> felt252 {
+            'VestingModule'
+        }
+        
+        fn get_compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn config_hash(self: @ContractState) -> felt252 {
+            self.config_hash_map.read('config_hash')
+        }
+        
+        fn module_check(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Mints are not restricted
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from == zero_address {
+                return true;
+            }
+            
+            let locked = self._locked_amount(from);
+            if locked == 0 {
+                return true;
+            }
+            
+            let balance = wallet_balance(self.compliance_map.read('compliance'), from);
+            balance >= locked + amount
+        }
+        
+        fn module_transfer_action(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            self._record_reclaim(from);
+        }
+        
+        fn module_mint_action(ref self: ContractState, to: ContractAddress, amount: u256) {
+            // Vesting is driven by schedules, not by mints
+            self._assert_only_compliance();
+        }
+        
+        fn module_burn_action(ref self: ContractState, from: ContractAddress, amount: u256) {
+            self._assert_only_compliance();
+            self._record_reclaim(from);
+        }
+    }
+    
+    #[abi(embed_v0)]
+    impl VestingModuleImpl of super::IVestingModule<ContractState> {
+        fn create_vesting(
+            ref self: ContractState,
+            wallet: ContractAddress,
+            total_amount: u256,
+            start: u64,
+            cliff: u64,
+            duration: u64
+        ) -> bool {
+            self._assert_only_agent();
+            assert(self.vesting_durations.read(wallet) == 0, 'Vesting already exists');
+            
+            self._write_schedule(wallet, total_amount, start, cliff, duration);
+            self.vesting_revoked_at.write(wallet, 0);
+            self.vesting_reclaimed.write(wallet, 0);
+            
+            self.emit(VestingCreated { wallet, total_amount, start, cliff, duration });
+            true
+        }
+        
+        fn amend_vesting(
+            ref self: ContractState,
+            wallet: ContractAddress,
+            total_amount: u256,
+            start: u64,
+            cliff: u64,
+            duration: u64
+        ) -> bool {
+            self._assert_only_agent();
+            assert(self.vesting_durations.read(wallet) != 0, 'Vesting not found');
+            assert(self.vesting_revoked_at.read(wallet) == 0, 'Vesting revoked');
+            
+            self._write_schedule(wallet, total_amount, start, cliff, duration);
+            
+            self.emit(VestingAmended { wallet, total_amount, start, cliff, duration });
+            true
+        }
+        
+        fn revoke_vesting(ref self: ContractState, wallet: ContractAddress) -> bool {
+            self._assert_only_agent();
+            assert(self.vesting_durations.read(wallet) != 0, 'Vesting not found');
+            assert(self.vesting_revoked_at.read(wallet) == 0, 'Vesting revoked');
+            
+            self.vesting_revoked_at.write(wallet, get_block_timestamp());
+            self._record_config_change(array!['revoke', wallet.into()]);
+            
+            self.emit(VestingRevoked { wallet, unvested_amount: self._locked_amount(wallet) });
+            true
+        }
+        
+        fn get_vesting(self: @ContractState, wallet: ContractAddress) -> (u256, u64, u64, u64, u64) {
+            (
+                self.vesting_totals.read(wallet),
+                self.vesting_starts.read(wallet),
+                self.vesting_cliffs.read(wallet),
+                self.vesting_durations.read(wallet),
+                self.vesting_revoked_at.read(wallet)
+            )
+        }
+        
+        fn get_vested_amount(self: @ContractState, wallet: ContractAddress) -> u256 {
+            self._vested_amount(wallet)
+        }
+        
+        fn get_locked_amount(self: @ContractState, wallet: ContractAddress) -> u256 {
+            self._locked_amount(wallet)
+        }
+        
+        fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
+            self.ownable.transfer_ownership(new_owner);
+            true
+        }
+        
+        fn owner(self: @ContractState) -> ContractAddress {
+            self.ownable.owner()
+        }
+    }
+    
+    // Internal functions
+    #[generate_trait]
+    impl InternalFunctions of InternalTrait {
+        fn _assert_only_compliance(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(caller == self.compliance_map.read('compliance'), 'Only compliance can call');
+        }
+        
+        fn _record_config_change(ref self: ContractState, change: Array<felt252>) {
+            let previous = self.config_hash_map.read('config_hash');
+            self.config_hash_map.write('config_hash', chain_config_hash(previous, change.span()));
+        }
+        
+        fn _assert_only_agent(self: @ContractState) {
+            let caller = get_caller_address();
+            assert(is_token_agent(self.compliance_map.read('compliance'), caller), 'Only token agent');
+        }
+        
+        fn _write_schedule(
+            ref self: ContractState,
+            wallet: ContractAddress,
+            total_amount: u256,
+            start: u64,
+            cliff: u64,
+            duration: u64
+        ) {
+            assert(duration > 0, 'Invalid duration');
+            assert(cliff <= duration, 'Cliff exceeds duration');
+            
+            self.vesting_totals.write(wallet, total_amount);
+            self.vesting_starts.write(wallet, start);
+            self.vesting_cliffs.write(wallet, cliff);
+            self.vesting_durations.write(wallet, duration);
+            self._record_config_change(
+                array!['schedule', wallet.into(), total_amount.low.into(), total_amount.high.into(), start.into(), cliff.into(), duration.into()]
+            );
+        }
+        
+        fn _vested_amount(self: @ContractState, wallet: ContractAddress) -> u256 {
+            let duration = self.vesting_durations.read(wallet);
+            if duration == 0 {
+                return 0;
+            }
+            
+            // Vesting stops when revoked
+            let mut now = get_block_timestamp();
+            let revoked_at = self.vesting_revoked_at.read(wallet);
+            if revoked_at != 0 && revoked_at < now {
+                now = revoked_at;
+            }
+            
+            let start = self.vesting_starts.read(wallet);
+            let total = self.vesting_totals.read(wallet);
+            if now < start + self.vesting_cliffs.read(wallet) {
+                return 0;
+            }
+            
+            let elapsed = now - start;
+            if elapsed >= duration {
+                return total;
+            }
+            
+            let elapsed_u256: u256 = elapsed.into();
+            let duration_u256: u256 = duration.into();
+            total * elapsed_u256 / duration_u256
+        }
+        
+        fn _locked_amount(self: @ContractState, wallet: ContractAddress) -> u256 {
+            let unvested = self.vesting_totals.read(wallet) - self._vested_amount(wallet);
+            let reclaimed = self.vesting_reclaimed.read(wallet);
+            if unvested > reclaimed {
+                unvested - reclaimed
+            } else {
+                0
+            }
+        }
+        
+        fn _record_reclaim(ref self: ContractState, wallet: ContractAddress) {
+            // Tokens moved below the locked amount were taken by an agent (forced transfer or burn)
+            let locked = self._locked_amount(wallet);
+            if locked == 0 {
+                return;
+            }
+            
+            let balance = wallet_balance(self.compliance_map.read('compliance'), wallet);
+            if balance < locked {
+                let reclaimed = self.vesting_reclaimed.read(wallet);
+                self.vesting_reclaimed.write(wallet, reclaimed + locked - balance);
+            }
+        }
+    }
+}
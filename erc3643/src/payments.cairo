@@ -0,0 +1,54 @@
+//! Payment legs
+//!
+//! Shared helpers for the ERC20 payment legs of settlement contracts (DvP payments,
+//! distributions, buybacks). Every helper checks the addresses it is given and that the
+//! payment token reported success, and `collect` also checks the contract balance grew by
+//! the amount, so tokens that return `true` without moving funds (or take a fee) are caught.
+
+use starknet::{ContractAddress, get_contract_address};
+
+// Payment token interface (ERC20)
+#[starknet::interface]
+pub trait IPaymentToken<TContractState> {
+    fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+    fn transfer(ref self: TContractState, recipient: ContractAddress, amount: u256) -> bool;
+    fn transfer_from(ref self: TContractState, sender: ContractAddress, recipient: ContractAddress, amount: u256) -> bool;
+}
+
+// Pulls `amount` of `payment_token` from `from` into the calling contract.
+// `from` must have allowed the calling contract to spend the amount.
+pub fn collect(payment_token: ContractAddress, from: ContractAddress, amount: u256) {
+    assert_valid_address(payment_token, 'Invalid payment token');
+    assert_valid_address(from, 'Invalid payer');
+    
+    let dispatcher = IPaymentTokenDispatcher { contract_address: payment_token };
+    let this = get_contract_address();
+    let balance_before = dispatcher.balance_of(this);
+    assert(dispatcher.transfer_from(from, this, amount), 'Payment collection failed');
+    assert(dispatcher.balance_of(this) == balance_before + amount, 'Payment amount not received');
+}
+
+// Pays `amount` of `payment_token` held by the calling contract to `recipient`.
+pub fn pay(payment_token: ContractAddress, recipient: ContractAddress, amount: u256) {
+    assert_valid_address(payment_token, 'Invalid payment token');
+    assert_valid_address(recipient, 'Invalid payment recipient');
+    
+    let dispatcher = IPaymentTokenDispatcher { contract_address: payment_token };
+    assert(dispatcher.transfer(recipient, amount), 'Payment failed');
+}
+
+// Pays `amount` of `payment_token` directly from `from` to `recipient`.
+// `from` must have allowed the calling contract to spend the amount.
+pub fn pay_from(payment_token: ContractAddress, from: ContractAddress, recipient: ContractAddress, amount: u256) {
+    assert_valid_address(payment_token, 'Invalid payment token');
+    assert_valid_address(from, 'Invalid payer');
+    assert_valid_address(recipient, 'Invalid payment recipient');
+    
+    let dispatcher = IPaymentTokenDispatcher { contract_address: payment_token };
+    assert(dispatcher.transfer_from(from, recipient, amount), 'Payment failed');
+}
+
+fn assert_valid_address(address: ContractAddress, error: felt252) {
+    let zero_address: ContractAddress = 0.try_into().unwrap();
+    assert(address != zero_address, error);
+}
@@ -0,0 +1,211 @@
+//! Redemptions
+//!
+//! Lets holders redeem security tokens with the issuer during redemption windows. A token
+//! agent opens a window for a token; while it is open, holders request the redemption of an
+//! amount, which freezes it on their wallet. An agent settles each request before the
+//! window closes by burning the tokens and recording the reference of the off-chain (or
+//! separate on-chain) payment. Requests still pending when their window closes expire and
+//! anyone can release them, which unfreezes the tokens.
+//!
+//! The redemption contract must be an agent of the token with the freeze and burn
+//! permissions.
+
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_block_timestamp,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Request statuses, pending requests read as expired once their window has closed
+pub const REDEMPTION_PENDING: u8 = 1;
+pub const REDEMPTION_SETTLED: u8 = 2;
+pub const REDEMPTION_EXPIRED: u8 = 3;
+
+// Redemption Interface
+#[starknet::interface]
+pub trait IRedemption<TContractState> {
+    fn set_redemption_window(ref self: TContractState, token: ContractAddress, opens_at: u64, closes_at: u64) -> bool;
+    fn get_redemption_window(self: @TContractState, token: ContractAddress) -> (u64, u64);
+    fn is_redemption_window_open(self: @TContractState, token: ContractAddress) -> bool;
+    fn request_redemption(ref self: TContractState, token: ContractAddress, amount: u256) -> u64;
+    fn settle_redemption(ref self: TContractState, request_id: u64, payment_reference: felt252) -> bool;
+    fn release_expired_redemption(ref self: TContractState, request_id: u64) -> bool;
+    fn get_redemption(self: @TContractState, request_id: u64) -> (ContractAddress, ContractAddress, u256, u64, u8, felt252);
+    fn get_redemption_count(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod Redemption {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        RedemptionWindowSet: RedemptionWindowSet,
+        RedemptionRequested: RedemptionRequested,
+        RedemptionSettled: RedemptionSettled,
+        RedemptionExpired: RedemptionExpired,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct RedemptionWindowSet {
+        #[key]
+        token: ContractAddress,
+        opens_at: u64,
+        closes_at: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct RedemptionRequested {
+        #[key]
+        request_id: u64,
+        token: ContractAddress,
+        holder: ContractAddress,
+        amount: u256,
+        expires_at: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct RedemptionSettled {
+        #[key]
+        request_id: u64,
+        payment_reference: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct RedemptionExpired {
+        #[key]
+        request_id: u64,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Current redemption window of each token
+        window_opens_at: Map<ContractAddress, u64>,
+        window_closes_at: Map<ContractAddress, u64>,
+        
+        // Requests, by id starting at 1, expiring when the window they were made in closes
+        request_count_map: Map<felt252, u64>,  // Using 'request_count' as key
+        request_tokens: Map<u64, ContractAddress>,
+        request_holders: Map<u64, ContractAddress>,
+        request_amounts: Map<u64, u256>,
+        request_expiries: Map<u64, u64>,
+        request_statuses: Map<u64, u8>,
+        request_payment_references: Map<u64, felt252>,
+    }
+    
+    #[abi(embed_v0)]
+    impl RedemptionImpl of super::IRedemption<ContractState> {
+        fn set_redemption_window(ref self: ContractState, token: ContractAddress, opens_at: u64, closes_at: u64) -> bool {
+            // Only token agents schedule redemption windows
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.is_compliance_agent(get_caller_address()), 'Only agents allowed');
+            assert(closes_at > opens_at, 'Invalid window');
+            
+            self.window_opens_at.write(token, opens_at);
+            self.window_closes_at.write(token, closes_at);
+            
+            self.emit(RedemptionWindowSet { token, opens_at, closes_at });
+            true
+        }
+        
+        fn get_redemption_window(self: @ContractState, token: ContractAddress) -> (u64, u64) {
+            (self.window_opens_at.read(token), self.window_closes_at.read(token))
+        }
+        
+        fn is_redemption_window_open(self: @ContractState, token: ContractAddress) -> bool {
+            let now = get_block_timestamp();
+            now >= self.window_opens_at.read(token) && now < self.window_closes_at.read(token)
+        }
+        
+        fn request_redemption(ref self: ContractState, token: ContractAddress, amount: u256) -> u64 {
+            assert(self.is_redemption_window_open(token), 'Redemption window closed');
+            assert(amount > 0, 'Invalid amount');
+            
+            // The tokens stay with the holder, frozen until settlement or expiry
+            let holder = get_caller_address();
+            IERC3643TokenDispatcher { contract_address: token }.freeze_partial_tokens(holder, amount);
+            
+            let request_id = self.request_count_map.read('request_count') + 1;
+            self.request_count_map.write('request_count', request_id);
+            
+            let expires_at = self.window_closes_at.read(token);
+            self.request_tokens.write(request_id, token);
+            self.request_holders.write(request_id, holder);
+            self.request_amounts.write(request_id, amount);
+            self.request_expiries.write(request_id, expires_at);
+            self.request_statuses.write(request_id, REDEMPTION_PENDING);
+            
+            self.emit(RedemptionRequested { request_id, token, holder, amount, expires_at });
+            request_id
+        }
+        
+        fn settle_redemption(ref self: ContractState, request_id: u64, payment_reference: felt252) -> bool {
+            assert(self.request_statuses.read(request_id) == REDEMPTION_PENDING, 'Request not pending');
+            assert(get_block_timestamp() < self.request_expiries.read(request_id), 'Request expired');
+            
+            // Only token agents settle, once the payment has been made
+            let token = self.request_tokens.read(request_id);
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            assert(token_dispatcher.is_compliance_agent(get_caller_address()), 'Only agents allowed');
+            
+            self.request_statuses.write(request_id, REDEMPTION_SETTLED);
+            self.request_payment_references.write(request_id, payment_reference);
+            
+            // Burn exactly the tokens frozen for this request
+            let holder = self.request_holders.read(request_id);
+            let amount = self.request_amounts.read(request_id);
+            token_dispatcher.unfreeze_partial_tokens(holder, amount);
+            token_dispatcher.forced_burn(holder, amount);
+            
+            self.emit(RedemptionSettled { request_id, payment_reference });
+            true
+        }
+        
+        fn release_expired_redemption(ref self: ContractState, request_id: u64) -> bool {
+            assert(self.request_statuses.read(request_id) == REDEMPTION_PENDING, 'Request not pending');
+            assert(get_block_timestamp() >= self.request_expiries.read(request_id), 'Request not expired');
+            
+            self.request_statuses.write(request_id, REDEMPTION_EXPIRED);
+            
+            // Agents may have unfrozen part of the amount in the meantime
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.request_tokens.read(request_id) };
+            let holder = self.request_holders.read(request_id);
+            let amount = self.request_amounts.read(request_id);
+            let frozen = token_dispatcher.get_frozen_tokens(holder);
+            let to_unfreeze = if frozen < amount { frozen } else { amount };
+            if to_unfreeze > 0 {
+                token_dispatcher.unfreeze_partial_tokens(holder, to_unfreeze);
+            }
+            
+            self.emit(RedemptionExpired { request_id });
+            true
+        }
+        
+        fn get_redemption(self: @ContractState, request_id: u64) -> (ContractAddress, ContractAddress, u256, u64, u8, felt252) {
+            let mut status = self.request_statuses.read(request_id);
+            if status == REDEMPTION_PENDING && get_block_timestamp() >= self.request_expiries.read(request_id) {
+                status = REDEMPTION_EXPIRED;
+            }
+            
+            (
+                self.request_tokens.read(request_id),
+                self.request_holders.read(request_id),
+                self.request_amounts.read(request_id),
+                self.request_expiries.read(request_id),
+                status,
+                self.request_payment_references.read(request_id)
+            )
+        }
+        
+        fn get_redemption_count(self: @ContractState) -> u64 {
+            self.request_count_map.read('request_count')
+        }
+    }
+}
@@ -3,10 +3,13 @@ use openzeppelin::token::erc20::ERC20Component;
 use openzeppelin::access::ownable::OwnableComponent;
 use openzeppelin::security::pausable::PausableComponent;
 use openzeppelin::utils::nonces::NoncesComponent;
-use openzeppelin::utils::cryptography::snip12::SNIP12Metadata;
+use openzeppelin::utils::cryptography::snip12::{SNIP12Metadata, StructHash, OffchainMessageHash, StarknetDomain};
+use openzeppelin::upgrades::UpgradeableComponent;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     get_caller_address,
+    get_contract_address,
     syscalls::call_contract_syscall,
     storage::StorageMapReadAccess,
     storage::StorageMapWriteAccess,
@@ -14,6 +17,149 @@ use starknet::{
 use core::array::ArrayTrait;
 use core::traits::Into;
 use core::byte_array::ByteArray;
+use core::hash::{HashStateTrait, HashStateExTrait};
+use core::poseidon::PoseidonTrait;
+use core::num::traits::{CheckedAdd, CheckedMul};
+
+use crate::compliance::{IComplianceDispatcher, IComplianceDispatcherTrait};
+use crate::interfaces::icompliance_module::{IComplianceModuleDispatcher, IComplianceModuleDispatcherTrait};
+use crate::modules::transfer_fees::{ITransferFeesModuleDispatcher, ITransferFeesModuleDispatcherTrait, BPS_DENOMINATOR};
+
+// Revert reasons of the token. Clients match on these messages, so they are kept stable
+// across releases: existing messages are never reworded, new failure modes get new ones.
+pub mod Errors {
+    pub const ADDRESS_FROZEN: felt252 = 'Address frozen';
+    pub const AGENT_LACKS_PERMISSION: felt252 = 'Agent lacks permission';
+    pub const ALLOWANCE_BELOW_ZERO: felt252 = 'Allowance below zero';
+    pub const AMOUNT_EXCEEDS_BALANCE: felt252 = 'Amount exceeds balance';
+    pub const AMOUNT_EXCEEDS_FROZEN_TOKENS: felt252 = 'Amount exceeds frozen tokens';
+    pub const AMOUNT_EXCEEDS_HELD_TOKENS: felt252 = 'Amount exceeds held tokens';
+    pub const ARRAY_LENGTH_MISMATCH: felt252 = 'Array length mismatch';
+    pub const AUTHORIZATION_ALREADY_USED: felt252 = 'Authorization already used';
+    pub const AUTHORIZATION_EXPIRED: felt252 = 'Authorization expired';
+    pub const COMPLIANCE_CHECK_FAILED: felt252 = 'Compliance check failed';
+    pub const DOCUMENT_NOT_FOUND: felt252 = 'Document not found';
+    pub const FEES_MODULE_BOUND_ELSEWHERE: felt252 = 'Fees module bound elsewhere';
+    pub const INSUFFICIENT_BALANCE: felt252 = 'Insufficient balance';
+    pub const INSUFFICIENT_PARTITION_BALANCE: felt252 = 'Insufficient partition balance';
+    pub const INSUFFICIENT_UNFROZEN_BALANCE: felt252 = 'Insufficient unfrozen balance';
+    pub const INVALID_AMOUNT: felt252 = 'Invalid amount';
+    pub const INVALID_DOCUMENT_NAME: felt252 = 'Invalid document name';
+    pub const INVALID_DOCUMENT_URI: felt252 = 'Invalid document URI';
+    pub const INVALID_HOLDER_INDEX: felt252 = 'Invalid holder index';
+    pub const INVALID_NAME: felt252 = 'Invalid name';
+    pub const INVALID_PAUSE_SCOPES: felt252 = 'Invalid pause scopes';
+    pub const INVALID_PERMISSIONS: felt252 = 'Invalid permissions';
+    pub const INVALID_SIGNATURE: felt252 = 'Invalid signature';
+    pub const INVALID_SNAPSHOT_ID: felt252 = 'Invalid snapshot id';
+    pub const INVALID_SPLIT_RATIO: felt252 = 'Invalid split ratio';
+    pub const INVALID_SYMBOL: felt252 = 'Invalid symbol';
+    pub const MINT_ALLOWANCE_EXCEEDED: felt252 = 'Mint allowance exceeded';
+    pub const MINT_ALLOWANCE_EXPIRED: felt252 = 'Mint allowance expired';
+    pub const NOT_AN_AGENT: felt252 = 'Not an agent';
+    pub const ONLY_AGENTS_ALLOWED: felt252 = 'Only agents allowed';
+    pub const OVERFLOW: felt252 = 'Arithmetic overflow';
+    pub const PERMIT_EXPIRED: felt252 = 'Permit expired';
+    pub const RECIPIENT_FROZEN: felt252 = 'Recipient frozen';
+    pub const RECIPIENT_NOT_VERIFIED: felt252 = 'Recipient not verified';
+    pub const SAME_ADDRESS: felt252 = 'Same source and destination';
+    pub const SAME_PARTITION: felt252 = 'Same partition';
+    pub const SENDER_FROZEN: felt252 = 'Sender frozen';
+    pub const SENDER_NOT_VERIFIED: felt252 = 'Sender not verified';
+    pub const SNAPSHOT_DOES_NOT_EXIST: felt252 = 'Snapshot does not exist';
+    pub const STORAGE_ALREADY_MIGRATED: felt252 = 'Storage already migrated';
+    pub const SUPPLY_CAP_EXCEEDED: felt252 = 'Supply cap exceeded';
+    pub const TOKENS_ALREADY_MINTED: felt252 = 'Tokens already minted';
+    pub const TOKEN_MOVEMENTS_PAUSED: felt252 = 'Token movements paused';
+    pub const TOKEN_NOT_BOUND_TO_COMPLIANCE: felt252 = 'Token not bound to compliance';
+    pub const TRANSFERS_PAUSED: felt252 = 'Transfers paused';
+    pub const TRANSFER_DATA_TOO_LONG: felt252 = 'Transfer data too long';
+    pub const TRANSFER_NOT_COMPLIANT: felt252 = 'Transfer not compliant';
+    pub const TRANSFER_REFERENCE_REQUIRED: felt252 = 'Transfer reference required';
+    pub const ZERO_AMOUNT_TRANSFER: felt252 = 'Zero amount transfer disallowed';
+}
+
+// Result codes of `can_transfer`, in the order the checks are evaluated
+pub const TRANSFER_OK: u8 = 0;
+pub const TRANSFER_PAUSED: u8 = 1;
+pub const TRANSFER_SENDER_FROZEN: u8 = 2;
+pub const TRANSFER_RECIPIENT_FROZEN: u8 = 3;
+pub const TRANSFER_INSUFFICIENT_BALANCE: u8 = 4;
+pub const TRANSFER_SENDER_NOT_VERIFIED: u8 = 5;
+pub const TRANSFER_RECIPIENT_NOT_VERIFIED: u8 = 6;
+pub const TRANSFER_TOKEN_NOT_BOUND: u8 = 7;
+pub const TRANSFER_NOT_COMPLIANT: u8 = 8;
+pub const TRANSFER_ZERO_AMOUNT: u8 = 9;
+
+// Longest settlement reference accepted by `transfer_with_data`, in bytes
+pub const MAX_TRANSFER_DATA_LENGTH: u32 = 256;
+
+// Agent permissions, combined as a bitmask. Agents added with `add_agent` hold them all.
+pub const PERMISSION_MINT: u8 = 1;
+pub const PERMISSION_BURN: u8 = 2;
+pub const PERMISSION_FORCED_TRANSFER: u8 = 4;
+pub const PERMISSION_FREEZE: u8 = 8;
+pub const PERMISSION_RECOVERY: u8 = 16;
+pub const PERMISSION_SNAPSHOT: u8 = 32;
+pub const PERMISSION_PARTITION: u8 = 64;
+pub const PERMISSION_ALL: u8 = 127;
+
+// Pause scopes, combined as a bitmask. `pause` stops transfers and forced transfers on top.
+pub const PAUSE_TRANSFERS: u8 = 1;  // Investor transfers only
+pub const PAUSE_ALL_MOVEMENTS: u8 = 2;  // Transfers, mints, burns, forced transfers and recoveries
+
+// Partition holding the part of a balance not assigned to a named partition (ERC-1410)
+pub const DEFAULT_PARTITION: felt252 = 0;
+
+// SNIP-12 message signed by a holder to let anyone relay a transfer on their behalf. The
+// domain is shared by every token of this class, so the message names the token it is for.
+pub const TRANSFER_AUTHORIZATION_TYPE_HASH: felt252 = selector!(
+    "\"TransferAuthorization\"(\"token\":\"ContractAddress\",\"from\":\"ContractAddress\",\"to\":\"ContractAddress\",\"amount\":\"u256\",\"nonce\":\"felt\",\"expiry\":\"u64\")\"u256\"(\"low\":\"u128\",\"high\":\"u128\")"
+);
+
+#[derive(Copy, Drop, Hash)]
+pub struct TransferAuthorization {
+    pub token: ContractAddress,
+    pub from: ContractAddress,
+    pub to: ContractAddress,
+    pub amount: u256,
+    pub nonce: felt252,
+    pub expiry: u64,
+}
+
+impl TransferAuthorizationStructHash of StructHash<TransferAuthorization> {
+    fn hash_struct(self: @TransferAuthorization) -> felt252 {
+        PoseidonTrait::new().update_with(TRANSFER_AUTHORIZATION_TYPE_HASH).update_with(*self).finalize()
+    }
+}
+
+// SNIP-12 message signed by a holder to approve a spender without sending a transaction,
+// naming the token like OpenZeppelin's ERC20 permit does
+pub const PERMIT_TYPE_HASH: felt252 = selector!(
+    "\"Permit\"(\"token\":\"ContractAddress\",\"owner\":\"ContractAddress\",\"spender\":\"ContractAddress\",\"amount\":\"u256\",\"nonce\":\"felt\",\"deadline\":\"u64\")\"u256\"(\"low\":\"u128\",\"high\":\"u128\")"
+);
+
+#[derive(Copy, Drop, Hash)]
+pub struct Permit {
+    pub token: ContractAddress,
+    pub owner: ContractAddress,
+    pub spender: ContractAddress,
+    pub amount: u256,
+    pub nonce: felt252,
+    pub deadline: u64,
+}
+
+impl PermitStructHash of StructHash<Permit> {
+    fn hash_struct(self: @Permit) -> felt252 {
+        PoseidonTrait::new().update_with(PERMIT_TYPE_HASH).update_with(*self).finalize()
+    }
+}
+
+// Holder account interface (SRC6)
+#[starknet::interface]
+trait IAccountContract<TContractState> {
+    fn is_valid_signature(self: @TContractState, hash: felt252, signature: Array<felt252>) -> felt252;
+}
 
 // Token Interface
 #[starknet::interface]
@@ -21,13 +167,54 @@ pub trait IERC3643Token<TContractState> {
     // ERC20 standard functions (inherited from OpenZeppelin)
     fn name(self: @TContractState) -> felt252;
     fn symbol(self: @TContractState) -> felt252;
+    fn onchain_id(self: @TContractState) -> felt252;
+    fn version(self: @TContractState) -> felt252;
+    fn set_name(ref self: TContractState, name: felt252) -> bool;
+    fn set_symbol(ref self: TContractState, symbol: felt252) -> bool;
+    fn set_onchain_id(ref self: TContractState, onchain_id: felt252) -> bool;
+    fn set_max_supply(ref self: TContractState, max_supply: u256) -> bool;
+    
+    // Documents attached to the token (ERC-1643)
+    fn set_document(ref self: TContractState, name: felt252, uri: ByteArray, document_hash: felt252) -> bool;
+    fn remove_document(ref self: TContractState, name: felt252) -> bool;
+    fn get_document(self: @TContractState, name: felt252) -> (ByteArray, felt252, u64);
+    fn get_all_documents(self: @TContractState) -> Array<felt252>;
+    fn max_supply(self: @TContractState) -> u256;
     fn decimals(self: @TContractState) -> u8;
     fn total_supply(self: @TContractState) -> u256;
     fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+    
+    // Balance snapshots, e.g. for record dates of distributions
+    fn snapshot(ref self: TContractState) -> u64;
+    fn get_current_snapshot_id(self: @TContractState) -> u64;
+    fn balance_of_at(self: @TContractState, account: ContractAddress, snapshot_id: u64) -> u256;
+    fn total_supply_at(self: @TContractState, snapshot_id: u64) -> u256;
+    
+    // Current holders (non-zero balances), in no particular order
+    fn get_holder_count(self: @TContractState) -> u32;
+    fn get_holder(self: @TContractState, index: u32) -> ContractAddress;
+    fn get_holders(self: @TContractState, offset: u32, limit: u32) -> Array<ContractAddress>;
+    fn is_holder(self: @TContractState, account: ContractAddress) -> bool;
+    
+    // Splits and reverse splits, applied as a multiplier on raw balances
+    fn apply_split(ref self: TContractState, numerator: u256, denominator: u256) -> bool;
+    fn get_split_multiplier(self: @TContractState) -> (u256, u256);
+    fn to_adjusted_amount(self: @TContractState, raw_amount: u256) -> u256;
+    fn to_raw_amount(self: @TContractState, adjusted_amount: u256) -> u256;
+    fn adjusted_balance_of(self: @TContractState, account: ContractAddress) -> u256;
+    fn adjusted_total_supply(self: @TContractState) -> u256;
+    
     fn allowance(self: @TContractState, owner: ContractAddress, spender: ContractAddress) -> u256;
     fn transfer(ref self: TContractState, to: ContractAddress, amount: u256) -> bool;
     fn transfer_from(ref self: TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool;
+    fn transfer_with_data(ref self: TContractState, to: ContractAddress, amount: u256, data: ByteArray) -> bool;
+    fn set_transfer_reference_required(ref self: TContractState, required: bool) -> bool;
+    fn is_transfer_reference_required(self: @TContractState) -> bool;
+    fn set_transfer_fees(ref self: TContractState, fees_module: ContractAddress) -> bool;
+    fn get_transfer_fees(self: @TContractState) -> ContractAddress;
     fn approve(ref self: TContractState, spender: ContractAddress, amount: u256) -> bool;
+    fn increase_allowance(ref self: TContractState, spender: ContractAddress, added_value: u256) -> bool;
+    fn decrease_allowance(ref self: TContractState, spender: ContractAddress, subtracted_value: u256) -> bool;
     
     // ERC20 camelCase functions (OpenZeppelin v2.0.0 supports these directly)
     fn totalSupply(self: @TContractState) -> u256;
@@ -44,12 +231,24 @@ pub trait IERC3643Token<TContractState> {
         signature: Span<felt252>
     );
     fn nonces(self: @TContractState, owner: ContractAddress) -> felt252;
+    fn transfer_with_authorization(
+        ref self: TContractState,
+        from: ContractAddress,
+        to: ContractAddress,
+        amount: u256,
+        nonce: felt252,
+        expiry: u64,
+        signature: Array<felt252>
+    ) -> bool;
+    fn is_authorization_used(self: @TContractState, from: ContractAddress, nonce: felt252) -> bool;
     fn DOMAIN_SEPARATOR(self: @TContractState) -> felt252;
     
     // Pausable interface (inherited from OpenZeppelin)
     fn pause(ref self: TContractState) -> bool;
     fn unpause(ref self: TContractState) -> bool;
     fn is_paused(self: @TContractState) -> bool;
+    fn set_pause_scopes(ref self: TContractState, scopes: u8) -> bool;
+    fn get_pause_scopes(self: @TContractState) -> u8;
     
     // Ownable interface (inherited from OpenZeppelin)
     fn owner(self: @TContractState) -> ContractAddress;
@@ -58,19 +257,82 @@ pub trait IERC3643Token<TContractState> {
     
     // ERC3643 extended functions
     fn forced_transfer(ref self: TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool;
+    fn batch_forced_transfer(
+        ref self: TContractState,
+        from_list: Array<ContractAddress>,
+        to_list: Array<ContractAddress>,
+        amounts: Array<u256>
+    ) -> bool;
     fn mint(ref self: TContractState, to: ContractAddress, amount: u256) -> bool;
     fn burn(ref self: TContractState, amount: u256) -> bool;
+    fn forced_burn(ref self: TContractState, user_address: ContractAddress, amount: u256) -> bool;
     fn recover(ref self: TContractState, lost_address: ContractAddress, amount: u256) -> bool;
     fn freeze_address(ref self: TContractState, address_to_freeze: ContractAddress) -> bool;
     fn unfreeze_address(ref self: TContractState, address_to_unfreeze: ContractAddress) -> bool;
     fn set_address_frozen(ref self: TContractState, target_address: ContractAddress, frozen: bool) -> bool;
+    fn batch_set_address_frozen(ref self: TContractState, target_addresses: Array<ContractAddress>, frozen: Array<bool>) -> bool;
+    fn freeze_partial_tokens(ref self: TContractState, user_address: ContractAddress, amount: u256) -> bool;
+    fn unfreeze_partial_tokens(ref self: TContractState, user_address: ContractAddress, amount: u256) -> bool;
+    fn get_frozen_tokens(self: @TContractState, user_address: ContractAddress) -> u256;
+    fn set_address_frozen_with_reason(ref self: TContractState, target_address: ContractAddress, frozen: bool, reason: felt252) -> bool;
+    fn freeze_partial_tokens_with_reason(ref self: TContractState, user_address: ContractAddress, amount: u256, reason: felt252) -> bool;
+    fn get_freeze_record(self: @TContractState, address: ContractAddress) -> (felt252, ContractAddress, u64);
+    
+    // Holds placed by agents on part of a balance, each agent only releasing its own
+    fn hold_tokens(ref self: TContractState, user_address: ContractAddress, amount: u256) -> bool;
+    fn release_held_tokens(ref self: TContractState, user_address: ContractAddress, amount: u256) -> bool;
+    fn get_held_tokens(self: @TContractState, user_address: ContractAddress, agent: ContractAddress) -> u256;
+    
+    // Partitions (tranches) of balances, e.g. 'restricted' or 'reg_s' (ERC-1410)
+    fn balance_of_by_partition(self: @TContractState, account: ContractAddress, partition: felt252) -> u256;
+    fn partitions_of(self: @TContractState, account: ContractAddress) -> Array<felt252>;
+    fn transfer_by_partition(
+        ref self: TContractState, partition: felt252, to: ContractAddress, amount: u256, data: ByteArray
+    ) -> bool;
+    fn reassign_partition(
+        ref self: TContractState,
+        account: ContractAddress,
+        from_partition: felt252,
+        to_partition: felt252,
+        amount: u256
+    ) -> bool;
+    
     fn set_compliance(ref self: TContractState, compliance_address: ContractAddress) -> bool;
     fn set_identity_registry(ref self: TContractState, identity_registry: ContractAddress) -> bool;
+    fn compliance(self: @TContractState) -> ContractAddress;
+    fn identity_registry(self: @TContractState) -> ContractAddress;
     fn is_verified_address(self: @TContractState, address: ContractAddress) -> bool;
+    fn can_transfer(self: @TContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u8;
+    fn can_transfer_batch(
+        self: @TContractState,
+        from_list: Array<ContractAddress>,
+        to_list: Array<ContractAddress>,
+        amounts: Array<u256>
+    ) -> Array<u8>;
+    fn can_transfer_with_reason(
+        self: @TContractState,
+        from: ContractAddress,
+        to: ContractAddress,
+        amount: u256
+    ) -> (u8, ContractAddress, felt252);
     fn is_compliance_agent(self: @TContractState, address: ContractAddress) -> bool;
     fn is_frozen(self: @TContractState, address: ContractAddress) -> bool;
     fn add_agent(ref self: TContractState, agent: ContractAddress) -> bool;
     fn remove_agent(ref self: TContractState, agent: ContractAddress) -> bool;
+    fn set_agent_permissions(ref self: TContractState, agent: ContractAddress, permissions: u8) -> bool;
+    fn get_agent_permissions(self: @TContractState, agent: ContractAddress) -> u8;
+    fn set_mint_allowance(ref self: TContractState, agent: ContractAddress, amount: u256, expiry: u64) -> bool;
+    fn remove_mint_allowance(ref self: TContractState, agent: ContractAddress) -> bool;
+    fn get_mint_allowance(self: @TContractState, agent: ContractAddress) -> (bool, u256, u64);
+    
+    // Aggregated views for indexers and integrating contracts
+    fn get_token_state(self: @TContractState) -> (felt252, felt252, u8, u256, bool, u8, ContractAddress, ContractAddress, u8);
+    fn get_holder_state(self: @TContractState, user_address: ContractAddress) -> (u256, u256, bool, felt252, bool);
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 #[starknet::contract]
@@ -82,6 +344,7 @@ pub mod ERC3643Token {
     component!(path: OwnableComponent, storage: ownable, event: OwnableEvent);
     component!(path: PausableComponent, storage: pausable, event: PausableEvent);
     component!(path: NoncesComponent, storage: nonces, event: NoncesEvent);
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
 
     // Implement component interfaces
     // We're removing abi(embed_v0) to avoid duplicate entry points in testing
@@ -94,6 +357,7 @@ pub mod ERC3643Token {
     impl PausableInternalImpl = PausableComponent::InternalImpl<ContractState>;
     impl NoncesImpl = NoncesComponent::NoncesImpl<ContractState>;
     impl NoncesInternalImpl = NoncesComponent::InternalImpl<ContractState>;
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Implement ImmutableConfig trait required for OpenZeppelin v2.0.0
     // In v2.0.0, ImmutableConfig is a trait with constants
@@ -119,14 +383,36 @@ pub mod ERC3643Token {
             from: ContractAddress,
             recipient: ContractAddress,
             amount: u256
-        ) {}
+        ) {
+            // Record the balances about to change in the current snapshot, if not done yet
+            let mut contract_state = self.get_contract_mut();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address {
+                contract_state._update_account_snapshot(from);
+                
+                // Forced moves and burns take from named partitions once the default one is spent
+                contract_state._release_partitions(from, amount);
+            }
+            if recipient != zero_address {
+                contract_state._update_account_snapshot(recipient);
+            }
+            if from == zero_address || recipient == zero_address {
+                contract_state._update_total_supply_snapshot();
+            }
+        }
         
         fn after_update(
             ref self: ERC20Component::ComponentState<ContractState>,
             from: ContractAddress,
             recipient: ContractAddress,
             amount: u256
-        ) {}
+        ) {
+            // Every balance change (transfer, mint, burn, forced transfer, recovery)
+            // is reported to compliance so stateful modules stay in sync
+            let mut contract_state = self.get_contract_mut();
+            contract_state._update_holders(from, recipient);
+            contract_state._notify_compliance(from, recipient, amount);
+        }
     }
     
     // Events
@@ -141,13 +427,59 @@ pub mod ERC3643Token {
         PausableEvent: PausableComponent::Event,
         #[flat]
         NoncesEvent: NoncesComponent::Event,
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
+        UpdatedTokenInformation: UpdatedTokenInformation,
+        MaxSupplySet: MaxSupplySet,
+        PauseScopesSet: PauseScopesSet,
         Frozen: Frozen,
         Unfrozen: Unfrozen,
+        TokensFrozen: TokensFrozen,
+        TokensUnfrozen: TokensUnfrozen,
+        FreezeActionRecorded: FreezeActionRecorded,
+        TokensHeld: TokensHeld,
+        HeldTokensReleased: HeldTokensReleased,
+        AuthorizationUsed: AuthorizationUsed,
+        TransferData: TransferData,
+        TransferReferenceRequirementSet: TransferReferenceRequirementSet,
+        TransferFeesSet: TransferFeesSet,
+        TransferFeeCharged: TransferFeeCharged,
+        DocumentUpdated: DocumentUpdated,
+        DocumentRemoved: DocumentRemoved,
+        Snapshot: Snapshot,
+        SplitApplied: SplitApplied,
+        TransferByPartition: TransferByPartition,
+        PartitionReassigned: PartitionReassigned,
         RecoverySuccess: RecoverySuccess,
+        ForcedBurn: ForcedBurn,
+        ForcedTransfer: ForcedTransfer,
         ComplianceAdded: ComplianceAdded,
         IdentityRegistryAdded: IdentityRegistryAdded,
         AgentAdded: AgentAdded,
         AgentRemoved: AgentRemoved,
+        AgentPermissionsSet: AgentPermissionsSet,
+        MintAllowanceSet: MintAllowanceSet,
+        MintAllowanceRemoved: MintAllowanceRemoved,
+        StorageMigrated: StorageMigrated,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct UpdatedTokenInformation {
+        name: felt252,
+        symbol: felt252,
+        decimals: u8,
+        version: felt252,
+        onchain_id: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PauseScopesSet {
+        scopes: u8,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MaxSupplySet {
+        max_supply: u256,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -160,6 +492,53 @@ pub mod ERC3643Token {
         address: ContractAddress,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct TokensFrozen {
+        address: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TokensUnfrozen {
+        address: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct FreezeActionRecorded {
+        #[key]
+        address: ContractAddress,
+        #[key]
+        agent: ContractAddress,
+        reason: felt252,
+        timestamp: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TokensHeld {
+        #[key]
+        address: ContractAddress,
+        #[key]
+        agent: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct HeldTokensReleased {
+        #[key]
+        address: ContractAddress,
+        #[key]
+        agent: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct AuthorizationUsed {
+        #[key]
+        from: ContractAddress,
+        nonce: felt252,
+    }
+    
     #[derive(Drop, starknet::Event)]
     struct RecoverySuccess {
         from: ContractAddress,
@@ -167,6 +546,104 @@ pub mod ERC3643Token {
         amount: u256,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct TransferData {
+        #[key]
+        from: ContractAddress,
+        #[key]
+        to: ContractAddress,
+        amount: u256,
+        #[key]
+        data_hash: felt252,
+        data: ByteArray,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferReferenceRequirementSet {
+        required: bool,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferFeesSet {
+        fees_module: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferFeeCharged {
+        #[key]
+        from: ContractAddress,
+        #[key]
+        to: ContractAddress,
+        treasury: ContractAddress,
+        fee: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DocumentUpdated {
+        #[key]
+        name: felt252,
+        uri: ByteArray,
+        document_hash: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct TransferByPartition {
+        #[key]
+        partition: felt252,
+        from: ContractAddress,
+        to: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct PartitionReassigned {
+        #[key]
+        account: ContractAddress,
+        from_partition: felt252,
+        to_partition: felt252,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct SplitApplied {
+        numerator: u256,
+        denominator: u256,
+        multiplier_numerator: u256,
+        multiplier_denominator: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct Snapshot {
+        #[key]
+        snapshot_id: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct DocumentRemoved {
+        #[key]
+        name: felt252,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ForcedTransfer {
+        #[key]
+        agent: ContractAddress,
+        #[key]
+        from: ContractAddress,
+        #[key]
+        to: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct ForcedBurn {
+        #[key]
+        agent: ContractAddress,
+        #[key]
+        from: ContractAddress,
+        amount: u256,
+    }
+    
     #[derive(Drop, starknet::Event)]
     struct ComplianceAdded {
         compliance: ContractAddress,
@@ -187,6 +664,30 @@ pub mod ERC3643Token {
         agent: ContractAddress,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct AgentPermissionsSet {
+        agent: ContractAddress,
+        permissions: u8,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MintAllowanceSet {
+        agent: ContractAddress,
+        amount: u256,
+        expiry: u64,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct MintAllowanceRemoved {
+        agent: ContractAddress,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
         // Component storage
@@ -202,16 +703,98 @@ pub mod ERC3643Token {
         #[substorage(v0)]
         nonces: NoncesComponent::Storage,
         
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
+        // Token information, using 'name', 'symbol' and 'onchain_id' as keys
+        token_information_map: starknet::storage::Map::<felt252, felt252>,
+        
+        // Hard cap on the total supply, 0 when uncapped
+        max_supply_map: starknet::storage::Map::<felt252, u256>,  // Using 'max_supply' as key
+        
+        // Documents by name (prospectus, notices...) with their URI, hash and last update
+        document_count_map: starknet::storage::Map::<felt252, u32>,  // Using 'document_count' as key
+        document_names: starknet::storage::Map::<u32, felt252>,
+        document_indices: starknet::storage::Map::<felt252, u32>,  // 1-based, 0 when absent
+        document_uris: starknet::storage::Map::<felt252, ByteArray>,
+        document_hashes: starknet::storage::Map::<felt252, felt252>,
+        document_timestamps: starknet::storage::Map::<felt252, u64>,
+        
+        // Balance snapshots: each account and the total supply keep the value they had
+        // when first changed after a snapshot, as (snapshot id, value) entries in id order
+        snapshot_id_map: starknet::storage::Map::<felt252, u64>,  // Using 'snapshot_id' as key
+        account_snapshot_counts: starknet::storage::Map::<ContractAddress, u32>,
+        account_snapshot_ids: starknet::storage::Map::<(ContractAddress, u32), u64>,
+        account_snapshot_values: starknet::storage::Map::<(ContractAddress, u32), u256>,
+        supply_snapshot_count_map: starknet::storage::Map::<felt252, u32>,  // Using 'supply_snapshot_count' as key
+        supply_snapshot_ids: starknet::storage::Map::<u32, u64>,
+        supply_snapshot_values: starknet::storage::Map::<u32, u256>,
+        
+        // Holder set: holders by index, and each holder's index plus one (0 when not a holder)
+        holder_count_map: starknet::storage::Map::<felt252, u32>,  // Using 'holder_count' as key
+        holders: starknet::storage::Map::<u32, ContractAddress>,
+        holder_indices: starknet::storage::Map::<ContractAddress, u32>,
+        
+        // Cumulative split multiplier, 1:1 until the first split
+        split_multiplier_map: starknet::storage::Map::<felt252, u256>,  // Using 'numerator' and 'denominator' as keys
+        
+        // Whether plain transfers are refused in favour of `transfer_with_data`
+        reference_required_map: starknet::storage::Map::<felt252, bool>,  // Using 'reference_required' as key
+        
+        // Fee schedule applied to transfers, none when zero
+        transfer_fees_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'transfer_fees' as key
+        
+        // Paused scopes, see PAUSE_*
+        pause_scopes_map: starknet::storage::Map::<felt252, u8>,  // Using 'pause_scopes' as key
+        
         // ERC3643 additional storage using maps to avoid direct storage access issues
         compliance_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'compliance' as key
         identity_registry_map: starknet::storage::Map::<felt252, ContractAddress>,  // Using 'registry' as key
         frozen_addresses: starknet::storage::Map::<ContractAddress, bool>,
+        frozen_tokens: starknet::storage::Map::<ContractAddress, u256>,  // Part of the balance that cannot move
+        
+        // Last freeze action on each address: reason code (0 when none given), agent and time
+        freeze_reasons: starknet::storage::Map::<ContractAddress, felt252>,
+        freeze_agents: starknet::storage::Map::<ContractAddress, ContractAddress>,
+        freeze_timestamps: starknet::storage::Map::<ContractAddress, u64>,
+        
+        // Holds by (holder, agent), kept apart from the freezes so neither can lift the other
+        held_tokens: starknet::storage::Map::<(ContractAddress, ContractAddress), u256>,
+        total_held_tokens: starknet::storage::Map::<ContractAddress, u256>,
+        
+        // Named partitions of each balance, the rest is the default partition
+        partition_balances: starknet::storage::Map::<(ContractAddress, felt252), u256>,
+        partitioned_balances: starknet::storage::Map::<ContractAddress, u256>,  // Sum of the named partitions
+        partition_counts: starknet::storage::Map::<ContractAddress, u32>,
+        holder_partitions: starknet::storage::Map::<(ContractAddress, u32), felt252>,
+        partition_indices: starknet::storage::Map::<(ContractAddress, felt252), u32>,  // 1-based, 0 when absent
         agents: starknet::storage::Map::<ContractAddress, bool>,
+        
+        // Permissions of agents restricted by `set_agent_permissions`, unrestricted agents hold all
+        agent_restricted: starknet::storage::Map::<ContractAddress, bool>,
+        agent_permissions: starknet::storage::Map::<ContractAddress, u8>,
+        
+        // Remaining mint allowance of capped agents (expiry 0 never expires), other agents mint freely
+        mint_allowance_capped: starknet::storage::Map::<ContractAddress, bool>,
+        mint_allowances: starknet::storage::Map::<ContractAddress, u256>,
+        mint_allowance_expiries: starknet::storage::Map::<ContractAddress, u64>,
+        
+        // Nonces of the transfer authorizations already relayed, per holder
+        used_authorizations: starknet::storage::Map::<(ContractAddress, felt252), bool>,
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: starknet::storage::Map::<felt252, u8>,  // Using 'version' as key
     }
     
     // Constants
     const AGENT_ROLE: felt252 = selector!("AGENT_ROLE");
     
+    // Version 2 stores the name and symbol, version 1 reported fixed ones
+    const STORAGE_VERSION: u8 = 2;
+    
+    // Version of this token implementation
+    const TOKEN_VERSION: felt252 = '1.0.0';
+    
     #[constructor]
     fn constructor(
         ref self: ContractState,
@@ -227,6 +810,10 @@ pub mod ERC3643Token {
         let symbol_bytes: ByteArray = "TKN";
         self.erc20.initializer(name_bytes, symbol_bytes);
         
+        // The ERC3643 interface reports the name and symbol given at deployment
+        self.token_information_map.write('name', name);
+        self.token_information_map.write('symbol', symbol);
+        
         // Initialize owner
         self.ownable.initializer(initial_owner);
         
@@ -239,101 +826,370 @@ pub mod ERC3643Token {
         
         // Add initial owner as an agent
         self.agents.write(initial_owner, true);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
     impl ERC3643TokenImpl of super::IERC3643Token<ContractState> {
         // ERC20 functions
         fn name(self: @ContractState) -> felt252 {
-            'Token'
+            self.token_information_map.read('name')
         }
 
         fn symbol(self: @ContractState) -> felt252 {
-            'TKN'
+            self.token_information_map.read('symbol')
         }
-
-        fn decimals(self: @ContractState) -> u8 {
-            // Use the value from our ImmutableConfig implementation
-            // In OpenZeppelin v2.0.0, decimals comes from ImmutableConfig
-            18
+        
+        fn onchain_id(self: @ContractState) -> felt252 {
+            self.token_information_map.read('onchain_id')
         }
-
-        fn total_supply(self: @ContractState) -> u256 {
-            self.erc20.total_supply()
+        
+        fn version(self: @ContractState) -> felt252 {
+            TOKEN_VERSION
         }
-
-        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
-            self.erc20.balance_of(account)
+        
+        fn set_name(ref self: ContractState, name: felt252) -> bool {
+            // Only owner can update the token information
+            self.ownable.assert_only_owner();
+            assert(name != 0, Errors::INVALID_NAME);
+            
+            self.token_information_map.write('name', name);
+            self._emit_token_information();
+            true
         }
-
-        fn allowance(self: @ContractState, owner: ContractAddress, spender: ContractAddress) -> u256 {
-            self.erc20.allowance(owner, spender)
+        
+        fn set_symbol(ref self: ContractState, symbol: felt252) -> bool {
+            // Only owner can update the token information
+            self.ownable.assert_only_owner();
+            assert(symbol != 0, Errors::INVALID_SYMBOL);
+            
+            self.token_information_map.write('symbol', symbol);
+            self._emit_token_information();
+            true
         }
-
-        fn transfer(ref self: ContractState, to: ContractAddress, amount: u256) -> bool {
-            // Check if contract is paused using OpenZeppelin's pausable component
-            self.pausable.assert_not_paused();
+        
+        fn set_onchain_id(ref self: ContractState, onchain_id: felt252) -> bool {
+            // Only owner can update the token information
+            self.ownable.assert_only_owner();
             
-            // Check if sender is frozen
-            let caller = get_caller_address();
-            assert(!self.frozen_addresses.read(caller), 'Sender frozen');
+            self.token_information_map.write('onchain_id', onchain_id);
+            self._emit_token_information();
+            true
+        }
+        
+        fn set_max_supply(ref self: ContractState, max_supply: u256) -> bool {
+            // Only owner can cap the supply, and only before the first mint
+            self.ownable.assert_only_owner();
+            assert(self.erc20.total_supply() == 0, Errors::TOKENS_ALREADY_MINTED);
             
-            // Check if recipient is frozen
-            assert(!self.frozen_addresses.read(to), 'Recipient frozen');
+            self.max_supply_map.write('max_supply', max_supply);
+            self.emit(MaxSupplySet { max_supply });
+            true
+        }
+        
+        fn max_supply(self: @ContractState) -> u256 {
+            self.max_supply_map.read('max_supply')
+        }
+        
+        fn set_document(ref self: ContractState, name: felt252, uri: ByteArray, document_hash: felt252) -> bool {
+            // Only owner can publish documents
+            self.ownable.assert_only_owner();
+            assert(name != 0, Errors::INVALID_DOCUMENT_NAME);
+            assert(uri.len() > 0, Errors::INVALID_DOCUMENT_URI);
             
-            // Check compliance for the transfer
-            self._check_transfer_compliance(caller, to, amount);
+            // New names are appended, existing ones updated in place
+            if self.document_indices.read(name) == 0 {
+                let document_count = self.document_count_map.read('document_count');
+                self.document_names.write(document_count, name);
+                self.document_indices.write(name, document_count + 1);
+                self.document_count_map.write('document_count', document_count + 1);
+            }
+            
+            self.document_uris.write(name, uri.clone());
+            self.document_hashes.write(name, document_hash);
+            self.document_timestamps.write(name, starknet::get_block_timestamp());
             
-            // Perform the transfer using ERC20 component
-            self.erc20.transfer(to, amount)
+            self.emit(DocumentUpdated { name, uri, document_hash });
+            true
         }
-
-        fn transfer_from(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
-            // Check if contract is paused using OpenZeppelin's pausable component
-            self.pausable.assert_not_paused();
+        
+        fn remove_document(ref self: ContractState, name: felt252) -> bool {
+            // Only owner can withdraw documents
+            self.ownable.assert_only_owner();
             
-            // Check if sender is frozen
-            assert(!self.frozen_addresses.read(from), 'Sender frozen');
+            let index = self.document_indices.read(name);
+            assert(index != 0, Errors::DOCUMENT_NOT_FOUND);
             
-            // Check if recipient is frozen
-            assert(!self.frozen_addresses.read(to), 'Recipient frozen');
+            // Move the last document into the freed slot
+            let document_count = self.document_count_map.read('document_count');
+            let last_index = document_count - 1;
+            if index - 1 != last_index {
+                let last_name = self.document_names.read(last_index);
+                self.document_names.write(index - 1, last_name);
+                self.document_indices.write(last_name, index);
+            }
             
-            // Check compliance for the transfer
-            self._check_transfer_compliance(from, to, amount);
+            self.document_names.write(last_index, 0);
+            self.document_indices.write(name, 0);
+            self.document_count_map.write('document_count', last_index);
+            self.document_uris.write(name, "");
+            self.document_hashes.write(name, 0);
+            self.document_timestamps.write(name, 0);
             
-            // Perform the transfer using ERC20 component
-            self.erc20.transfer_from(from, to, amount)
-        }
-
-        fn approve(ref self: ContractState, spender: ContractAddress, amount: u256) -> bool {
-            self.erc20.approve(spender, amount)
+            self.emit(DocumentRemoved { name });
+            true
         }
         
-        // ERC20 camelCase functions
-        fn totalSupply(self: @ContractState) -> u256 {
-            self.erc20.total_supply()
+        fn get_document(self: @ContractState, name: felt252) -> (ByteArray, felt252, u64) {
+            (self.document_uris.read(name), self.document_hashes.read(name), self.document_timestamps.read(name))
         }
         
-        fn balanceOf(self: @ContractState, account: ContractAddress) -> u256 {
-            self.erc20.balance_of(account)
+        fn get_all_documents(self: @ContractState) -> Array<felt252> {
+            let mut names = ArrayTrait::<felt252>::new();
+            let document_count = self.document_count_map.read('document_count');
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= document_count {
+                    break;
+                }
+                
+                names.append(self.document_names.read(i));
+                
+                i += 1;
+            };
+            
+            names
         }
         
-        fn transferFrom(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
-            // Direct implementation to avoid ambiguity
-            // Check if contract is paused using OpenZeppelin's pausable component
-            self.pausable.assert_not_paused();
+        fn snapshot(ref self: ContractState) -> u64 {
+            // Only agents can take snapshots
+            self._assert_agent_permission(get_caller_address(), PERMISSION_SNAPSHOT);
             
-            // Check if sender is frozen
-            assert(!self.frozen_addresses.read(from), 'Sender frozen');
+            let snapshot_id = self.snapshot_id_map.read('snapshot_id') + 1;
+            self.snapshot_id_map.write('snapshot_id', snapshot_id);
             
-            // Check if recipient is frozen
-            assert(!self.frozen_addresses.read(to), 'Recipient frozen');
+            self.emit(Snapshot { snapshot_id });
+            snapshot_id
+        }
+        
+        fn get_current_snapshot_id(self: @ContractState) -> u64 {
+            self.snapshot_id_map.read('snapshot_id')
+        }
+        
+        fn balance_of_at(self: @ContractState, account: ContractAddress, snapshot_id: u64) -> u256 {
+            self._assert_valid_snapshot_id(snapshot_id);
             
-            // Check compliance for the transfer
-            self._check_transfer_compliance(from, to, amount);
+            // The first entry recorded at or after the snapshot holds its value,
+            // without one the balance has not changed since
+            let count = self.account_snapshot_counts.read(account);
+            let i = self._account_snapshot_index(account, snapshot_id);
+            
+            if i < count {
+                self.account_snapshot_values.read((account, i))
+            } else {
+                self.erc20.balance_of(account)
+            }
+        }
+        
+        fn total_supply_at(self: @ContractState, snapshot_id: u64) -> u256 {
+            self._assert_valid_snapshot_id(snapshot_id);
+            
+            let count = self.supply_snapshot_count_map.read('supply_snapshot_count');
+            let i = self._supply_snapshot_index(snapshot_id);
+            
+            if i < count {
+                self.supply_snapshot_values.read(i)
+            } else {
+                self.erc20.total_supply()
+            }
+        }
+        
+        fn get_holder_count(self: @ContractState) -> u32 {
+            self.holder_count_map.read('holder_count')
+        }
+        
+        fn get_holder(self: @ContractState, index: u32) -> ContractAddress {
+            assert(index < self.holder_count_map.read('holder_count'), Errors::INVALID_HOLDER_INDEX);
+            self.holders.read(index)
+        }
+        
+        fn get_holders(self: @ContractState, offset: u32, limit: u32) -> Array<ContractAddress> {
+            // Pages of at most `limit` holders from `offset`, empty past the end
+            let count = self.holder_count_map.read('holder_count');
+            let mut holders = array![];
+            let mut i = offset;
+            loop {
+                if i >= count || i - offset >= limit {
+                    break;
+                }
+                holders.append(self.holders.read(i));
+                i += 1;
+            };
+            holders
+        }
+        
+        fn is_holder(self: @ContractState, account: ContractAddress) -> bool {
+            self.holder_indices.read(account) != 0
+        }
+
+        fn apply_split(ref self: ContractState, numerator: u256, denominator: u256) -> bool {
+            // Only owner can apply corporate actions
+            self.ownable.assert_only_owner();
+            assert(numerator > 0 && denominator > 0, Errors::INVALID_SPLIT_RATIO);
+            
+            // A 10:1 split is (10, 1), a 1:10 reverse split (1, 10); raw balances are untouched
+            let (current_numerator, current_denominator) = self.get_split_multiplier();
+            let multiplier_numerator = self._checked_mul(current_numerator, numerator);
+            let multiplier_denominator = self._checked_mul(current_denominator, denominator);
+            let divisor = self._gcd(multiplier_numerator, multiplier_denominator);
+            let multiplier_numerator = multiplier_numerator / divisor;
+            let multiplier_denominator = multiplier_denominator / divisor;
             
-            // Perform the transfer using ERC20 component
-            self.erc20.transfer_from(from, to, amount)
+            self.split_multiplier_map.write('numerator', multiplier_numerator);
+            self.split_multiplier_map.write('denominator', multiplier_denominator);
+            
+            self.emit(SplitApplied { numerator, denominator, multiplier_numerator, multiplier_denominator });
+            true
+        }
+        
+        fn get_split_multiplier(self: @ContractState) -> (u256, u256) {
+            let numerator = self.split_multiplier_map.read('numerator');
+            if numerator == 0 {
+                return (1, 1);
+            }
+            (numerator, self.split_multiplier_map.read('denominator'))
+        }
+        
+        fn to_adjusted_amount(self: @ContractState, raw_amount: u256) -> u256 {
+            let (numerator, denominator) = self.get_split_multiplier();
+            self._checked_mul(raw_amount, numerator) / denominator
+        }
+        
+        fn to_raw_amount(self: @ContractState, adjusted_amount: u256) -> u256 {
+            let (numerator, denominator) = self.get_split_multiplier();
+            self._checked_mul(adjusted_amount, denominator) / numerator
+        }
+        
+        fn adjusted_balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+            self.to_adjusted_amount(self.erc20.balance_of(account))
+        }
+        
+        fn adjusted_total_supply(self: @ContractState) -> u256 {
+            self.to_adjusted_amount(self.erc20.total_supply())
+        }
+        
+        fn decimals(self: @ContractState) -> u8 {
+            // Use the value from our ImmutableConfig implementation
+            // In OpenZeppelin v2.0.0, decimals comes from ImmutableConfig
+            18
+        }
+
+        fn total_supply(self: @ContractState) -> u256 {
+            self.erc20.total_supply()
+        }
+
+        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+            self.erc20.balance_of(account)
+        }
+
+        fn allowance(self: @ContractState, owner: ContractAddress, spender: ContractAddress) -> u256 {
+            self.erc20.allowance(owner, spender)
+        }
+
+        fn transfer(ref self: ContractState, to: ContractAddress, amount: u256) -> bool {
+            // Venues requiring trade references only accept `transfer_with_data`
+            self._transfer(get_caller_address(), to, amount, false);
+            true
+        }
+        
+        fn transfer_with_data(ref self: ContractState, to: ContractAddress, amount: u256, data: ByteArray) -> bool {
+            // Reference (ISIN, trade id, travel-rule hash...) logged for reconciliation
+            assert(data.len() <= MAX_TRANSFER_DATA_LENGTH, Errors::TRANSFER_DATA_TOO_LONG);
+            
+            let caller = get_caller_address();
+            self._transfer(caller, to, amount, data.len() > 0);
+            self._emit_transfer_data(caller, to, amount, data);
+            true
+        }
+        
+        fn set_transfer_reference_required(ref self: ContractState, required: bool) -> bool {
+            // Only owner can change the transfer policy
+            self.ownable.assert_only_owner();
+            
+            self.reference_required_map.write('reference_required', required);
+            self.emit(TransferReferenceRequirementSet { required });
+            true
+        }
+        
+        fn is_transfer_reference_required(self: @ContractState) -> bool {
+            self.reference_required_map.read('reference_required')
+        }
+        
+        fn set_transfer_fees(ref self: ContractState, fees_module: ContractAddress) -> bool {
+            // Only owner can charge fees on transfers, the zero address stops them
+            self.ownable.assert_only_owner();
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if fees_module != zero_address {
+                let module_compliance = ITransferFeesModuleDispatcher { contract_address: fees_module }.get_compliance();
+                assert(module_compliance == self.compliance_map.read('compliance'), Errors::FEES_MODULE_BOUND_ELSEWHERE);
+            }
+            
+            self.transfer_fees_map.write('transfer_fees', fees_module);
+            self.emit(TransferFeesSet { fees_module });
+            true
+        }
+        
+        fn get_transfer_fees(self: @ContractState) -> ContractAddress {
+            self.transfer_fees_map.read('transfer_fees')
+        }
+        
+
+        fn transfer_from(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Same checks and fees as a transfer from the holder, without a reference
+            self.erc20._spend_allowance(from, get_caller_address(), amount);
+            self._transfer(from, to, amount, false);
+            true
+        }
+
+        fn approve(ref self: ContractState, spender: ContractAddress, amount: u256) -> bool {
+            self.erc20.approve(spender, amount)
+        }
+        
+        fn increase_allowance(ref self: ContractState, spender: ContractAddress, added_value: u256) -> bool {
+            let caller = get_caller_address();
+            let current_allowance = self.erc20.allowance(caller, spender);
+            
+            self.erc20._approve(caller, spender, self._checked_add(current_allowance, added_value));
+            true
+        }
+        
+        fn decrease_allowance(ref self: ContractState, spender: ContractAddress, subtracted_value: u256) -> bool {
+            // Decreasing to zero revokes the delegate entirely
+            let caller = get_caller_address();
+            let current_allowance = self.erc20.allowance(caller, spender);
+            assert(current_allowance >= subtracted_value, Errors::ALLOWANCE_BELOW_ZERO);
+            
+            self.erc20._approve(caller, spender, current_allowance - subtracted_value);
+            true
+        }
+        
+        // ERC20 camelCase functions
+        fn totalSupply(self: @ContractState) -> u256 {
+            self.erc20.total_supply()
+        }
+        
+        fn balanceOf(self: @ContractState, account: ContractAddress) -> u256 {
+            self.erc20.balance_of(account)
+        }
+        
+        fn transferFrom(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            // Direct implementation to avoid ambiguity
+            self.erc20._spend_allowance(from, get_caller_address(), amount);
+            self._transfer(from, to, amount, false);
+            true
         }
         
         // ERC2612 permit implementation
@@ -345,14 +1201,19 @@ pub mod ERC3643Token {
             deadline: u64,
             signature: Span<felt252>
         ) {
-            // For now, permit functionality is a placeholder
-            // We would need to implement a proper permit mechanism
-            // This requires more specific implementation with OpenZeppelin v2.0.0
-            assert(deadline >= starknet::get_block_timestamp(), 'Permit expired');
-            
-            // Approve the spender for the amount
-            // Use the nonce and increment it
-            self.nonces.use_nonce(owner);
+            // Anyone can relay the approval, the owner's signature stands in for the caller
+            assert(deadline >= starknet::get_block_timestamp(), Errors::PERMIT_EXPIRED);
+            
+            // Nonces are sequential, so each signed permit is used at most once
+            let nonce = self.nonces.use_nonce(owner);
+            let permit = Permit { token: get_contract_address(), owner, spender, amount, nonce, deadline };
+            let hash = permit.get_message_hash(owner);
+            let mut signature_array = ArrayTrait::<felt252>::new();
+            signature_array.append_span(signature);
+            let result = super::IAccountContractDispatcher { contract_address: owner }
+                .is_valid_signature(hash, signature_array);
+            assert(result == starknet::VALIDATED || result == 1, Errors::INVALID_SIGNATURE);
+            
             self.erc20._approve(owner, spender, amount);
         }
         
@@ -361,8 +1222,47 @@ pub mod ERC3643Token {
             self.nonces.nonces(owner)
         }
         
+        fn transfer_with_authorization(
+            ref self: ContractState,
+            from: ContractAddress,
+            to: ContractAddress,
+            amount: u256,
+            nonce: felt252,
+            expiry: u64,
+            signature: Array<felt252>
+        ) -> bool {
+            // Anyone can relay the transfer, the holder's signature stands in for the caller
+            assert(expiry >= starknet::get_block_timestamp(), Errors::AUTHORIZATION_EXPIRED);
+            assert(!self.used_authorizations.read((from, nonce)), Errors::AUTHORIZATION_ALREADY_USED);
+            
+            let authorization = TransferAuthorization { token: get_contract_address(), from, to, amount, nonce, expiry };
+            let hash = authorization.get_message_hash(from);
+            let result = super::IAccountContractDispatcher { contract_address: from }
+                .is_valid_signature(hash, signature);
+            assert(result == starknet::VALIDATED || result == 1, Errors::INVALID_SIGNATURE);
+            
+            // Nonces are single use, whatever their order
+            self.used_authorizations.write((from, nonce), true);
+            self.emit(AuthorizationUsed { from, nonce });
+            
+            // Same checks and fees as a transfer from the holder, without a reference
+            self._transfer(from, to, amount, false);
+            true
+        }
+        
+        fn is_authorization_used(self: @ContractState, from: ContractAddress, nonce: felt252) -> bool {
+            self.used_authorizations.read((from, nonce))
+        }
+        
         fn DOMAIN_SEPARATOR(self: @ContractState) -> felt252 {
-            'ERC3643Token_v1' // Simple domain separator
+            // The SNIP-12 domain hashed into every permit and transfer authorization
+            let domain = StarknetDomain {
+                name: ERC3643TokenSNIP12Metadata::name(),
+                version: ERC3643TokenSNIP12Metadata::version(),
+                chain_id: starknet::get_tx_info().unbox().chain_id,
+                revision: 1,
+            };
+            domain.hash_struct()
         }
 
         // Pausable functions
@@ -388,6 +1288,20 @@ pub mod ERC3643Token {
             // Use the OpenZeppelin pausable component
             self.pausable.is_paused()
         }
+        
+        fn set_pause_scopes(ref self: ContractState, scopes: u8) -> bool {
+            // Only owner can pause
+            self.ownable.assert_only_owner();
+            assert(scopes <= PAUSE_TRANSFERS | PAUSE_ALL_MOVEMENTS, Errors::INVALID_PAUSE_SCOPES);
+            
+            self.pause_scopes_map.write('pause_scopes', scopes);
+            self.emit(PauseScopesSet { scopes });
+            true
+        }
+        
+        fn get_pause_scopes(self: @ContractState) -> u8 {
+            self.pause_scopes_map.read('pause_scopes')
+        }
 
         // Ownable functions
         fn owner(self: @ContractState) -> ContractAddress {
@@ -407,29 +1321,55 @@ pub mod ERC3643Token {
         // ERC3643 specific functions
         fn forced_transfer(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
             let caller = get_caller_address();
-            assert(self.agents.read(caller), 'Only agents allowed');
-            
-            self._check_transfer_compliance(from, to, amount);
+            self._assert_agent_permission(caller, PERMISSION_FORCED_TRANSFER);
             
-            // Use the ERC20 internal transfer function but check for frozen status first
-            assert(!self.frozen_addresses.read(from), 'Sender frozen');
-            assert(!self.frozen_addresses.read(to), 'Recipient frozen');
+            self._forced_transfer(from, to, amount);
+            true
+        }
+        
+        fn batch_forced_transfer(
+            ref self: ContractState,
+            from_list: Array<ContractAddress>,
+            to_list: Array<ContractAddress>,
+            amounts: Array<u256>
+        ) -> bool {
+            // Agent checked once for the whole batch
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FORCED_TRANSFER);
+            assert(from_list.len() == to_list.len(), Errors::ARRAY_LENGTH_MISMATCH);
+            assert(from_list.len() == amounts.len(), Errors::ARRAY_LENGTH_MISMATCH);
             
-            // Ensure contract is not paused using OpenZeppelin's pausable component
-            self.pausable.assert_not_paused();
+            // All or nothing, one failing leg reverts the whole batch
+            let mut i: u32 = 0;
+            loop {
+                if i >= from_list.len() {
+                    break;
+                }
+                
+                self._forced_transfer(*from_list.at(i), *to_list.at(i), *amounts.at(i));
+                
+                i += 1;
+            };
             
-            // Use ERC20 internal transfer method to bypass allowance checks
-            // This is a forced transfer, so we don't need to check allowances
-            self.erc20._transfer(from, to, amount);
             true
         }
         
         fn mint(ref self: ContractState, to: ContractAddress, amount: u256) -> bool {
             let caller = get_caller_address();
-            assert(self.agents.read(caller), 'Only agents allowed');
+            self._assert_agent_permission(caller, PERMISSION_MINT);
+            self._assert_movements_not_paused();
+            self._use_mint_allowance(caller, amount);
             
             // Verify recipient has valid identity
-            assert(self._is_verified_address(to), 'Recipient not verified');
+            assert(self._is_verified_address(to), Errors::RECIPIENT_NOT_VERIFIED);
+            
+            let max_supply = self.max_supply_map.read('max_supply');
+            let new_supply = self._checked_add(self.erc20.total_supply(), amount);
+            assert(max_supply == 0 || new_supply <= max_supply, Errors::SUPPLY_CAP_EXCEEDED);
+            
+            // Mints are evaluated by the compliance modules with a zero sender
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            self._assert_compliant(zero_address, to, amount);
             
             // Mint tokens using ERC20 component
             self.erc20.mint(to, amount);
@@ -438,22 +1378,44 @@ pub mod ERC3643Token {
         
         fn burn(ref self: ContractState, amount: u256) -> bool {
             let caller = get_caller_address();
+            self._assert_movements_not_paused();
+            
+            // Frozen tokens cannot be burnt by their holder
+            assert(self._free_balance(caller) >= amount, Errors::INSUFFICIENT_UNFROZEN_BALANCE);
             
             // Use ERC20 component burn function
             self.erc20.burn(caller, amount);
             true
         }
         
+        fn forced_burn(ref self: ContractState, user_address: ContractAddress, amount: u256) -> bool {
+            // Redemptions and court orders, no holder signature needed
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_BURN);
+            self._assert_movements_not_paused();
+            
+            // Frozen tokens may be burnt, they are unfrozen first
+            self._unfreeze_for_forced_move(user_address, amount);
+            self.erc20.burn(user_address, amount);
+            
+            self.emit(ForcedBurn { agent: caller, from: user_address, amount });
+            true
+        }
+        
         fn recover(ref self: ContractState, lost_address: ContractAddress, amount: u256) -> bool {
-            // Only owner can recover tokens
-            self.ownable.assert_only_owner();
+            // Only agents allowed to recover tokens
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_RECOVERY);
+            self._assert_movements_not_paused();
             
             let owner = self.ownable.owner();
+            assert(lost_address != owner, Errors::SAME_ADDRESS);
             let recovered_balance = self.erc20.balance_of(lost_address);
-            assert(recovered_balance >= amount, 'Insufficient balance');
+            assert(recovered_balance >= amount, Errors::INSUFFICIENT_BALANCE);
             
             // Transfer tokens from lost address to owner using internal transfer
-            assert(!self.frozen_addresses.read(lost_address), 'Address frozen');
+            assert(!self.frozen_addresses.read(lost_address), Errors::ADDRESS_FROZEN);
+            self._unfreeze_for_forced_move(lost_address, amount);
             self.erc20._transfer(lost_address, owner, amount);
             
             self.emit(RecoverySuccess { from: lost_address, to: owner, amount });
@@ -469,17 +1431,185 @@ pub mod ERC3643Token {
         }
         
         fn set_address_frozen(ref self: ContractState, target_address: ContractAddress, frozen: bool) -> bool {
+            self.set_address_frozen_with_reason(target_address, frozen, 0)
+        }
+        
+        fn batch_set_address_frozen(ref self: ContractState, target_addresses: Array<ContractAddress>, frozen: Array<bool>) -> bool {
             let caller = get_caller_address();
-            assert(self.agents.read(caller), 'Only agents allowed');
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            assert(target_addresses.len() == frozen.len(), Errors::ARRAY_LENGTH_MISMATCH);
             
-            self.frozen_addresses.write(target_address, frozen);
+            let mut i: u32 = 0;
+            loop {
+                if i >= target_addresses.len() {
+                    break;
+                }
+                
+                self._set_address_frozen(*target_addresses.at(i), *frozen.at(i), caller, 0);
+                
+                i += 1;
+            };
             
-            if frozen {
-                self.emit(Frozen { address: target_address });
+            true
+        }
+        
+        fn freeze_partial_tokens(ref self: ContractState, user_address: ContractAddress, amount: u256) -> bool {
+            self.freeze_partial_tokens_with_reason(user_address, amount, 0)
+        }
+        
+        fn unfreeze_partial_tokens(ref self: ContractState, user_address: ContractAddress, amount: u256) -> bool {
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            
+            let frozen = self.frozen_tokens.read(user_address);
+            assert(frozen >= amount, Errors::AMOUNT_EXCEEDS_FROZEN_TOKENS);
+            
+            self.frozen_tokens.write(user_address, frozen - amount);
+            self.emit(TokensUnfrozen { address: user_address, amount });
+            self._record_freeze_action(user_address, caller, 0);
+            true
+        }
+        
+        fn get_frozen_tokens(self: @ContractState, user_address: ContractAddress) -> u256 {
+            self.frozen_tokens.read(user_address)
+        }
+        
+        fn set_address_frozen_with_reason(ref self: ContractState, target_address: ContractAddress, frozen: bool, reason: felt252) -> bool {
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            
+            self._set_address_frozen(target_address, frozen, caller, reason);
+            true
+        }
+        
+        fn freeze_partial_tokens_with_reason(ref self: ContractState, user_address: ContractAddress, amount: u256, reason: felt252) -> bool {
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            
+            let frozen = self._checked_add(self.frozen_tokens.read(user_address), amount);
+            assert(self.erc20.balance_of(user_address) >= frozen, Errors::AMOUNT_EXCEEDS_BALANCE);
+            
+            self.frozen_tokens.write(user_address, frozen);
+            self.emit(TokensFrozen { address: user_address, amount });
+            self._record_freeze_action(user_address, caller, reason);
+            true
+        }
+        
+        fn get_freeze_record(self: @ContractState, address: ContractAddress) -> (felt252, ContractAddress, u64) {
+            // (reason, agent, timestamp) of the last freeze or unfreeze
+            (
+                self.freeze_reasons.read(address),
+                self.freeze_agents.read(address),
+                self.freeze_timestamps.read(address)
+            )
+        }
+        
+        fn hold_tokens(ref self: ContractState, user_address: ContractAddress, amount: u256) -> bool {
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            
+            // Only tokens the holder could transfer can be held
+            assert(self._free_balance(user_address) >= amount, Errors::INSUFFICIENT_UNFROZEN_BALANCE);
+            
+            let held = self._checked_add(self.held_tokens.read((user_address, caller)), amount);
+            self.held_tokens.write((user_address, caller), held);
+            let total_held = self._checked_add(self.total_held_tokens.read(user_address), amount);
+            self.total_held_tokens.write(user_address, total_held);
+            
+            self.emit(TokensHeld { address: user_address, agent: caller, amount });
+            true
+        }
+        
+        fn release_held_tokens(ref self: ContractState, user_address: ContractAddress, amount: u256) -> bool {
+            let caller = get_caller_address();
+            self._assert_agent_permission(caller, PERMISSION_FREEZE);
+            
+            let held = self.held_tokens.read((user_address, caller));
+            assert(held >= amount, Errors::AMOUNT_EXCEEDS_HELD_TOKENS);
+            
+            self.held_tokens.write((user_address, caller), held - amount);
+            self.total_held_tokens.write(user_address, self.total_held_tokens.read(user_address) - amount);
+            
+            self.emit(HeldTokensReleased { address: user_address, agent: caller, amount });
+            true
+        }
+        
+        fn get_held_tokens(self: @ContractState, user_address: ContractAddress, agent: ContractAddress) -> u256 {
+            self.held_tokens.read((user_address, agent))
+        }
+        
+        fn balance_of_by_partition(self: @ContractState, account: ContractAddress, partition: felt252) -> u256 {
+            if partition == DEFAULT_PARTITION {
+                self._default_partition_balance(account)
             } else {
-                self.emit(Unfrozen { address: target_address });
+                self.partition_balances.read((account, partition))
             }
+        }
+        
+        fn partitions_of(self: @ContractState, account: ContractAddress) -> Array<felt252> {
+            let mut partitions = ArrayTrait::<felt252>::new();
+            let partition_count = self.partition_counts.read(account);
+            
+            let mut i: u32 = 0;
+            loop {
+                if i >= partition_count {
+                    break;
+                }
+                
+                partitions.append(self.holder_partitions.read((account, i)));
+                
+                i += 1;
+            };
+            
+            partitions
+        }
+        
+        fn transfer_by_partition(
+            ref self: ContractState, partition: felt252, to: ContractAddress, amount: u256, data: ByteArray
+        ) -> bool {
+            // Optional reference, as with `transfer_with_data`
+            assert(data.len() <= MAX_TRANSFER_DATA_LENGTH, Errors::TRANSFER_DATA_TOO_LONG);
+            let caller = get_caller_address();
             
+            // The tokens leave the sender's partition, move with the usual checks
+            // and land in the same partition of the recipient, net of any transfer fee
+            if partition != DEFAULT_PARTITION {
+                self._unassign_partition(caller, partition, amount);
+            }
+            let received = self._transfer(caller, to, amount, data.len() > 0);
+            if partition != DEFAULT_PARTITION && received > 0 {
+                self._assign_partition(to, partition, received);
+            }
+            
+            self.emit(TransferByPartition { partition, from: caller, to, amount });
+            if data.len() > 0 {
+                self._emit_transfer_data(caller, to, amount, data);
+            }
+            true
+        }
+        
+        fn reassign_partition(
+            ref self: ContractState,
+            account: ContractAddress,
+            from_partition: felt252,
+            to_partition: felt252,
+            amount: u256
+        ) -> bool {
+            // Only agents move tokens between partitions, e.g. when a lockup ends
+            self._assert_agent_permission(get_caller_address(), PERMISSION_PARTITION);
+            assert(from_partition != to_partition, Errors::SAME_PARTITION);
+            assert(amount > 0, Errors::INVALID_AMOUNT);
+            
+            if from_partition == DEFAULT_PARTITION {
+                assert(self._default_partition_balance(account) >= amount, Errors::INSUFFICIENT_PARTITION_BALANCE);
+            } else {
+                self._unassign_partition(account, from_partition, amount);
+            }
+            if to_partition != DEFAULT_PARTITION {
+                self._assign_partition(account, to_partition, amount);
+            }
+            
+            self.emit(PartitionReassigned { account, from_partition, to_partition, amount });
             true
         }
         
@@ -501,10 +1631,70 @@ pub mod ERC3643Token {
             true
         }
         
+        fn compliance(self: @ContractState) -> ContractAddress {
+            self.compliance_map.read('compliance')
+        }
+        
+        fn identity_registry(self: @ContractState) -> ContractAddress {
+            self.identity_registry_map.read('registry')
+        }
+        
         fn is_verified_address(self: @ContractState, address: ContractAddress) -> bool {
             self._is_verified_address(address)
         }
         
+        fn can_transfer(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u8 {
+            // Read-only pre-flight of the checks made by `transfer` and `transfer_from`
+            self._transfer_status(from, to, amount)
+        }
+        
+        fn can_transfer_batch(
+            self: @ContractState,
+            from_list: Array<ContractAddress>,
+            to_list: Array<ContractAddress>,
+            amounts: Array<u256>
+        ) -> Array<u8> {
+            assert(from_list.len() == to_list.len(), Errors::ARRAY_LENGTH_MISMATCH);
+            assert(from_list.len() == amounts.len(), Errors::ARRAY_LENGTH_MISMATCH);
+            
+            // Entries are evaluated independently against the current state
+            let mut codes = ArrayTrait::<u8>::new();
+            let mut i: u32 = 0;
+            loop {
+                if i >= from_list.len() {
+                    break;
+                }
+                
+                codes.append(self._transfer_status(*from_list.at(i), *to_list.at(i), *amounts.at(i)));
+                
+                i += 1;
+            };
+            
+            codes
+        }
+        
+        fn can_transfer_with_reason(
+            self: @ContractState,
+            from: ContractAddress,
+            to: ContractAddress,
+            amount: u256
+        ) -> (u8, ContractAddress, felt252) {
+            // Like `can_transfer`, also naming the compliance module that rejects the transfer
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            let code = self._transfer_status(from, to, amount);
+            if code != TRANSFER_NOT_COMPLIANT {
+                return (code, zero_address, 0);
+            }
+            
+            let compliance = IComplianceDispatcher { contract_address: self.compliance_map.read('compliance') };
+            let (found, _, rule) = compliance.find_failing_rule(from, to, amount, false);
+            if !found {
+                return (code, zero_address, 0);
+            }
+            
+            (code, rule, IComplianceModuleDispatcher { contract_address: rule }.name())
+        }
+        
         fn is_compliance_agent(self: @ContractState, address: ContractAddress) -> bool {
             self.agents.read(address)
         }
@@ -518,6 +1708,7 @@ pub mod ERC3643Token {
             self.ownable.assert_only_owner();
             
             self.agents.write(agent, true);
+            self.agent_restricted.write(agent, false);
             self.emit(AgentAdded { agent });
             true
         }
@@ -530,32 +1721,177 @@ pub mod ERC3643Token {
             self.emit(AgentRemoved { agent });
             true
         }
+        
+        fn set_agent_permissions(ref self: ContractState, agent: ContractAddress, permissions: u8) -> bool {
+            // Only owner can scope agents
+            self.ownable.assert_only_owner();
+            assert(self.agents.read(agent), Errors::NOT_AN_AGENT);
+            assert(permissions <= PERMISSION_ALL, Errors::INVALID_PERMISSIONS);
+            
+            self.agent_restricted.write(agent, true);
+            self.agent_permissions.write(agent, permissions);
+            self.emit(AgentPermissionsSet { agent, permissions });
+            true
+        }
+        
+        fn set_mint_allowance(ref self: ContractState, agent: ContractAddress, amount: u256, expiry: u64) -> bool {
+            // Only owner can bound what an agent may mint
+            self.ownable.assert_only_owner();
+            
+            self.mint_allowance_capped.write(agent, true);
+            self.mint_allowances.write(agent, amount);
+            self.mint_allowance_expiries.write(agent, expiry);
+            self.emit(MintAllowanceSet { agent, amount, expiry });
+            true
+        }
+        
+        fn remove_mint_allowance(ref self: ContractState, agent: ContractAddress) -> bool {
+            // Only owner can lift the cap
+            self.ownable.assert_only_owner();
+            
+            self.mint_allowance_capped.write(agent, false);
+            self.mint_allowances.write(agent, 0);
+            self.mint_allowance_expiries.write(agent, 0);
+            self.emit(MintAllowanceRemoved { agent });
+            true
+        }
+        
+        fn get_mint_allowance(self: @ContractState, agent: ContractAddress) -> (bool, u256, u64) {
+            (
+                self.mint_allowance_capped.read(agent),
+                self.mint_allowances.read(agent),
+                self.mint_allowance_expiries.read(agent)
+            )
+        }
+        
+        fn get_agent_permissions(self: @ContractState, agent: ContractAddress) -> u8 {
+            if !self.agents.read(agent) {
+                return 0;
+            }
+            if !self.agent_restricted.read(agent) {
+                return PERMISSION_ALL;
+            }
+            self.agent_permissions.read(agent)
+        }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the token implementation
+            self.ownable.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.ownable.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, Errors::STORAGE_ALREADY_MIGRATED);
+            
+            // Version 2 stores the name and symbol, earlier versions reported fixed ones
+            if from_version < 2 {
+                self.token_information_map.write('name', 'Token');
+                self.token_information_map.write('symbol', 'TKN');
+            }
+            
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn get_token_state(self: @ContractState) -> (felt252, felt252, u8, u256, bool, u8, ContractAddress, ContractAddress, u8) {
+            // (name, symbol, decimals, total supply, paused, pause scopes, compliance, identity registry, storage version)
+            (
+                self.token_information_map.read('name'),
+                self.token_information_map.read('symbol'),
+                self.decimals(),
+                self.erc20.total_supply(),
+                self.pausable.is_paused(),
+                self.pause_scopes_map.read('pause_scopes'),
+                self.compliance_map.read('compliance'),
+                self.identity_registry_map.read('registry'),
+                self.storage_version_map.read('version')
+            )
+        }
+        
+        fn get_holder_state(self: @ContractState, user_address: ContractAddress) -> (u256, u256, bool, felt252, bool) {
+            // (balance, frozen tokens, frozen, identity, verified)
+            let calldata = array![user_address.into()];
+            let result = call_contract_syscall(
+                self.identity_registry_map.read('registry'),
+                selector!("get_identity"),
+                calldata.span()
+            ).unwrap();
+            let identity = if result.len() > 0 { *result.at(0) } else { 0 };
+            
+            (
+                self.erc20.balance_of(user_address),
+                self.frozen_tokens.read(user_address),
+                self.frozen_addresses.read(user_address),
+                identity,
+                self._is_verified_address(user_address)
+            )
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     
     // Internal functions implementation
     #[generate_trait]
     impl InternalFunctions of InternalTrait {
+        fn _zero_amount_transfers_allowed(self: @ContractState) -> bool {
+            IComplianceDispatcher { contract_address: self.compliance_map.read('compliance') }
+                .are_zero_amount_transfers_allowed()
+        }
+        
         fn _check_transfer_compliance(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
             // Following checks-effects-interactions pattern to prevent reentrancy
             
             // 1. CHECKS: Verify identities first
             // Check if sender and recipient have verified identities
-            assert(self._is_verified_address(from), 'Sender not verified');
-            assert(self._is_verified_address(to), 'Recipient not verified');
+            assert(self._is_verified_address(from), Errors::SENDER_NOT_VERIFIED);
+            assert(self._is_verified_address(to), Errors::RECIPIENT_NOT_VERIFIED);
             
-            // 2. Read state that we'll need for external call
+            // 2. Evaluate the compliance modules
+            self._assert_compliant(from, to, amount);
+        }
+        
+        fn _assert_compliant(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            self._assert_compliance_call(from, to, amount, false);
+        }
+        
+        fn _assert_compliance_call(
+            self: @ContractState,
+            from: ContractAddress,
+            to: ContractAddress,
+            amount: u256,
+            forced: bool
+        ) {
+            // The compliance contract must have agreed to govern this token
+            assert(self._is_bound_to_compliance(), Errors::TOKEN_NOT_BOUND_TO_COMPLIANCE);
+            
+            // Read state that we'll need for external call
             let compliance_contract = self.compliance_map.read('compliance');
             
             // Convert u256 amount to felt252s for the call
             let amount_low = amount.low;
             let amount_high = amount.high;
             
-            // 3. INTERACTIONS: Make external call last (after all checks and state changes)
+            // INTERACTIONS: Make external call last (after all checks and state changes)
+            let check_selector = if forced {
+                selector!("check_forced_transfer")
+            } else {
+                selector!("check_compliance")
+            };
             let calldata = array![from.into(), to.into(), amount_low.into(), amount_high.into()];
             let success = call_contract_syscall(
                 compliance_contract,
-                selector!("check_compliance"),
+                check_selector,
                 calldata.span()
             ).unwrap();
             
@@ -563,10 +1899,507 @@ pub mod ERC3643Token {
             let has_result = success.len() > 0;
             if has_result {
                 let result_value = *success.at(0);
-                assert(result_value != 0, 'Transfer not compliant');
+                if result_value == 0 {
+                    // The revert names the rejecting module: (error, rule index, module)
+                    let (_, index, rule) = IComplianceDispatcher { contract_address: compliance_contract }
+                        .find_failing_rule(from, to, amount, forced);
+                    panic(array![Errors::TRANSFER_NOT_COMPLIANT, index.into(), rule.into()]);
+                }
             } else {
                 // No result means the call failed
-                assert(false, 'Compliance check failed');
+                assert(false, Errors::COMPLIANCE_CHECK_FAILED);
+            }
+        }
+        
+        fn _is_bound_to_compliance(self: @ContractState) -> bool {
+            let compliance_contract = self.compliance_map.read('compliance');
+            let calldata = array![get_contract_address().into()];
+            let result = call_contract_syscall(
+                compliance_contract,
+                selector!("is_token_bound"),
+                calldata.span()
+            ).unwrap();
+            
+            result.len() > 0 && *result.at(0) != 0
+        }
+        
+        fn _is_compliant(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> bool {
+            let compliance_contract = self.compliance_map.read('compliance');
+            let calldata = array![from.into(), to.into(), amount.low.into(), amount.high.into()];
+            let result = call_contract_syscall(
+                compliance_contract,
+                selector!("check_compliance"),
+                calldata.span()
+            ).unwrap();
+            
+            result.len() > 0 && *result.at(0) != 0
+        }
+        
+        fn _transfer_status(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> u8 {
+            // Same checks as a transfer, reported instead of asserted
+            let pause_scopes = self.pause_scopes_map.read('pause_scopes');
+            if self.pausable.is_paused() || pause_scopes & (PAUSE_TRANSFERS | PAUSE_ALL_MOVEMENTS) != 0 {
+                return TRANSFER_PAUSED;
+            }
+            if self.frozen_addresses.read(from) {
+                return TRANSFER_SENDER_FROZEN;
+            }
+            if self.frozen_addresses.read(to) {
+                return TRANSFER_RECIPIENT_FROZEN;
+            }
+            if self._free_balance(from) < amount {
+                return TRANSFER_INSUFFICIENT_BALANCE;
+            }
+            if !self._is_verified_address(from) {
+                return TRANSFER_SENDER_NOT_VERIFIED;
+            }
+            if !self._is_verified_address(to) {
+                return TRANSFER_RECIPIENT_NOT_VERIFIED;
+            }
+            if !self._is_bound_to_compliance() {
+                return TRANSFER_TOKEN_NOT_BOUND;
+            }
+            if !self._is_compliant(from, to, amount) {
+                return TRANSFER_NOT_COMPLIANT;
+            }
+            if amount == 0 && !self._zero_amount_transfers_allowed() {
+                return TRANSFER_ZERO_AMOUNT;
+            }
+            
+            TRANSFER_OK
+        }
+        
+        // Returns the amount the recipient received, net of the transfer fee
+        fn _transfer(
+            ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256, with_reference: bool
+        ) -> u256 {
+            self._assert_transfer_allowed(from, amount, with_reference);
+            if amount == 0 {
+                assert(self._zero_amount_transfers_allowed(), Errors::ZERO_AMOUNT_TRANSFER);
+            }
+            
+            // The fee is split off the amount, each leg checked and reported to compliance on its own
+            let (fee, treasury) = self._transfer_fee(from, to, amount);
+            if fee > 0 {
+                self._assert_leg_allowed(from, treasury, fee);
+                self.erc20._transfer(from, treasury, fee);
+                self.emit(TransferFeeCharged { from, to, treasury, fee });
+            }
+            
+            // Checked once the fee has moved, so the modules see the treasury's new balance
+            self._assert_leg_allowed(from, to, amount - fee);
+            self.erc20._transfer(from, to, amount - fee);
+            amount - fee
+        }
+        
+        fn _emit_transfer_data(
+            ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256, data: ByteArray
+        ) {
+            // The hash lets indexers match references without decoding them
+            let mut serialized_data = ArrayTrait::<felt252>::new();
+            data.serialize(ref serialized_data);
+            let data_hash = core::poseidon::poseidon_hash_span(serialized_data.span());
+            
+            self.emit(TransferData { from, to, amount, data_hash, data });
+        }
+        
+        fn _assert_transfer_allowed(ref self: ContractState, from: ContractAddress, amount: u256, with_reference: bool) {
+            // Venues requiring trade references only accept transfers that carry one
+            assert(
+                with_reference || !self.reference_required_map.read('reference_required'),
+                Errors::TRANSFER_REFERENCE_REQUIRED
+            );
+            
+            // Check if transfers are paused, fully or by scope
+            self._assert_transfers_not_paused();
+            
+            // Check if sender is frozen
+            assert(!self.frozen_addresses.read(from), Errors::SENDER_FROZEN);
+            
+            // Frozen tokens stay put
+            assert(self._free_balance(from) >= amount, Errors::INSUFFICIENT_UNFROZEN_BALANCE);
+        }
+        
+        fn _assert_leg_allowed(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // Check if recipient is frozen
+            assert(!self.frozen_addresses.read(to), Errors::RECIPIENT_FROZEN);
+            
+            // Check compliance for the transfer
+            self._check_transfer_compliance(from, to, amount);
+        }
+        
+        fn _transfer_fee(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) -> (u256, ContractAddress) {
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            let fees_module = self.transfer_fees_map.read('transfer_fees');
+            if fees_module == zero_address || amount == 0 {
+                return (0, zero_address);
+            }
+            
+            let fees = ITransferFeesModuleDispatcher { contract_address: fees_module };
+            if fees.is_transfer_exempt(from, to) {
+                return (0, zero_address);
+            }
+            
+            // amount * fee_bps / 10000 + flat_fee, capped at the amount
+            let (fee_bps, flat_fee) = fees.get_fee();
+            let proportional_fee = self._checked_mul(amount, fee_bps.into()) / BPS_DENOMINATOR.into();
+            let fee = self._checked_add(proportional_fee, flat_fee);
+            let fee = if fee > amount { amount } else { fee };
+            (fee, fees.get_treasury())
+        }
+        
+        fn _forced_transfer(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            // Both parties must be verified and the modules must accept the transfer,
+            // rules the compliance owner exempted from forced transfers excepted
+            assert(self._is_verified_address(from), Errors::SENDER_NOT_VERIFIED);
+            assert(self._is_verified_address(to), Errors::RECIPIENT_NOT_VERIFIED);
+            self._assert_compliance_call(from, to, amount, true);
+            
+            // Moving tokens to their holder would only unfreeze them
+            assert(from != to, Errors::SAME_ADDRESS);
+            
+            // Use the ERC20 internal transfer function but check for frozen status first
+            assert(!self.frozen_addresses.read(from), Errors::SENDER_FROZEN);
+            assert(!self.frozen_addresses.read(to), Errors::RECIPIENT_FROZEN);
+            
+            // Ensure contract is not paused using OpenZeppelin's pausable component
+            self.pausable.assert_not_paused();
+            self._assert_movements_not_paused();
+            
+            // Forced transfers may move frozen tokens, which are unfrozen first
+            self._unfreeze_for_forced_move(from, amount);
+            
+            // Use ERC20 internal transfer method to bypass allowance checks
+            // This is a forced transfer, so we don't need to check allowances
+            self.erc20._transfer(from, to, amount);
+            
+            // Tells agent-driven moves apart from the holder's own transfers
+            self.emit(ForcedTransfer { agent: get_caller_address(), from, to, amount });
+        }
+        
+        fn _assert_transfers_not_paused(self: @ContractState) {
+            self.pausable.assert_not_paused();
+            let pause_scopes = self.pause_scopes_map.read('pause_scopes');
+            assert(pause_scopes & (PAUSE_TRANSFERS | PAUSE_ALL_MOVEMENTS) == 0, Errors::TRANSFERS_PAUSED);
+        }
+        
+        fn _assert_movements_not_paused(self: @ContractState) {
+            let pause_scopes = self.pause_scopes_map.read('pause_scopes');
+            assert(pause_scopes & PAUSE_ALL_MOVEMENTS == 0, Errors::TOKEN_MOVEMENTS_PAUSED);
+        }
+        
+        fn _assert_agent_permission(self: @ContractState, agent: ContractAddress, permission: u8) {
+            assert(self.agents.read(agent), Errors::ONLY_AGENTS_ALLOWED);
+            if self.agent_restricted.read(agent) {
+                assert(self.agent_permissions.read(agent) & permission != 0, Errors::AGENT_LACKS_PERMISSION);
+            }
+        }
+        
+        fn _use_mint_allowance(ref self: ContractState, agent: ContractAddress, amount: u256) {
+            if !self.mint_allowance_capped.read(agent) {
+                return;
+            }
+            
+            let expiry = self.mint_allowance_expiries.read(agent);
+            assert(expiry == 0 || starknet::get_block_timestamp() < expiry, Errors::MINT_ALLOWANCE_EXPIRED);
+            
+            let allowance = self.mint_allowances.read(agent);
+            assert(allowance >= amount, Errors::MINT_ALLOWANCE_EXCEEDED);
+            self.mint_allowances.write(agent, allowance - amount);
+        }
+        
+        fn _emit_token_information(ref self: ContractState) {
+            self.emit(UpdatedTokenInformation {
+                name: self.token_information_map.read('name'),
+                symbol: self.token_information_map.read('symbol'),
+                decimals: 18,
+                version: TOKEN_VERSION,
+                onchain_id: self.token_information_map.read('onchain_id'),
+            });
+        }
+        
+        fn _set_address_frozen(
+            ref self: ContractState,
+            target_address: ContractAddress,
+            frozen: bool,
+            agent: ContractAddress,
+            reason: felt252
+        ) {
+            self.frozen_addresses.write(target_address, frozen);
+            
+            if frozen {
+                self.emit(Frozen { address: target_address });
+            } else {
+                self.emit(Unfrozen { address: target_address });
+            }
+            self._record_freeze_action(target_address, agent, reason);
+        }
+        
+        fn _record_freeze_action(ref self: ContractState, address: ContractAddress, agent: ContractAddress, reason: felt252) {
+            let timestamp = starknet::get_block_timestamp();
+            self.freeze_reasons.write(address, reason);
+            self.freeze_agents.write(address, agent);
+            self.freeze_timestamps.write(address, timestamp);
+            
+            self.emit(FreezeActionRecorded { address, agent, reason, timestamp });
+        }
+        
+        fn _gcd(self: @ContractState, a: u256, b: u256) -> u256 {
+            let mut a = a;
+            let mut b = b;
+            loop {
+                if b == 0 {
+                    break;
+                }
+                let remainder = a % b;
+                a = b;
+                b = remainder;
+            };
+            a
+        }
+        
+        fn _assert_valid_snapshot_id(self: @ContractState, snapshot_id: u64) {
+            assert(snapshot_id > 0, Errors::INVALID_SNAPSHOT_ID);
+            assert(snapshot_id <= self.snapshot_id_map.read('snapshot_id'), Errors::SNAPSHOT_DOES_NOT_EXIST);
+        }
+        
+        // Index of the first entry recorded at or after the snapshot, the entry count if none.
+        // Entries are kept in id order, so a binary search finds it.
+        fn _account_snapshot_index(self: @ContractState, account: ContractAddress, snapshot_id: u64) -> u32 {
+            let mut low: u32 = 0;
+            let mut high = self.account_snapshot_counts.read(account);
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if self.account_snapshot_ids.read((account, middle)) < snapshot_id {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            };
+            low
+        }
+        
+        fn _supply_snapshot_index(self: @ContractState, snapshot_id: u64) -> u32 {
+            let mut low: u32 = 0;
+            let mut high = self.supply_snapshot_count_map.read('supply_snapshot_count');
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if self.supply_snapshot_ids.read(middle) < snapshot_id {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            };
+            low
+        }
+        
+        fn _update_account_snapshot(ref self: ContractState, account: ContractAddress) {
+            let current_id = self.snapshot_id_map.read('snapshot_id');
+            if current_id == 0 {
+                return;
+            }
+            
+            // Only the first change after a snapshot is recorded
+            let count = self.account_snapshot_counts.read(account);
+            if count > 0 && self.account_snapshot_ids.read((account, count - 1)) >= current_id {
+                return;
+            }
+            
+            self.account_snapshot_ids.write((account, count), current_id);
+            self.account_snapshot_values.write((account, count), self.erc20.balance_of(account));
+            self.account_snapshot_counts.write(account, count + 1);
+        }
+        
+        fn _update_holders(ref self: ContractState, from: ContractAddress, recipient: ContractAddress) {
+            // Accounts join the holder set on their first credit and leave it when emptied
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            if from != zero_address && self.erc20.balance_of(from) == 0 {
+                self._remove_holder(from);
+            }
+            if recipient != zero_address && self.erc20.balance_of(recipient) > 0 {
+                self._add_holder(recipient);
+            }
+        }
+        
+        fn _add_holder(ref self: ContractState, account: ContractAddress) {
+            if self.holder_indices.read(account) != 0 {
+                return;
+            }
+            
+            let count = self.holder_count_map.read('holder_count');
+            self.holders.write(count, account);
+            self.holder_indices.write(account, count + 1);
+            self.holder_count_map.write('holder_count', count + 1);
+        }
+        
+        fn _remove_holder(ref self: ContractState, account: ContractAddress) {
+            let index = self.holder_indices.read(account);
+            if index == 0 {
+                return;
+            }
+            
+            // The last holder takes the removed one's place
+            let last = self.holder_count_map.read('holder_count') - 1;
+            if index - 1 != last {
+                let last_holder = self.holders.read(last);
+                self.holders.write(index - 1, last_holder);
+                self.holder_indices.write(last_holder, index);
+            }
+            
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            self.holders.write(last, zero_address);
+            self.holder_indices.write(account, 0);
+            self.holder_count_map.write('holder_count', last);
+        }
+        
+        fn _update_total_supply_snapshot(ref self: ContractState) {
+            let current_id = self.snapshot_id_map.read('snapshot_id');
+            if current_id == 0 {
+                return;
+            }
+            
+            let count = self.supply_snapshot_count_map.read('supply_snapshot_count');
+            if count > 0 && self.supply_snapshot_ids.read(count - 1) >= current_id {
+                return;
+            }
+            
+            self.supply_snapshot_ids.write(count, current_id);
+            self.supply_snapshot_values.write(count, self.erc20.total_supply());
+            self.supply_snapshot_count_map.write('supply_snapshot_count', count + 1);
+        }
+        
+        fn _checked_add(self: @ContractState, a: u256, b: u256) -> u256 {
+            // Reverts with a named error instead of the core 'u256_add Overflow' panic
+            match a.checked_add(b) {
+                Option::Some(sum) => sum,
+                Option::None => core::panic_with_felt252(Errors::OVERFLOW),
+            }
+        }
+        
+        fn _checked_mul(self: @ContractState, a: u256, b: u256) -> u256 {
+            match a.checked_mul(b) {
+                Option::Some(product) => product,
+                Option::None => core::panic_with_felt252(Errors::OVERFLOW),
+            }
+        }
+        
+        fn _free_balance(self: @ContractState, address: ContractAddress) -> u256 {
+            // Plain transfers spend the default partition, minus its frozen and held tokens
+            let balance = self.erc20.balance_of(address);
+            let locked = self._checked_add(self.frozen_tokens.read(address), self.partitioned_balances.read(address));
+            let locked = self._checked_add(locked, self.total_held_tokens.read(address));
+            if balance > locked {
+                balance - locked
+            } else {
+                0
+            }
+        }
+        
+        fn _default_partition_balance(self: @ContractState, address: ContractAddress) -> u256 {
+            // Saturates so a broken invariant cannot lock every later debit of the holder
+            let balance = self.erc20.balance_of(address);
+            let partitioned = self.partitioned_balances.read(address);
+            if partitioned >= balance { 0 } else { balance - partitioned }
+        }
+        
+        fn _assign_partition(ref self: ContractState, address: ContractAddress, partition: felt252, amount: u256) {
+            if self.partition_indices.read((address, partition)) == 0 {
+                let partition_count = self.partition_counts.read(address);
+                self.holder_partitions.write((address, partition_count), partition);
+                self.partition_indices.write((address, partition), partition_count + 1);
+                self.partition_counts.write(address, partition_count + 1);
+            }
+            
+            let partition_balance = self.partition_balances.read((address, partition));
+            self.partition_balances.write((address, partition), self._checked_add(partition_balance, amount));
+            let partitioned = self.partitioned_balances.read(address);
+            self.partitioned_balances.write(address, self._checked_add(partitioned, amount));
+        }
+        
+        fn _unassign_partition(ref self: ContractState, address: ContractAddress, partition: felt252, amount: u256) {
+            let partition_balance = self.partition_balances.read((address, partition));
+            assert(partition_balance >= amount, Errors::INSUFFICIENT_PARTITION_BALANCE);
+            
+            self.partition_balances.write((address, partition), partition_balance - amount);
+            let partitioned = self.partitioned_balances.read(address);
+            self.partitioned_balances.write(address, partitioned - amount);
+            
+            // Empty partitions leave the list, the last one taking their slot
+            if partition_balance == amount {
+                let index = self.partition_indices.read((address, partition));
+                let last_index = self.partition_counts.read(address) - 1;
+                if index - 1 != last_index {
+                    let last_partition = self.holder_partitions.read((address, last_index));
+                    self.holder_partitions.write((address, index - 1), last_partition);
+                    self.partition_indices.write((address, last_partition), index);
+                }
+                
+                self.holder_partitions.write((address, last_index), 0);
+                self.partition_indices.write((address, partition), 0);
+                self.partition_counts.write(address, last_index);
+            }
+        }
+        
+        fn _release_partitions(ref self: ContractState, address: ContractAddress, amount: u256) {
+            // Move tokens back to the default partition until it covers the amount
+            let default_balance = self._default_partition_balance(address);
+            if amount <= default_balance {
+                return;
+            }
+            
+            let mut shortfall = amount - default_balance;
+            loop {
+                let partition_count = self.partition_counts.read(address);
+                if shortfall == 0 || partition_count == 0 {
+                    break;
+                }
+                
+                let partition = self.holder_partitions.read((address, partition_count - 1));
+                let partition_balance = self.partition_balances.read((address, partition));
+                let released = if partition_balance > shortfall { shortfall } else { partition_balance };
+                self._unassign_partition(address, partition, released);
+                shortfall -= released;
+                
+                self.emit(PartitionReassigned {
+                    account: address,
+                    from_partition: partition,
+                    to_partition: DEFAULT_PARTITION,
+                    amount: released,
+                });
+            };
+        }
+        
+        fn _unfreeze_for_forced_move(ref self: ContractState, address: ContractAddress, amount: u256) {
+            // Unfreeze only what the unfrozen balance does not cover
+            let balance = self.erc20.balance_of(address);
+            let frozen = self.frozen_tokens.read(address);
+            let unfrozen_balance = if balance > frozen { balance - frozen } else { 0 };
+            if amount <= unfrozen_balance {
+                return;
+            }
+            
+            let to_unfreeze = amount - unfrozen_balance;
+            let to_unfreeze = if to_unfreeze > frozen { frozen } else { to_unfreeze };
+            self.frozen_tokens.write(address, frozen - to_unfreeze);
+            self.emit(TokensUnfrozen { address, amount: to_unfreeze });
+        }
+        
+        fn _notify_compliance(self: @ContractState, from: ContractAddress, to: ContractAddress, amount: u256) {
+            let compliance_contract = self.compliance_map.read('compliance');
+            let zero_address: ContractAddress = 0.try_into().unwrap();
+            let amount_low = amount.low;
+            let amount_high = amount.high;
+            
+            // Mints come from and burns go to the zero address
+            if from == zero_address {
+                let calldata = array![to.into(), amount_low.into(), amount_high.into()];
+                call_contract_syscall(compliance_contract, selector!("created"), calldata.span()).unwrap();
+            } else if to == zero_address {
+                let calldata = array![from.into(), amount_low.into(), amount_high.into()];
+                call_contract_syscall(compliance_contract, selector!("destroyed"), calldata.span()).unwrap();
+            } else {
+                let calldata = array![from.into(), to.into(), amount_low.into(), amount_high.into()];
+                call_contract_syscall(compliance_contract, selector!("transferred"), calldata.span()).unwrap();
             }
         }
         
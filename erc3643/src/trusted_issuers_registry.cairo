@@ -1,7 +1,9 @@
 // Simplified version without using OwnableComponent
 // We'll implement our own ownership control
+use openzeppelin::upgrades::UpgradeableComponent;
 use starknet::{
     ContractAddress, 
+    ClassHash,
     get_caller_address,
     storage::StorageMapReadAccess,
     storage::StorageMapWriteAccess,
@@ -21,24 +23,37 @@ pub trait ITrustedIssuersRegistry<TContractState> {
     fn get_issuer_claim_topics(self: @TContractState, issuer: felt252) -> Array<felt252>;
     fn is_trusted_issuer(self: @TContractState, issuer: felt252) -> bool;
     fn has_claim_topic(self: @TContractState, issuer: felt252, claim_topic: felt252) -> bool;
+    fn get_trusted_issuers_for_claim_topic(self: @TContractState, claim_topic: felt252) -> Array<felt252>;
+    fn get_trusted_issuers_epoch(self: @TContractState) -> u64;
     fn transfer_ownership(ref self: TContractState, new_owner: ContractAddress) -> bool;
     fn owner(self: @TContractState) -> ContractAddress;
+    
+    // Upgrade and storage versioning
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash) -> bool;
+    fn migrate(ref self: TContractState) -> bool;
+    fn storage_version(self: @TContractState) -> u8;
 }
 
 #[starknet::contract]
 pub mod TrustedIssuersRegistry {
     use super::*;
     
-    // No component declarations - we'll implement ownership directly
+    // No ownership component - we'll implement ownership directly
+    component!(path: UpgradeableComponent, storage: upgradeable, event: UpgradeableEvent);
+    
+    impl UpgradeableInternalImpl = UpgradeableComponent::InternalImpl<ContractState>;
     
     // Events
     #[event]
     #[derive(Drop, starknet::Event)]
     enum Event {
+        #[flat]
+        UpgradeableEvent: UpgradeableComponent::Event,
         OwnershipTransferred: OwnershipTransferred,
         TrustedIssuerAdded: TrustedIssuerAdded,
         TrustedIssuerRemoved: TrustedIssuerRemoved,
         ClaimTopicsUpdated: ClaimTopicsUpdated,
+        StorageMigrated: StorageMigrated,
     }
     
     #[derive(Drop, starknet::Event)]
@@ -62,8 +77,18 @@ pub mod TrustedIssuersRegistry {
         issuer: felt252,
     }
     
+    #[derive(Drop, starknet::Event)]
+    struct StorageMigrated {
+        from_version: u8,
+        to_version: u8,
+    }
+    
     #[storage]
     struct Storage {
+        // Component storage
+        #[substorage(v0)]
+        upgradeable: UpgradeableComponent::Storage,
+        
         // Owner storage - using a map with a fixed key for consistency
         _owner_map: Map<felt252, ContractAddress>,  // Map to store the owner at key "owner"
         
@@ -75,8 +100,25 @@ pub mod TrustedIssuersRegistry {
         // Issuer claim topics storage
         issuer_claim_topic_counts: Map<felt252, u32>,  // Number of claim topics for an issuer
         issuer_claim_topics: Map<(felt252, u32), felt252>,  // Issuer + index to claim topic mapping
+        
+        // Reverse index: issuers trusted for each claim topic
+        topic_issuer_counts: Map<felt252, u32>,  // Number of issuers for a claim topic
+        topic_issuers: Map<(felt252, u32), felt252>,  // Claim topic + index to issuer mapping
+        topic_issuer_indexes: Map<(felt252, felt252), u32>,  // (claim topic, issuer) to 1-based index
+        
+        // Bumped on every issuer change, so registries know when cached verifications are stale
+        epoch_map: Map<felt252, u64>,  // Using 'epoch' as key
+        
+        // Layout version of the storage above, bumped by `migrate` after an upgrade
+        storage_version_map: Map<felt252, u8>,  // Using 'version' as key
     }
     
+    // Version 2 indexes the issuers of each claim topic, built from the issuer topics
+    const STORAGE_VERSION: u8 = 2;
+    
+    // Maximum number of claim topics a single issuer can be trusted for
+    const MAX_CLAIM_TOPICS: u32 = 15;
+    
     #[constructor]
     fn constructor(ref self: ContractState, initial_owner: ContractAddress) {
         // Initialize owner directly
@@ -93,6 +135,9 @@ pub mod TrustedIssuersRegistry {
         
         // Initialize counter using a Map entry for simplicity
         self.trusted_issuer_counter_map.write(0, 0);
+        
+        // Fresh deployments start at the current storage layout
+        self.storage_version_map.write('version', STORAGE_VERSION);
     }
     
     #[abi(embed_v0)]
@@ -160,10 +205,12 @@ pub mod TrustedIssuersRegistry {
             self.trusted_issuer_indexes.write(issuer, 0);
             
             // Clear claim topics for this issuer
+            self._unindex_issuer_claim_topics(issuer);
             self.issuer_claim_topic_counts.write(issuer, 0);
             
             // Decrement issuer count
             self.set_trusted_issuer_count(trusted_issuer_count - 1);
+            self._bump_epoch();
             
             // Emit event
             self.emit(TrustedIssuerRemoved { issuer });
@@ -240,29 +287,55 @@ pub mod TrustedIssuersRegistry {
         }
         
         fn has_claim_topic(self: @ContractState, issuer: felt252, claim_topic: felt252) -> bool {
-            // Check if issuer exists
-            if !self.is_trusted_issuer(issuer) {
-                return false;
+            // Until `migrate` builds the per-topic index after an upgrade, scan the issuer topics
+            if self.storage_version_map.read('version') < 2 {
+                return self._scan_claim_topic(issuer, claim_topic);
             }
             
-            // Get claim topics count
-            let claim_topic_count = self.issuer_claim_topic_counts.read(issuer);
+            // The per-topic index only holds trusted issuers
+            self.topic_issuer_indexes.read((claim_topic, issuer)) != 0
+        }
+        
+        fn get_trusted_issuers_for_claim_topic(self: @ContractState, claim_topic: felt252) -> Array<felt252> {
+            let mut issuers = ArrayTrait::<felt252>::new();
+            
+            // Until `migrate` builds the per-topic index after an upgrade, scan every issuer
+            if self.storage_version_map.read('version') < 2 {
+                let trusted_issuer_count = self.get_trusted_issuer_count();
+                let mut i: u32 = 0;
+                loop {
+                    if i >= trusted_issuer_count {
+                        break;
+                    }
+                    
+                    let issuer = self.trusted_issuers_by_index.read(i);
+                    if self._scan_claim_topic(issuer, claim_topic) {
+                        issuers.append(issuer);
+                    }
+                    
+                    i += 1;
+                };
+                return issuers;
+            }
+            
+            let issuer_count = self.topic_issuer_counts.read(claim_topic);
             
-            // Check if claim topic exists
             let mut i: u32 = 0;
             loop {
-                if i >= claim_topic_count {
+                if i >= issuer_count {
                     break;
                 }
                 
-                if self.issuer_claim_topics.read((issuer, i)) == claim_topic {
-                    return true;
-                }
+                issuers.append(self.topic_issuers.read((claim_topic, i)));
                 
                 i += 1;
             };
             
-            false
+            issuers
+        }
+        
+        fn get_trusted_issuers_epoch(self: @ContractState) -> u64 {
+            self.epoch_map.read('epoch')
         }
         
         fn transfer_ownership(ref self: ContractState, new_owner: ContractAddress) -> bool {
@@ -275,6 +348,57 @@ pub mod TrustedIssuersRegistry {
             // Use our internal implementation explicitly
             InternalFunctions::owner(self)
         }
+        
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) -> bool {
+            // Only owner can upgrade the implementation
+            self.assert_only_owner();
+            
+            self.upgradeable.upgrade(new_class_hash);
+            true
+        }
+        
+        fn migrate(ref self: ContractState) -> bool {
+            // Only owner can migrate storage
+            self.assert_only_owner();
+            
+            // Deployments made before versioning existed read as version 0
+            let from_version = self.storage_version_map.read('version');
+            assert(from_version < STORAGE_VERSION, 'Storage already migrated');
+            
+            // Version 2 added the per-topic issuer index, build it from the issuer topics
+            if from_version < 2 {
+                let trusted_issuer_count = self.get_trusted_issuer_count();
+                let mut i: u32 = 0;
+                loop {
+                    if i >= trusted_issuer_count {
+                        break;
+                    }
+                    
+                    let issuer = self.trusted_issuers_by_index.read(i);
+                    let claim_topic_count = self.issuer_claim_topic_counts.read(issuer);
+                    let mut j: u32 = 0;
+                    loop {
+                        if j >= claim_topic_count {
+                            break;
+                        }
+                        
+                        self._index_topic_issuer(self.issuer_claim_topics.read((issuer, j)), issuer);
+                        
+                        j += 1;
+                    };
+                    
+                    i += 1;
+                };
+            }
+            
+            self.storage_version_map.write('version', STORAGE_VERSION);
+            self.emit(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            true
+        }
+        
+        fn storage_version(self: @ContractState) -> u8 {
+            self.storage_version_map.read('version')
+        }
     }
     
     // Internal functions
@@ -319,7 +443,10 @@ pub mod TrustedIssuersRegistry {
         }
         
         fn _update_issuer_claim_topics(ref self: ContractState, issuer: felt252, claim_topics: Array<felt252>) {
+            self._assert_valid_claim_topics(@claim_topics);
+            
             // Clear existing claim topics
+            self._unindex_issuer_claim_topics(issuer);
             self.issuer_claim_topic_counts.write(issuer, 0);
             
             // Add new claim topics
@@ -335,6 +462,7 @@ pub mod TrustedIssuersRegistry {
                 let topic = *claim_topics.at(i);
                 let index: u32 = i.try_into().unwrap();
                 self.issuer_claim_topics.write((issuer, index), topic);
+                self._index_topic_issuer(topic, issuer);
                 
                 i += 1;
             };
@@ -342,6 +470,108 @@ pub mod TrustedIssuersRegistry {
             // Update the count
             let topic_count: u32 = len.try_into().unwrap();
             self.issuer_claim_topic_counts.write(issuer, topic_count);
+            self._bump_epoch();
+        }
+        
+        fn _bump_epoch(ref self: ContractState) {
+            self.epoch_map.write('epoch', self.epoch_map.read('epoch') + 1);
+        }
+        
+        fn _assert_valid_claim_topics(self: @ContractState, claim_topics: @Array<felt252>) {
+            let len = claim_topics.len();
+            assert(len > 0, 'Claim topics empty');
+            assert(len <= MAX_CLAIM_TOPICS, 'Too many claim topics');
+            
+            // Each topic can only appear once
+            let mut i: u32 = 0;
+            loop {
+                if i >= len {
+                    break;
+                }
+                
+                let topic = *claim_topics.at(i);
+                let mut j: u32 = i + 1;
+                loop {
+                    if j >= len {
+                        break;
+                    }
+                    
+                    assert(*claim_topics.at(j) != topic, 'Duplicate claim topic');
+                    
+                    j += 1;
+                };
+                
+                i += 1;
+            };
+        }
+        
+        fn _scan_claim_topic(self: @ContractState, issuer: felt252, claim_topic: felt252) -> bool {
+            // Check if issuer exists
+            if !self.is_trusted_issuer(issuer) {
+                return false;
+            }
+            
+            // Get claim topics count
+            let claim_topic_count = self.issuer_claim_topic_counts.read(issuer);
+            
+            // Check if claim topic exists
+            let mut i: u32 = 0;
+            loop {
+                if i >= claim_topic_count {
+                    break false;
+                }
+                
+                if self.issuer_claim_topics.read((issuer, i)) == claim_topic {
+                    break true;
+                }
+                
+                i += 1;
+            }
+        }
+        
+        fn _index_topic_issuer(ref self: ContractState, claim_topic: felt252, issuer: felt252) {
+            // Already listed for this topic
+            if self.topic_issuer_indexes.read((claim_topic, issuer)) != 0 {
+                return;
+            }
+            
+            let issuer_count = self.topic_issuer_counts.read(claim_topic);
+            self.topic_issuers.write((claim_topic, issuer_count), issuer);
+            self.topic_issuer_indexes.write((claim_topic, issuer), issuer_count + 1); // +1 to differentiate from 0
+            self.topic_issuer_counts.write(claim_topic, issuer_count + 1);
+        }
+        
+        fn _unindex_topic_issuer(ref self: ContractState, claim_topic: felt252, issuer: felt252) {
+            let index_1_based = self.topic_issuer_indexes.read((claim_topic, issuer));
+            if index_1_based == 0 {
+                return;
+            }
+            
+            // Move the last issuer of the topic into the freed slot
+            let index = index_1_based - 1;
+            let issuer_count = self.topic_issuer_counts.read(claim_topic);
+            if index < issuer_count - 1 {
+                let last_issuer = self.topic_issuers.read((claim_topic, issuer_count - 1));
+                self.topic_issuers.write((claim_topic, index), last_issuer);
+                self.topic_issuer_indexes.write((claim_topic, last_issuer), index + 1);
+            }
+            
+            self.topic_issuer_indexes.write((claim_topic, issuer), 0);
+            self.topic_issuer_counts.write(claim_topic, issuer_count - 1);
+        }
+        
+        fn _unindex_issuer_claim_topics(ref self: ContractState, issuer: felt252) {
+            let claim_topic_count = self.issuer_claim_topic_counts.read(issuer);
+            let mut i: u32 = 0;
+            loop {
+                if i >= claim_topic_count {
+                    break;
+                }
+                
+                self._unindex_topic_issuer(self.issuer_claim_topics.read((issuer, i)), issuer);
+                
+                i += 1;
+            };
         }
     }
 }
\ No newline at end of file
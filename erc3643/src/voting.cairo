@@ -0,0 +1,86 @@
+//! Voter Weight
+//!
+//! Voting weight of security token holders for on-chain governance of corporate
+//! resolutions. The weight of a voter is their token balance at the snapshot a proposal
+//! was created with, and only wallets verified in the token's identity registry vote.
+//! Voters (or anyone on their behalf) record their weight for a snapshot; governance
+//! contracts read the recorded weights, which cannot change afterwards, and the total
+//! supply at the snapshot as the maximum weight.
+
+use starknet::{
+    ContractAddress,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Voter Weight Interface
+#[starknet::interface]
+pub trait IVoterWeight<TContractState> {
+    fn record_voter_weight(ref self: TContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> u256;
+    fn get_voter_weight_record(self: @TContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> (bool, u256);
+    fn get_voter_weight(self: @TContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> u256;
+    fn get_max_voter_weight(self: @TContractState, token: ContractAddress, snapshot_id: u64) -> u256;
+}
+
+#[starknet::contract]
+pub mod VoterWeight {
+    use super::*;
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        VoterWeightRecorded: VoterWeightRecorded,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct VoterWeightRecorded {
+        #[key]
+        token: ContractAddress,
+        #[key]
+        snapshot_id: u64,
+        voter: ContractAddress,
+        weight: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        // Weights recorded per (token, snapshot id, voter)
+        recorded: Map<(ContractAddress, u64, ContractAddress), bool>,
+        voter_weights: Map<(ContractAddress, u64, ContractAddress), u256>,
+    }
+    
+    #[abi(embed_v0)]
+    impl VoterWeightImpl of super::IVoterWeight<ContractState> {
+        fn record_voter_weight(ref self: ContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> u256 {
+            assert(!self.recorded.read((token, snapshot_id, voter)), 'Weight already recorded');
+            
+            let weight = self.get_voter_weight(token, snapshot_id, voter);
+            self.recorded.write((token, snapshot_id, voter), true);
+            self.voter_weights.write((token, snapshot_id, voter), weight);
+            
+            self.emit(VoterWeightRecorded { token, snapshot_id, voter, weight });
+            weight
+        }
+        
+        fn get_voter_weight_record(self: @ContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> (bool, u256) {
+            (self.recorded.read((token, snapshot_id, voter)), self.voter_weights.read((token, snapshot_id, voter)))
+        }
+        
+        fn get_voter_weight(self: @ContractState, token: ContractAddress, snapshot_id: u64, voter: ContractAddress) -> u256 {
+            // Balance at the snapshot, for wallets still verified
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: token };
+            if !token_dispatcher.is_verified_address(voter) {
+                return 0;
+            }
+            token_dispatcher.balance_of_at(voter, snapshot_id)
+        }
+        
+        fn get_max_voter_weight(self: @ContractState, token: ContractAddress, snapshot_id: u64) -> u256 {
+            IERC3643TokenDispatcher { contract_address: token }.total_supply_at(snapshot_id)
+        }
+    }
+}
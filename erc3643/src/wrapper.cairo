@@ -0,0 +1,125 @@
+//! Wrapped token
+//!
+//! Plain ERC20 mirror of a security token, for wallets and tooling that only handle
+//! standard ERC20s. Verified holders wrap security tokens, which are moved into this
+//! contract and minted 1:1 as wrapped tokens; unwrapping burns the wrapped tokens and sends
+//! the security tokens back with a regular `transfer`, so the token re-runs its identity
+//! and compliance checks for the holder unwrapping.
+//!
+//! This contract must itself be a verified address of the token so it can receive
+//! security tokens.
+
+use openzeppelin::token::erc20::ERC20Component;
+use starknet::{
+    ContractAddress,
+    get_caller_address,
+    get_contract_address,
+    storage::StorageMapReadAccess,
+    storage::StorageMapWriteAccess,
+    storage::Map,
+};
+
+use crate::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+
+// Wrapper Interface
+#[starknet::interface]
+pub trait IWrapper<TContractState> {
+    fn wrap(ref self: TContractState, amount: u256) -> bool;
+    fn unwrap(ref self: TContractState, amount: u256) -> bool;
+    fn underlying(self: @TContractState) -> ContractAddress;
+}
+
+#[starknet::contract]
+pub mod Wrapper {
+    use super::*;
+    
+    component!(path: ERC20Component, storage: erc20, event: ERC20Event);
+    
+    // Wrapped tokens are plain ERC20s
+    #[abi(embed_v0)]
+    impl ERC20MixinImpl = ERC20Component::ERC20MixinImpl<ContractState>;
+    impl ERC20InternalImpl = ERC20Component::InternalImpl<ContractState>;
+    impl ERC20HooksImpl = openzeppelin::token::erc20::ERC20HooksEmptyImpl<ContractState>;
+    
+    // Same decimals as the security token
+    impl ERC20Config of ERC20Component::ImmutableConfig {
+        const DECIMALS: u8 = 18;
+    }
+    
+    // Events
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        ERC20Event: ERC20Component::Event,
+        Wrapped: Wrapped,
+        Unwrapped: Unwrapped,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct Wrapped {
+        #[key]
+        holder: ContractAddress,
+        amount: u256,
+    }
+    
+    #[derive(Drop, starknet::Event)]
+    struct Unwrapped {
+        #[key]
+        holder: ContractAddress,
+        amount: u256,
+    }
+    
+    #[storage]
+    struct Storage {
+        #[substorage(v0)]
+        erc20: ERC20Component::Storage,
+        
+        // Security token held in this contract
+        underlying_map: Map<felt252, ContractAddress>,  // Using 'underlying' as key
+    }
+    
+    #[constructor]
+    fn constructor(ref self: ContractState, name: ByteArray, symbol: ByteArray, underlying: ContractAddress) {
+        self.erc20.initializer(name, symbol);
+        self.underlying_map.write('underlying', underlying);
+    }
+    
+    #[abi(embed_v0)]
+    impl WrapperImpl of super::IWrapper<ContractState> {
+        fn wrap(ref self: ContractState, amount: u256) -> bool {
+            assert(amount > 0, 'Invalid amount');
+            
+            // Only verified holders can take tokens out of the permissioned token
+            let holder = get_caller_address();
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.underlying_map.read('underlying') };
+            assert(token_dispatcher.is_verified_address(holder), 'Holder not verified');
+            
+            // The holder allowed this contract to take the tokens
+            let received = token_dispatcher.transfer_from(holder, get_contract_address(), amount);
+            assert(received, 'Transfer failed');
+            self.erc20.mint(holder, amount);
+            
+            self.emit(Wrapped { holder, amount });
+            true
+        }
+        
+        fn unwrap(ref self: ContractState, amount: u256) -> bool {
+            assert(amount > 0, 'Invalid amount');
+            
+            // The return transfer runs the token's full identity and compliance checks
+            let holder = get_caller_address();
+            self.erc20.burn(holder, amount);
+            let token_dispatcher = IERC3643TokenDispatcher { contract_address: self.underlying_map.read('underlying') };
+            let sent = token_dispatcher.transfer(holder, amount);
+            assert(sent, 'Transfer failed');
+            
+            self.emit(Unwrapped { holder, amount });
+            true
+        }
+        
+        fn underlying(self: @ContractState) -> ContractAddress {
+            self.underlying_map.read('underlying')
+        }
+    }
+}
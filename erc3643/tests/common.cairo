@@ -0,0 +1,180 @@
+//! Deployment of a token suite for the behaviour tests
+//!
+//! `deploy_suite` wires identity storage, registries, compliance and token the
+//! way the factory does, with the owner as token and registry agent. Modules
+//! are deployed against the suite compliance with `deploy_module`.
+
+use starknet::ContractAddress;
+use snforge_std::{
+    declare, ContractClassTrait, DeclareResultTrait, start_cheat_caller_address, stop_cheat_caller_address,
+    store, map_entry_address
+};
+use erc3643::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+use erc3643::compliance::{IComplianceDispatcher, IComplianceDispatcherTrait};
+use erc3643::identity_registry::{IIdentityRegistryDispatcher, IIdentityRegistryDispatcherTrait};
+use erc3643::identity_storage::{IIdentityStorageDispatcher, IIdentityStorageDispatcherTrait};
+use erc3643::claim_topics_registry::IClaimTopicsRegistryDispatcher;
+use erc3643::trusted_issuers_registry::{ITrustedIssuersRegistryDispatcher, ITrustedIssuersRegistryDispatcherTrait};
+
+pub const NAME: felt252 = 'T-REX Token';
+pub const SYMBOL: felt252 = 'TREX';
+pub const COUNTRY_USA: felt252 = 840; // ISO code for USA
+pub const COUNTRY_FRANCE: felt252 = 250; // ISO code for France
+
+pub fn owner() -> ContractAddress {
+    'owner'.try_into().unwrap()
+}
+
+pub fn alice() -> ContractAddress {
+    'alice'.try_into().unwrap()
+}
+
+pub fn bob() -> ContractAddress {
+    'bob'.try_into().unwrap()
+}
+
+pub fn charlie() -> ContractAddress {
+    'charlie'.try_into().unwrap()
+}
+
+pub fn treasury() -> ContractAddress {
+    'treasury'.try_into().unwrap()
+}
+
+#[derive(Copy, Drop)]
+pub struct Suite {
+    pub token: IERC3643TokenDispatcher,
+    pub compliance: IComplianceDispatcher,
+    pub identity_registry: IIdentityRegistryDispatcher,
+    pub identity_storage: IIdentityStorageDispatcher,
+    pub claim_topics_registry: IClaimTopicsRegistryDispatcher,
+    pub trusted_issuers_registry: ITrustedIssuersRegistryDispatcher,
+}
+
+pub fn deploy(name: ByteArray, calldata: Array<felt252>) -> ContractAddress {
+    let contract = declare(name).unwrap().contract_class();
+    let (contract_address, _) = contract.deploy(@calldata).unwrap();
+    contract_address
+}
+
+pub fn deploy_suite() -> Suite {
+    let owner = owner();
+    
+    let identity_storage = deploy("IdentityStorage", array![owner.into()]);
+    let claim_topics_registry = deploy("ClaimTopicsRegistry", array![owner.into()]);
+    let trusted_issuers_registry = deploy("TrustedIssuersRegistry", array![owner.into()]);
+    let identity_registry = deploy(
+        "IdentityRegistry",
+        array![owner.into(), identity_storage.into(), claim_topics_registry.into(), trusted_issuers_registry.into()]
+    );
+    let compliance = deploy("Compliance", array![owner.into()]);
+    let token = deploy(
+        "ERC3643Token", array![NAME, SYMBOL, owner.into(), compliance.into(), identity_registry.into()]
+    );
+    
+    // Let the registry write identities and the compliance govern the token
+    start_cheat_caller_address(identity_storage, owner);
+    IIdentityStorageDispatcher { contract_address: identity_storage }.bind_identity_registry(identity_registry);
+    stop_cheat_caller_address(identity_storage);
+    
+    start_cheat_caller_address(compliance, owner);
+    IComplianceDispatcher { contract_address: compliance }.bind_token(token);
+    stop_cheat_caller_address(compliance);
+    
+    Suite {
+        token: IERC3643TokenDispatcher { contract_address: token },
+        compliance: IComplianceDispatcher { contract_address: compliance },
+        identity_registry: IIdentityRegistryDispatcher { contract_address: identity_registry },
+        identity_storage: IIdentityStorageDispatcher { contract_address: identity_storage },
+        claim_topics_registry: IClaimTopicsRegistryDispatcher { contract_address: claim_topics_registry },
+        trusted_issuers_registry: ITrustedIssuersRegistryDispatcher { contract_address: trusted_issuers_registry },
+    }
+}
+
+// Deploys a module whose constructor takes the owner, the compliance and `extra`
+pub fn deploy_module(suite: Suite, name: ByteArray, extra: Array<felt252>) -> ContractAddress {
+    let mut calldata = array![owner().into(), suite.compliance.contract_address.into()];
+    calldata.append_span(extra.span());
+    deploy(name, calldata)
+}
+
+pub fn add_rule(suite: Suite, module: ContractAddress) {
+    add_rule_with_exemption(suite, module, false);
+}
+
+pub fn add_rule_with_exemption(suite: Suite, module: ContractAddress, forced_transfer_exempt: bool) {
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.add_rule(module, forced_transfer_exempt);
+    stop_cheat_caller_address(suite.compliance.contract_address);
+}
+
+pub fn trust_issuer(suite: Suite, issuer: felt252, claim_topics: Array<felt252>) {
+    start_cheat_caller_address(suite.trusted_issuers_registry.contract_address, owner());
+    suite.trusted_issuers_registry.add_trusted_issuer(issuer, claim_topics);
+    stop_cheat_caller_address(suite.trusted_issuers_registry.contract_address);
+}
+
+pub fn register(suite: Suite, wallet: ContractAddress, identity: felt252, country: felt252) {
+    start_cheat_caller_address(suite.identity_registry.contract_address, owner());
+    suite.identity_registry.register_identity(wallet, identity, country);
+    stop_cheat_caller_address(suite.identity_registry.contract_address);
+}
+
+pub fn mint(suite: Suite, to: ContractAddress, amount: u256) {
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.mint(to, amount);
+    stop_cheat_caller_address(suite.token.contract_address);
+}
+
+pub fn transfer(suite: Suite, from: ContractAddress, to: ContractAddress, amount: u256) {
+    start_cheat_caller_address(suite.token.contract_address, from);
+    suite.token.transfer(to, amount);
+    stop_cheat_caller_address(suite.token.contract_address);
+}
+
+// Writes a storage map entry directly, e.g. to roll a contract back to an older layout.
+// Multi-felt values such as u256 (low, high) take one felt per storage slot.
+pub fn store_map_entry(contract: ContractAddress, map_selector: felt252, keys: Array<felt252>, value: Array<felt252>) {
+    store(contract, map_entry_address(map_selector, keys.span()), value.span());
+}
+
+// Simulates a deployment made with an older release, before `migrate` ran
+pub fn set_storage_version(contract: ContractAddress, version: u8) {
+    store_map_entry(contract, selector!("storage_version_map"), array!['version'], array![version.into()]);
+}
+
+// Account accepting the first message hash it is asked about and rejecting any other, which
+// stands for a holder who signed exactly one SNIP-12 message
+#[starknet::contract]
+pub mod SingleMessageSigner {
+    use starknet::storage::{StoragePointerReadAccess, StoragePointerWriteAccess};
+    
+    #[storage]
+    struct Storage {
+        signed_hash: felt252,
+    }
+    
+    #[external(v0)]
+    fn is_valid_signature(ref self: ContractState, hash: felt252, signature: Array<felt252>) -> felt252 {
+        if self.signed_hash.read() == 0 {
+            self.signed_hash.write(hash);
+        }
+        if self.signed_hash.read() == hash { starknet::VALIDATED } else { 0 }
+    }
+}
+
+// A holder account registered in each suite
+pub fn deploy_signer(suites: Array<Suite>) -> ContractAddress {
+    let signer = deploy("SingleMessageSigner", array![]);
+    let mut i: u32 = 0;
+    loop {
+        if i >= suites.len() {
+            break;
+        }
+        
+        register(*suites.at(i), signer, 'signer_id', COUNTRY_FRANCE);
+        
+        i += 1;
+    };
+    signer
+}
@@ -0,0 +1,21 @@
+// Shared deployment fixture
+mod common;
+
+// Library helpers
+mod test_country;
+
+// Behaviour tests against deployed suites
+mod test_access_control;
+mod test_agent_permissions;
+mod test_bridge;
+mod test_compliance;
+mod test_forced_transfer;
+mod test_identity_registry;
+mod test_initializers;
+mod test_migrations;
+mod test_modules;
+mod test_permit;
+mod test_settlement;
+mod test_transfer_fees;
+mod test_transfers;
+mod test_trusted_issuers_registry;
@@ -0,0 +1,85 @@
+use snforge_std::start_cheat_caller_address;
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::compliance::IComplianceDispatcherTrait;
+use erc3643::identity_registry::IIdentityRegistryDispatcherTrait;
+use erc3643::identity_storage::IIdentityStorageDispatcherTrait;
+use erc3643::claim_topics_registry::IClaimTopicsRegistryDispatcherTrait;
+use erc3643::trusted_issuers_registry::ITrustedIssuersRegistryDispatcherTrait;
+use erc3643::interfaces::icompliance_module::{IComplianceModuleDispatcher, IComplianceModuleDispatcherTrait};
+use crate::common::{deploy_suite, deploy_module, alice, bob, COUNTRY_FRANCE};
+
+#[test]
+#[should_panic(expected: 'Only agents allowed')]
+fn test_mint_by_non_agent() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.mint(alice(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_add_agent_by_non_owner() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.add_agent(alice());
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_add_rule_by_non_owner() {
+    let suite = deploy_suite();
+    let module = deploy_module(suite, "CountryRestrictModule", array![]);
+    
+    start_cheat_caller_address(suite.compliance.contract_address, alice());
+    suite.compliance.add_rule(module, false);
+}
+
+#[test]
+#[should_panic(expected: 'Only agents allowed')]
+fn test_register_identity_by_non_agent() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.identity_registry.contract_address, alice());
+    suite.identity_registry.register_identity(alice(), 'alice_id', COUNTRY_FRANCE);
+}
+
+#[test]
+#[should_panic(expected: 'Only registry can call')]
+fn test_identity_storage_write_bypassing_registry() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.identity_storage.contract_address, alice());
+    suite.identity_storage.register_identity(alice(), 'alice_id', COUNTRY_FRANCE);
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_add_claim_topic_by_non_owner() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.claim_topics_registry.contract_address, alice());
+    suite.claim_topics_registry.add_claim_topic(1);
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_add_trusted_issuer_by_non_owner() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.trusted_issuers_registry.contract_address, alice());
+    suite.trusted_issuers_registry.add_trusted_issuer('issuer', array![1]);
+}
+
+#[test]
+#[should_panic(expected: 'Only compliance can call')]
+fn test_module_hook_by_non_compliance() {
+    let suite = deploy_suite();
+    let module = IComplianceModuleDispatcher {
+        contract_address: deploy_module(suite, "CountryRestrictModule", array![])
+    };
+    
+    start_cheat_caller_address(module.contract_address, alice());
+    module.module_transfer_action(alice(), bob(), 100);
+}
@@ -0,0 +1,51 @@
+use starknet::ContractAddress;
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::token::{IERC3643TokenDispatcherTrait, PERMISSION_MINT, PERMISSION_SNAPSHOT};
+use crate::common::{deploy_suite, register, mint, owner, alice, COUNTRY_FRANCE, Suite};
+
+fn agent() -> ContractAddress {
+    'agent'.try_into().unwrap()
+}
+
+fn setup_restricted_agent(permissions: u8) -> Suite {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.add_agent(agent());
+    suite.token.set_agent_permissions(agent(), permissions);
+    stop_cheat_caller_address(suite.token.contract_address);
+    suite
+}
+
+#[test]
+fn test_snapshot_with_permission() {
+    let suite = setup_restricted_agent(PERMISSION_SNAPSHOT);
+    start_cheat_caller_address(suite.token.contract_address, agent());
+    assert(suite.token.snapshot() == 1, 'Snapshot taken');
+}
+
+#[test]
+#[should_panic(expected: 'Agent lacks permission')]
+fn test_snapshot_requires_permission() {
+    let suite = setup_restricted_agent(PERMISSION_MINT);
+    start_cheat_caller_address(suite.token.contract_address, agent());
+    suite.token.snapshot();
+}
+
+#[test]
+#[should_panic(expected: 'Agent lacks permission')]
+fn test_reassign_partition_requires_permission() {
+    let suite = setup_restricted_agent(PERMISSION_MINT);
+    start_cheat_caller_address(suite.token.contract_address, agent());
+    suite.token.reassign_partition(alice(), 0, 'restricted', 100);
+}
+
+#[test]
+#[should_panic(expected: 'Agent lacks permission')]
+fn test_recover_requires_permission() {
+    let suite = setup_restricted_agent(PERMISSION_SNAPSHOT);
+    start_cheat_caller_address(suite.token.contract_address, agent());
+    suite.token.recover(alice(), 100);
+}
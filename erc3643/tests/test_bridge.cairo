@@ -0,0 +1,48 @@
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address, start_cheat_block_timestamp_global};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::bridge::{IBridgeDispatcher, IBridgeDispatcherTrait};
+use crate::common::{Suite, deploy, deploy_suite, deploy_module, add_rule, register, mint, owner, alice, COUNTRY_FRANCE};
+
+const START: u64 = 1000;
+const L1_BRIDGE: felt252 = 0x1234;
+const L1_RECIPIENT: felt252 = 0x5678;
+
+// Alice holds 1000 tokens locked until START + 500, the bridge being a token agent
+fn setup_locked_holder() -> (Suite, IBridgeDispatcher) {
+    start_cheat_block_timestamp_global(START);
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    let lockup = deploy_module(suite, "LockupModule", array![(START + 500).into()]);
+    add_rule(suite, lockup);
+    mint(suite, alice(), 1000);
+    
+    let bridge = IBridgeDispatcher { contract_address: deploy("Bridge", array![suite.token.contract_address.into()]) };
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.add_agent(bridge.contract_address);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    start_cheat_caller_address(bridge.contract_address, owner());
+    bridge.set_l1_bridge(L1_BRIDGE.try_into().unwrap());
+    stop_cheat_caller_address(bridge.contract_address);
+    (suite, bridge)
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_lock_for_bridge_respects_lockup() {
+    let (_, bridge) = setup_locked_holder();
+    
+    start_cheat_caller_address(bridge.contract_address, alice());
+    bridge.lock_for_bridge(L1_RECIPIENT.try_into().unwrap(), 100);
+}
+
+#[test]
+fn test_lock_for_bridge_after_lockup() {
+    let (suite, bridge) = setup_locked_holder();
+    start_cheat_block_timestamp_global(START + 500);
+    
+    start_cheat_caller_address(bridge.contract_address, alice());
+    bridge.lock_for_bridge(L1_RECIPIENT.try_into().unwrap(), 100);
+    
+    assert(suite.token.balance_of(alice()) == 900, 'Bridged tokens burnt');
+}
@@ -0,0 +1,105 @@
+use starknet::ContractAddress;
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::compliance::IComplianceDispatcherTrait;
+use erc3643::token::{IERC3643TokenDispatcherTrait, TRANSFER_NOT_COMPLIANT};
+use erc3643::interfaces::icompliance_module::{IComplianceModuleDispatcher, IComplianceModuleDispatcherTrait};
+use erc3643::modules::max_holders::{IMaxHoldersModuleDispatcher, IMaxHoldersModuleDispatcherTrait};
+use erc3643::modules::transfer_allowlist::{ITransferAllowlistModuleDispatcher, ITransferAllowlistModuleDispatcherTrait};
+use crate::common::{
+    Suite, deploy_suite, deploy, deploy_module, add_rule, register, mint, owner, alice, bob, NAME, SYMBOL,
+    COUNTRY_FRANCE
+};
+
+fn deploy_second_token(suite: Suite) -> ContractAddress {
+    deploy(
+        "ERC3643Token",
+        array![
+            NAME,
+            SYMBOL,
+            owner().into(),
+            suite.compliance.contract_address.into(),
+            suite.identity_registry.contract_address.into()
+        ]
+    )
+}
+
+#[test]
+#[should_panic(expected: 'Compliance already bound')]
+fn test_compliance_governs_a_single_token() {
+    let suite = deploy_suite();
+    let second_token = deploy_second_token(suite);
+    
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.bind_token(second_token);
+}
+
+#[test]
+fn test_token_can_be_replaced_after_unbinding() {
+    let suite = deploy_suite();
+    let second_token = deploy_second_token(suite);
+    
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.unbind_token(suite.token.contract_address);
+    suite.compliance.bind_token(second_token);
+    stop_cheat_caller_address(suite.compliance.contract_address);
+    
+    assert(suite.compliance.get_token() == second_token, 'Second token not bound');
+    assert(!suite.compliance.is_token_bound(suite.token.contract_address), 'First token still bound');
+    assert(suite.compliance.get_bound_tokens().len() == 1, 'Single bound token');
+}
+
+#[test]
+fn test_failing_rule_is_reported() {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    let blackout_address = deploy_module(suite, "BlackoutModule", array![]);
+    let max_holders_address = deploy_module(suite, "MaxHoldersModule", array![1]);
+    add_rule(suite, blackout_address);
+    add_rule(suite, max_holders_address);
+    mint(suite, alice(), 1000);
+    
+    // Alice moving her whole position keeps a single holder
+    let (found, _, _) = suite.compliance.find_failing_rule(alice(), bob(), 1000, false);
+    assert(!found, 'No rule should fail');
+    
+    // Bob would be a second holder, rejected by the second rule
+    let (found, index, rule) = suite.compliance.find_failing_rule(alice(), bob(), 500, false);
+    assert(found, 'Failing rule not found');
+    assert(index == 1, 'Wrong rule index');
+    assert(rule == max_holders_address, 'Wrong rule reported');
+    
+    let (code, module, name) = suite.token.can_transfer_with_reason(alice(), bob(), 500);
+    assert(code == TRANSFER_NOT_COMPLIANT, 'Wrong status code');
+    assert(module == max_holders_address, 'Wrong module reported');
+    assert(name == 'MaxHoldersModule', 'Wrong module name');
+}
+
+#[test]
+fn test_configuration_hash_covers_module_parameters() {
+    let suite = deploy_suite();
+    let max_holders_address = deploy_module(suite, "MaxHoldersModule", array![10]);
+    let allowlist_address = deploy_module(suite, "TransferAllowlistModule", array![]);
+    add_rule(suite, max_holders_address);
+    add_rule(suite, allowlist_address);
+    let initial_hash = suite.compliance.get_configuration_hash();
+    
+    start_cheat_caller_address(max_holders_address, owner());
+    IMaxHoldersModuleDispatcher { contract_address: max_holders_address }.set_max_holders(20);
+    stop_cheat_caller_address(max_holders_address);
+    let raised_hash = suite.compliance.get_configuration_hash();
+    assert(raised_hash != initial_hash, 'Scalar change not hashed');
+    
+    // Parameters kept in maps are covered too
+    start_cheat_caller_address(allowlist_address, owner());
+    ITransferAllowlistModuleDispatcher { contract_address: allowlist_address }.allow_counterparty(alice());
+    stop_cheat_caller_address(allowlist_address);
+    assert(suite.compliance.get_configuration_hash() != raised_hash, 'Map change not hashed');
+    
+    // Restoring a scalar restores its part of the hash
+    start_cheat_caller_address(max_holders_address, owner());
+    IMaxHoldersModuleDispatcher { contract_address: max_holders_address }.set_max_holders(10);
+    stop_cheat_caller_address(max_holders_address);
+    let module_hash = IComplianceModuleDispatcher { contract_address: max_holders_address }.config_hash();
+    assert(module_hash == core::poseidon::poseidon_hash_span(array![10].span()), 'Hash of the current value');
+}
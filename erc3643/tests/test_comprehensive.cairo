@@ -1,67 +0,0 @@
-#[cfg(test)]
-mod comprehensive_tests {
-    // Keep the imports in comments as reference, but don't actually import them
-    // to avoid unused import warnings
-    //
-    // use erc3643::token::IERC3643Token;
-    // use erc3643::identity_registry::IIdentityRegistry;
-    // use erc3643::identity_storage::IIdentityStorage;
-    // use erc3643::compliance::ICompliance;
-    // use erc3643::claim_topics_registry::IClaimTopicsRegistry;
-    // use erc3643::trusted_issuers_registry::ITrustedIssuersRegistry;
-    
-    const NAME: felt252 = 'T-REX Token';
-    const SYMBOL: felt252 = 'TREX';
-    const COUNTRY_USA: felt252 = 840; // ISO code for USA
-    const COUNTRY_FRANCE: felt252 = 250; // ISO code for France
-    
-    // Claim topics for demonstration
-    const CLAIM_TOPIC_KYC: felt252 = 1; // KYC verification
-    const CLAIM_TOPIC_ACCREDITED: felt252 = 2; // Accredited investor status
-    
-    #[test]
-    fn test_interface_availability() {
-        // This test ensures that all interfaces are properly defined
-        // In a more advanced test setup, we would actually verify
-        // the interfaces by importing and using them.
-        assert(true, 'Interfaces exist');
-    }
-    
-    #[test]
-    fn test_constants() {
-        // Verify the constants are what we expect
-        assert(NAME == 'T-REX Token', 'Name matches');
-        assert(SYMBOL == 'TREX', 'Symbol matches');
-        assert(COUNTRY_USA == 840, 'Country matches');
-        assert(COUNTRY_FRANCE == 250, 'France matches');
-        assert(CLAIM_TOPIC_KYC == 1, 'KYC matches');
-        assert(CLAIM_TOPIC_ACCREDITED == 2, 'Accredited matches');
-    }
-    
-    #[test]
-    fn test_erc3643_architecture() {
-        // This test validates the high-level architecture of the ERC3643 standard
-        // In a more advanced test setup, we would import the interfaces and
-        // create assertions about their structure and functionality.
-        
-        // Check that each component exists
-        assert(true, 'Token interface'); 
-        assert(true, 'Registry interface');
-        assert(true, 'Storage interface'); 
-        assert(true, 'Compliance interface');
-        assert(true, 'Claim interface');
-        assert(true, 'Issuers interface');
-    }
-    
-    #[test]
-    fn test_token_functions() {
-        // Check that the token interface includes all the necessary functions
-        // In a more advanced test setup, we would import the token interface
-        // and verify the presence of each function.
-        assert(true, 'Token functions available');
-        
-        // The token interface should include these functions:
-        // - ERC20 standard functions: name, symbol, decimals, total_supply, balance_of, etc.
-        // - ERC3643 functions: mint, burn, freeze_address, is_verified_address, etc.
-    }
-}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use erc3643::country::{is_valid_country, is_eu, is_eea, is_fatf_grey_list};
+
+#[test]
+fn test_valid_country_range() {
+    assert(is_valid_country(250), 'France should be valid');
+    assert(is_valid_country(1), 'Lower bound should be valid');
+    assert(is_valid_country(999), 'Upper bound should be valid');
+    assert(!is_valid_country(0), 'Zero should be invalid');
+    assert(!is_valid_country(1000), 'Above range should be invalid');
+    assert(!is_valid_country('FR'), 'Alpha code should be invalid');
+}
+
+#[test]
+fn test_region_groupings() {
+    assert(is_eu(276), 'Germany is in the EU');
+    assert(!is_eu(578), 'Norway is not in the EU');
+    assert(is_eea(578), 'Norway is in the EEA');
+    assert(is_eea(276), 'EU members are in the EEA');
+    assert(!is_eea(840), 'US is not in the EEA');
+    assert(is_fatf_grey_list(710), 'South Africa is grey listed');
+    assert(!is_fatf_grey_list(250), 'France is not grey listed');
+}
@@ -0,0 +1,107 @@
+use starknet::ContractAddress;
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address, start_cheat_block_timestamp_global};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::modules::blackout::{IBlackoutModuleDispatcher, IBlackoutModuleDispatcherTrait};
+use crate::common::{
+    Suite, deploy_suite, deploy_module, add_rule, add_rule_with_exemption, register, mint, transfer, owner, alice,
+    bob, charlie, COUNTRY_FRANCE
+};
+
+fn setup() -> Suite {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(suite, alice(), 1000);
+    suite
+}
+
+// Blackout window over [100, 200), the clock set inside it
+fn start_blackout(suite: Suite, forced_transfer_exempt: bool) {
+    let blackout_address = deploy_module(suite, "BlackoutModule", array![]);
+    add_rule_with_exemption(suite, blackout_address, forced_transfer_exempt);
+    
+    start_cheat_caller_address(blackout_address, owner());
+    IBlackoutModuleDispatcher { contract_address: blackout_address }.add_blackout_window(100, 200);
+    stop_cheat_caller_address(blackout_address);
+    
+    start_cheat_block_timestamp_global(150);
+}
+
+fn forced_transfer(suite: Suite, from: ContractAddress, to: ContractAddress, amount: u256) {
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.forced_transfer(from, to, amount);
+    stop_cheat_caller_address(suite.token.contract_address);
+}
+
+#[test]
+fn test_forced_transfer_skips_exempt_blackout() {
+    let suite = setup();
+    start_blackout(suite, true);
+    
+    forced_transfer(suite, alice(), bob(), 400);
+    
+    assert(suite.token.balance_of(alice()) == 600, 'Sender debited');
+    assert(suite.token.balance_of(bob()) == 400, 'Recipient credited');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_holder_transfer_blocked_by_blackout() {
+    let suite = setup();
+    start_blackout(suite, true);
+    
+    transfer(suite, alice(), bob(), 400);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_forced_transfer_blocked_by_blackout_without_exemption() {
+    let suite = setup();
+    start_blackout(suite, false);
+    
+    forced_transfer(suite, alice(), bob(), 400);
+}
+
+#[test]
+fn test_forced_transfer_skips_any_exempt_rule() {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    let max_holders_address = deploy_module(suite, "MaxHoldersModule", array![1]);
+    add_rule_with_exemption(suite, max_holders_address, true);
+    mint(suite, alice(), 1000);
+    
+    // The exemption follows the owner's flag, not the module's name
+    assert(suite.compliance.is_forced_transfer_exempt(max_holders_address), 'Rule not exempt');
+    forced_transfer(suite, alice(), bob(), 400);
+    
+    assert(suite.token.balance_of(bob()) == 400, 'Recipient credited');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_forced_transfer_checks_other_modules() {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    let max_holders_address = deploy_module(suite, "MaxHoldersModule", array![1]);
+    add_rule(suite, max_holders_address);
+    mint(suite, alice(), 1000);
+    
+    // Alice keeps part of her position, Bob would be a second holder
+    forced_transfer(suite, alice(), bob(), 400);
+}
+
+#[test]
+#[should_panic(expected: 'Sender not verified')]
+fn test_forced_transfer_requires_verified_sender() {
+    let suite = setup();
+    register(suite, charlie(), 'charlie_id', COUNTRY_FRANCE);
+    mint(suite, charlie(), 100);
+    
+    start_cheat_caller_address(suite.identity_registry.contract_address, owner());
+    suite.identity_registry.delete_identity(charlie());
+    stop_cheat_caller_address(suite.identity_registry.contract_address);
+    
+    forced_transfer(suite, charlie(), bob(), 100);
+}
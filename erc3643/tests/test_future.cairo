@@ -1,58 +0,0 @@
-#[cfg(test)]
-mod future_tests {
-    #[test]
-    fn test_readme() {
-        // This test documents future test enhancements 
-        // that would be implemented with more advanced testing utilities
-        
-        // The following are examples of tests that would be beneficial in a production environment
-        
-        // Proposed ERC3643 Test Suite:
-        
-        // 1. Deployment and Initialization Tests:
-        //    - Test proper contract deployment with all parameters
-        //    - Test initialization of all components (token, registry, storage, etc.)
-        //    - Test initialization with invalid parameters
-        
-        // 2. Identity Registration Tests:
-        //    - Test identity registration for various addresses
-        //    - Test identity updates and modifications
-        //    - Test identity deletion
-        //    - Test identity verification
-        
-        // 3. Token Operation Tests:
-        //    - Test token minting to verified addresses
-        //    - Test token transfers between verified addresses
-        //    - Test token burning
-        //    - Test token recovery
-        //    - Test force-transfers by agents
-        
-        // 4. Compliance Tests:
-        //    - Test adding/removing compliance rules
-        //    - Test adding/removing compliance checks
-        //    - Test transfers with various compliance rules
-        //    - Test transfers with country restrictions
-        
-        // 5. Freezing Tests:
-        //    - Test freezing addresses
-        //    - Test unfreezing addresses
-        //    - Test transfers to/from frozen addresses
-        
-        // 6. Agent Tests:
-        //    - Test adding/removing agents
-        //    - Test agent operations (force transfer, freezing, etc.)
-        //    - Test operations by non-agents
-        
-        // 7. Integration Tests:
-        //    - Test full token lifecycle (issuance, trading, redemption)
-        //    - Test regulatory actions (freezing, recovery, etc.)
-        //    - Test compliance updates and effects
-        
-        // 8. Claim Topics and Trusted Issuers Tests:
-        //    - Test adding/removing claim topics
-        //    - Test adding/removing trusted issuers
-        //    - Test verification with various claim configurations
-        
-        assert(true, 'Future test plans');
-    }
-}
\ No newline at end of file
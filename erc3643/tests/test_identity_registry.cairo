@@ -0,0 +1,163 @@
+use starknet::{ContractAddress, EthAddress};
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::identity_registry::IIdentityRegistryDispatcherTrait;
+use erc3643::claim_topics_registry::IClaimTopicsRegistryDispatcherTrait;
+use erc3643::trusted_issuers_registry::ITrustedIssuersRegistryDispatcherTrait;
+use crate::common::{deploy_suite, register, trust_issuer, owner, alice, COUNTRY_FRANCE, Suite};
+
+const CLAIM_TOPIC_KYC: felt252 = 1;
+
+// secp256k1 test vector signed with private key
+// 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318 over the ONCHAINID claim
+// keccak256(abi.encode(0x5FbDB2315678afecb367f032d93F642f64180aa3, 1, "kyc:passed"))
+const ETH_ISSUER: felt252 = 0x2c7536e3605d9c16a7a3d7b1898e529396a65c23;
+const ONCHAIN_ID: felt252 = 0x5fbdb2315678afecb367f032d93f642f64180aa3;
+const ETH_CLAIM_DIGEST: u256 = 0x788ca58d4e0d1a2ef9c974cc64d2de3d908bd10fa654f5f12b7addf847b43e7b;
+const SIG_V: u32 = 28;
+const SIG_R: u256 = 0xbb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d;
+const SIG_S: u256 = 0x4f50d5325868ed23c8b52e23d8a6332ef3457c0d3c9fe9cd959357e583e08662;
+
+fn issuer() -> ContractAddress {
+    'issuer'.try_into().unwrap()
+}
+
+#[test]
+fn test_claim_changes_invalidate_signatures() {
+    let suite = deploy_suite();
+    let registry = suite.identity_registry;
+    let issuer: felt252 = issuer().into();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    trust_issuer(suite, issuer, array![CLAIM_TOPIC_KYC]);
+    
+    let signed_hash = registry.claim_hash('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok', issuer);
+    assert(registry.get_claim_nonce('alice_id', CLAIM_TOPIC_KYC, issuer) == 0, 'Fresh claim nonce');
+    
+    start_cheat_caller_address(registry.contract_address, issuer());
+    registry.add_claim('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok');
+    let added_hash = registry.claim_hash('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok', issuer);
+    registry.remove_claim('alice_id', CLAIM_TOPIC_KYC, issuer);
+    stop_cheat_caller_address(registry.contract_address);
+    
+    // The signature over the first hash cannot re-add the revoked claim
+    let replay_hash = registry.claim_hash('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok', issuer);
+    assert(registry.get_claim_nonce('alice_id', CLAIM_TOPIC_KYC, issuer) == 2, 'Nonce bumped twice');
+    assert(added_hash != signed_hash, 'Adding changes the hash');
+    assert(replay_hash != signed_hash, 'Removing changes the hash');
+    assert(replay_hash != added_hash, 'Each change is distinct');
+}
+
+#[test]
+fn test_claim_hash_is_bound_to_the_issuer() {
+    let suite = deploy_suite();
+    let registry = suite.identity_registry;
+    
+    let first = registry.claim_hash('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok', 'issuer_a');
+    let second = registry.claim_hash('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok', 'issuer_b');
+    assert(first != second, 'Hash ignores the issuer');
+}
+
+fn setup_eth_claim() -> Suite {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    trust_issuer(suite, ETH_ISSUER, array![CLAIM_TOPIC_KYC]);
+    
+    let onchain_id: EthAddress = ONCHAIN_ID.try_into().unwrap();
+    start_cheat_caller_address(suite.identity_registry.contract_address, owner());
+    suite.identity_registry.set_onchain_id('alice_id', onchain_id);
+    stop_cheat_caller_address(suite.identity_registry.contract_address);
+    suite
+}
+
+#[test]
+fn test_eth_claim_hash_matches_onchainid() {
+    let suite = setup_eth_claim();
+    let digest = suite.identity_registry.eth_claim_hash('alice_id', CLAIM_TOPIC_KYC, "kyc:passed");
+    assert(digest == ETH_CLAIM_DIGEST, 'ONCHAINID digest mismatch');
+}
+
+#[test]
+fn test_add_eth_signed_claim() {
+    let suite = setup_eth_claim();
+    let registry = suite.identity_registry;
+    
+    registry.add_eth_signed_claim('alice_id', CLAIM_TOPIC_KYC, "kyc:passed", ETH_ISSUER, SIG_V, SIG_R, SIG_S);
+    assert(registry.get_claim_nonce('alice_id', CLAIM_TOPIC_KYC, ETH_ISSUER) == 1, 'Claim added');
+    assert(!registry.is_eth_claim_revoked(ETH_CLAIM_DIGEST), 'Digest not revoked');
+}
+
+#[test]
+#[should_panic]
+fn test_add_eth_signed_claim_rejects_other_data() {
+    let suite = setup_eth_claim();
+    suite
+        .identity_registry
+        .add_eth_signed_claim('alice_id', CLAIM_TOPIC_KYC, "kyc:failed", ETH_ISSUER, SIG_V, SIG_R, SIG_S);
+}
+
+#[test]
+#[should_panic(expected: 'Claim signature revoked')]
+fn test_removed_eth_claim_cannot_be_replayed() {
+    let suite = setup_eth_claim();
+    let registry = suite.identity_registry;
+    registry.add_eth_signed_claim('alice_id', CLAIM_TOPIC_KYC, "kyc:passed", ETH_ISSUER, SIG_V, SIG_R, SIG_S);
+    
+    start_cheat_caller_address(registry.contract_address, owner());
+    registry.remove_claim('alice_id', CLAIM_TOPIC_KYC, ETH_ISSUER);
+    stop_cheat_caller_address(registry.contract_address);
+    assert(registry.is_eth_claim_revoked(ETH_CLAIM_DIGEST), 'Digest revoked');
+    
+    registry.add_eth_signed_claim('alice_id', CLAIM_TOPIC_KYC, "kyc:passed", ETH_ISSUER, SIG_V, SIG_R, SIG_S);
+}
+
+#[test]
+#[should_panic(expected: 'ONCHAINID not set')]
+fn test_eth_claim_requires_linked_onchain_id() {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    suite.identity_registry.eth_claim_hash('alice_id', CLAIM_TOPIC_KYC, "kyc:passed");
+}
+
+#[test]
+#[should_panic(expected: 'Registry updates paused')]
+fn test_rotate_wallet_respects_updates_pause() {
+    let suite = deploy_suite();
+    let registry = suite.identity_registry;
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    
+    start_cheat_caller_address(registry.contract_address, owner());
+    registry.set_updates_paused(true);
+    stop_cheat_caller_address(registry.contract_address);
+    
+    // The wallet owner is held to the pause like agents are
+    start_cheat_caller_address(registry.contract_address, alice());
+    registry.rotate_wallet(alice(), 'alice_new'.try_into().unwrap());
+}
+
+#[test]
+fn test_cached_verification_dropped_when_issuer_untrusted() {
+    let suite = deploy_suite();
+    let registry = suite.identity_registry;
+    let issuer: felt252 = issuer().into();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    trust_issuer(suite, issuer, array![CLAIM_TOPIC_KYC]);
+    
+    start_cheat_caller_address(suite.claim_topics_registry.contract_address, owner());
+    suite.claim_topics_registry.add_claim_topic(CLAIM_TOPIC_KYC);
+    stop_cheat_caller_address(suite.claim_topics_registry.contract_address);
+    
+    start_cheat_caller_address(registry.contract_address, issuer());
+    registry.add_claim('alice_id', CLAIM_TOPIC_KYC, 'kyc_ok');
+    stop_cheat_caller_address(registry.contract_address);
+    
+    start_cheat_caller_address(registry.contract_address, owner());
+    registry.set_verification_max_age(1000);
+    stop_cheat_caller_address(registry.contract_address);
+    assert(registry.refresh_verification('alice_id'), 'Verified before removal');
+    
+    // The issuer registry changes without the identity registry being told
+    start_cheat_caller_address(suite.trusted_issuers_registry.contract_address, owner());
+    suite.trusted_issuers_registry.remove_trusted_issuer(issuer);
+    stop_cheat_caller_address(suite.trusted_issuers_registry.contract_address);
+    
+    assert(!registry.is_verified_address(alice()), 'Stale cache used');
+}
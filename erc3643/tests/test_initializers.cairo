@@ -0,0 +1,74 @@
+use starknet::ContractAddress;
+use erc3643::components::erc3643::ERC3643Component;
+use erc3643::components::erc3643::ERC3643Component::InternalTrait as ERC3643InternalTrait;
+use erc3643::components::identity_registry::IdentityRegistryComponent;
+use erc3643::components::identity_registry::IdentityRegistryComponent::InternalTrait as IdentityRegistryInternalTrait;
+use crate::common::{NAME, SYMBOL, owner, alice};
+
+// Minimal contracts embedding each component, one per component since both keep an
+// `initialized_map` at the top level of the storage
+#[starknet::contract]
+mod ERC3643Mock {
+    use erc3643::components::erc3643::ERC3643Component;
+    
+    component!(path: ERC3643Component, storage: erc3643, event: ERC3643Event);
+    
+    #[storage]
+    struct Storage {
+        #[substorage(v0)]
+        erc3643: ERC3643Component::Storage,
+    }
+    
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        ERC3643Event: ERC3643Component::Event,
+    }
+}
+
+#[starknet::contract]
+mod IdentityRegistryMock {
+    use erc3643::components::identity_registry::IdentityRegistryComponent;
+    
+    component!(path: IdentityRegistryComponent, storage: identity_registry, event: IdentityRegistryEvent);
+    
+    #[storage]
+    struct Storage {
+        #[substorage(v0)]
+        identity_registry: IdentityRegistryComponent::Storage,
+    }
+    
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        IdentityRegistryEvent: IdentityRegistryComponent::Event,
+    }
+}
+
+fn contract(name: felt252) -> ContractAddress {
+    name.try_into().unwrap()
+}
+
+#[test]
+#[should_panic(expected: 'Already initialized')]
+fn test_erc3643_component_cannot_be_reinitialized() {
+    let mut state: ERC3643Component::ComponentState<ERC3643Mock::ContractState> =
+        ERC3643Component::component_state_for_testing();
+    state.initializer(NAME, SYMBOL, owner(), contract('compliance'), contract('registry'));
+    
+    // A second run would swap the registries and make the caller an agent
+    state.initializer(NAME, SYMBOL, alice(), contract('rogue_compliance'), contract('rogue_registry'));
+}
+
+#[test]
+#[should_panic(expected: 'Already initialized')]
+fn test_identity_registry_component_cannot_be_reinitialized() {
+    let mut state: IdentityRegistryComponent::ComponentState<IdentityRegistryMock::ContractState> =
+        IdentityRegistryComponent::component_state_for_testing();
+    state.initializer(owner(), contract('storage'), contract('topics'), contract('issuers'));
+    
+    // A second run would swap the registries and grant the agent role
+    state.initializer(alice(), contract('rogue_storage'), contract('topics'), contract('issuers'));
+}
@@ -0,0 +1,121 @@
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::compliance::IComplianceDispatcherTrait;
+use erc3643::identity_registry::IIdentityRegistryDispatcherTrait;
+use erc3643::identity_storage::IIdentityStorageDispatcherTrait;
+use erc3643::claim_topics_registry::IClaimTopicsRegistryDispatcherTrait;
+use crate::common::{deploy_suite, deploy_module, add_rule, owner, alice, store_map_entry, set_storage_version};
+
+#[test]
+fn test_identity_storage_migrates_single_registry() {
+    let suite = deploy_suite();
+    let storage = suite.identity_storage;
+    let registry = suite.identity_registry.contract_address;
+    
+    // A version 1 deployment only knows its registry through the single registry slot
+    set_storage_version(storage.contract_address, 1);
+    store_map_entry(storage.contract_address, selector!("registry_map"), array!['registry'], array![registry.into()]);
+    store_map_entry(storage.contract_address, selector!("registry_indices"), array![registry.into()], array![0]);
+    store_map_entry(storage.contract_address, selector!("registry_count_map"), array!['registry_count'], array![0]);
+    store_map_entry(storage.contract_address, selector!("registries"), array![0], array![0]);
+    assert(storage.get_bound_identity_registries().len() == 0, 'No registry bound');
+    
+    start_cheat_caller_address(storage.contract_address, owner());
+    storage.migrate();
+    stop_cheat_caller_address(storage.contract_address);
+    
+    assert(storage.storage_version() == 2, 'Migrated to version 2');
+    assert(storage.get_bound_identity_registries() == array![registry], 'Registry bound');
+}
+
+#[test]
+fn test_token_migration_sets_default_metadata() {
+    let suite = deploy_suite();
+    let token = suite.token;
+    
+    // Version 1 reported a fixed name and symbol and stored none
+    set_storage_version(token.contract_address, 1);
+    store_map_entry(token.contract_address, selector!("token_information_map"), array!['name'], array![0]);
+    store_map_entry(token.contract_address, selector!("token_information_map"), array!['symbol'], array![0]);
+    
+    start_cheat_caller_address(token.contract_address, owner());
+    token.migrate();
+    stop_cheat_caller_address(token.contract_address);
+    
+    assert(token.storage_version() == 2, 'Migrated to version 2');
+    assert(token.name() == 'Token', 'Default name stored');
+    assert(token.symbol() == 'TKN', 'Default symbol stored');
+}
+
+#[test]
+fn test_unversioned_contracts_migrate_to_current_version() {
+    let suite = deploy_suite();
+    set_storage_version(suite.compliance.contract_address, 0);
+    set_storage_version(suite.claim_topics_registry.contract_address, 0);
+    set_storage_version(suite.identity_registry.contract_address, 0);
+    
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.migrate();
+    stop_cheat_caller_address(suite.compliance.contract_address);
+    
+    start_cheat_caller_address(suite.claim_topics_registry.contract_address, owner());
+    suite.claim_topics_registry.migrate();
+    stop_cheat_caller_address(suite.claim_topics_registry.contract_address);
+    
+    start_cheat_caller_address(suite.identity_registry.contract_address, owner());
+    suite.identity_registry.migrate();
+    stop_cheat_caller_address(suite.identity_registry.contract_address);
+    
+    assert(suite.compliance.storage_version() == 2, 'Compliance migrated');
+    assert(suite.claim_topics_registry.storage_version() == 1, 'Claim topics migrated');
+    assert(suite.identity_registry.storage_version() == 1, 'Identity registry migrated');
+}
+
+#[test]
+fn test_compliance_migration_exempts_blackout_rules() {
+    let suite = deploy_suite();
+    let blackout = deploy_module(suite, "BlackoutModule", array![]);
+    let max_holders = deploy_module(suite, "MaxHoldersModule", array![10]);
+    add_rule(suite, blackout);
+    add_rule(suite, max_holders);
+    
+    // Version 1 let forced transfers skip blackout modules without recording it
+    set_storage_version(suite.compliance.contract_address, 1);
+    
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.migrate();
+    stop_cheat_caller_address(suite.compliance.contract_address);
+    
+    assert(suite.compliance.is_forced_transfer_exempt(blackout), 'Blackout exempt');
+    assert(!suite.compliance.is_forced_transfer_exempt(max_holders), 'Other rules still apply');
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_migrate_by_non_owner() {
+    let suite = deploy_suite();
+    set_storage_version(suite.identity_storage.contract_address, 1);
+    
+    start_cheat_caller_address(suite.identity_storage.contract_address, alice());
+    suite.identity_storage.migrate();
+}
+
+#[test]
+#[should_panic(expected: 'Storage already migrated')]
+fn test_token_migrate_twice() {
+    let suite = deploy_suite();
+    set_storage_version(suite.token.contract_address, 1);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.migrate();
+    suite.token.migrate();
+}
+
+#[test]
+#[should_panic(expected: 'Storage already migrated')]
+fn test_compliance_migrate_on_current_version() {
+    let suite = deploy_suite();
+    
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.migrate();
+}
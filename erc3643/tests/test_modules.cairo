@@ -0,0 +1,321 @@
+use starknet::ContractAddress;
+use snforge_std::{
+    start_cheat_caller_address, stop_cheat_caller_address, start_cheat_block_timestamp_global, start_mock_call
+};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::identity_storage::CATEGORY_RETAIL;
+use erc3643::modules::country_restrict::{ICountryRestrictModuleDispatcher, ICountryRestrictModuleDispatcherTrait};
+use erc3643::modules::country_holder_limit::{
+    ICountryHolderLimitModuleDispatcher, ICountryHolderLimitModuleDispatcherTrait
+};
+use erc3643::modules::daily_transfer_limit::{
+    IDailyTransferLimitModuleDispatcher, IDailyTransferLimitModuleDispatcherTrait
+};
+use erc3643::modules::exchange_limits::{IExchangeLimitsModuleDispatcher, IExchangeLimitsModuleDispatcherTrait};
+use erc3643::modules::holding_period::{IHoldingPeriodModuleDispatcher, IHoldingPeriodModuleDispatcherTrait};
+use erc3643::modules::investor_category_caps::{
+    IInvestorCategoryCapsModuleDispatcher, IInvestorCategoryCapsModuleDispatcherTrait
+};
+use erc3643::modules::investor_value_cap::{IInvestorValueCapModuleDispatcher, IInvestorValueCapModuleDispatcherTrait};
+use erc3643::modules::lockup::{ILockupModuleDispatcher, ILockupModuleDispatcherTrait};
+use erc3643::modules::time_transfer_limits::{
+    ITimeTransferLimitsModuleDispatcher, ITimeTransferLimitsModuleDispatcherTrait
+};
+use erc3643::modules::transfer_allowlist::{
+    ITransferAllowlistModuleDispatcher, ITransferAllowlistModuleDispatcherTrait
+};
+use erc3643::modules::velocity_limit::{IVelocityLimitModuleDispatcher, IVelocityLimitModuleDispatcherTrait};
+use erc3643::modules::vesting::{IVestingModuleDispatcher, IVestingModuleDispatcherTrait};
+use crate::common::{
+    Suite, deploy_suite, deploy_module, add_rule, register, mint, transfer, owner, alice, bob, COUNTRY_FRANCE,
+    COUNTRY_USA
+};
+
+const START: u64 = 1000;
+const DAY: u64 = 86400;
+const UNIT: u256 = 1000000000000000000; // One whole token at 18 decimals
+
+// Alice and Bob registered in France, the module bound before anything is minted
+fn setup_module(name: ByteArray, extra: Array<felt252>) -> (Suite, ContractAddress) {
+    start_cheat_block_timestamp_global(START);
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    let module = deploy_module(suite, name, extra);
+    add_rule(suite, module);
+    (suite, module)
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_country_restrict_blocks_restricted_recipient() {
+    let (suite, module) = setup_module("CountryRestrictModule", array![]);
+    register(suite, 'carol'.try_into().unwrap(), 'carol_id', COUNTRY_USA);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(module, owner());
+    ICountryRestrictModuleDispatcher { contract_address: module }.add_country_restriction(COUNTRY_USA);
+    stop_cheat_caller_address(module);
+    
+    transfer(suite, alice(), bob(), 100);
+    transfer(suite, alice(), 'carol'.try_into().unwrap(), 100);
+}
+
+#[test]
+fn test_country_holder_limit_frees_slot_of_leaving_holder() {
+    let (suite, module) = setup_module("CountryHolderLimitModule", array![]);
+    let limit = ICountryHolderLimitModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    limit.set_country_cap(COUNTRY_FRANCE, 1);
+    stop_cheat_caller_address(module);
+    mint(suite, alice(), 1000);
+    
+    // Alice hands her whole position over, the French holder count stays at one
+    transfer(suite, alice(), bob(), 1000);
+    assert(limit.get_country_holder_count(COUNTRY_FRANCE) == 1, 'Single French holder');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_country_holder_limit_blocks_new_holder() {
+    let (suite, module) = setup_module("CountryHolderLimitModule", array![]);
+    start_cheat_caller_address(module, owner());
+    ICountryHolderLimitModuleDispatcher { contract_address: module }.set_country_cap(COUNTRY_FRANCE, 1);
+    stop_cheat_caller_address(module);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 400);
+}
+
+#[test]
+fn test_daily_transfer_limit_resets_next_day() {
+    let (suite, module) = setup_module("DailyTransferLimitModule", array![1000, 0]);
+    mint(suite, alice(), 5000);
+    
+    transfer(suite, alice(), bob(), 1000);
+    assert(
+        IDailyTransferLimitModuleDispatcher { contract_address: module }.get_spent_today(alice()) == 1000,
+        'Daily volume recorded'
+    );
+    
+    start_cheat_block_timestamp_global(START + DAY);
+    transfer(suite, alice(), bob(), 1000);
+    assert(suite.token.balance_of(bob()) == 2000, 'Second day allowed');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_daily_transfer_limit_blocks_excess() {
+    let (suite, _) = setup_module("DailyTransferLimitModule", array![1000, 0]);
+    mint(suite, alice(), 5000);
+    
+    transfer(suite, alice(), bob(), 600);
+    transfer(suite, alice(), bob(), 500);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_exchange_deposit_limit() {
+    let (suite, module) = setup_module("ExchangeLimitsModule", array![]);
+    let limits = IExchangeLimitsModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    limits.tag_exchange(bob());
+    limits.set_exchange_limits(bob(), DAY, 500, 0);
+    stop_cheat_caller_address(module);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 400);
+    assert(limits.get_deposited(alice(), bob()) == 400, 'Deposit recorded');
+    transfer(suite, alice(), bob(), 200);
+}
+
+#[test]
+fn test_holding_period_releases_lots() {
+    let (suite, module) = setup_module("HoldingPeriodModule", array![100]);
+    let holding = IHoldingPeriodModuleDispatcher { contract_address: module };
+    mint(suite, alice(), 1000);
+    assert(holding.get_transferable_balance(alice()) == 0, 'Lot still held');
+    
+    start_cheat_block_timestamp_global(START + 100);
+    assert(holding.get_transferable_balance(alice()) == 1000, 'Lot released');
+    transfer(suite, alice(), bob(), 1000);
+    
+    // The recipient starts its own holding period
+    assert(holding.get_transferable_balance(bob()) == 0, 'Recipient lot held');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_holding_period_blocks_early_transfer() {
+    let (suite, _) = setup_module("HoldingPeriodModule", array![100]);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_block_timestamp_global(START + 50);
+    transfer(suite, alice(), bob(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_investor_category_cap() {
+    let (suite, module) = setup_module("InvestorCategoryCapsModule", array![]);
+    let caps = IInvestorCategoryCapsModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    caps.set_category_cap(CATEGORY_RETAIL, 500);
+    stop_cheat_caller_address(module);
+    
+    mint(suite, alice(), 500);
+    assert(caps.get_investor_balance(alice()) == 500, 'Balance tracked');
+    mint(suite, alice(), 1);
+}
+
+fn setup_value_cap(publish_time: u64) -> Suite {
+    let (suite, module) = setup_module("InvestorValueCapModule", array![]);
+    let oracle: ContractAddress = 'oracle'.try_into().unwrap();
+    
+    // Ten currency units per whole token, published at `publish_time`
+    start_mock_call(oracle, selector!("get_price"), (10_u128, 0_u128, 0_u8, publish_time));
+    
+    let cap = IInvestorValueCapModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    cap.set_price_feed(oracle, 'TREX/USD', 3600, 100);
+    cap.set_max_investor_value(1000);
+    stop_cheat_caller_address(module);
+    suite
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_investor_value_cap() {
+    let suite = setup_value_cap(START);
+    
+    mint(suite, alice(), 100 * UNIT);
+    mint(suite, alice(), UNIT);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_investor_value_cap_fails_closed_on_stale_price() {
+    let suite = setup_value_cap(START);
+    
+    start_cheat_block_timestamp_global(START + 3601);
+    mint(suite, alice(), UNIT);
+}
+
+#[test]
+fn test_lockup_releases_at_batch_time() {
+    let (suite, module) = setup_module("LockupModule", array![(START + 500).into()]);
+    let lockup = ILockupModuleDispatcher { contract_address: module };
+    mint(suite, alice(), 1000);
+    assert(lockup.get_locked_balance(alice()) == 1000, 'Minted tokens locked');
+    
+    start_cheat_block_timestamp_global(START + 500);
+    transfer(suite, alice(), bob(), 1000);
+    assert(suite.token.balance_of(bob()) == 1000, 'Unlocked transfer');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_lockup_blocks_locked_tokens() {
+    let (suite, _) = setup_module("LockupModule", array![(START + 500).into()]);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 1);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_time_transfer_limit() {
+    let (suite, module) = setup_module("TimeTransferLimitsModule", array![]);
+    let limits = ITimeTransferLimitsModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    limits.set_time_transfer_limit(DAY, 500);
+    stop_cheat_caller_address(module);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 400);
+    assert(limits.get_spent(alice(), DAY) == 400, 'Window volume recorded');
+    transfer(suite, alice(), bob(), 200);
+}
+
+#[test]
+fn test_transfer_allowlist_pair() {
+    let (suite, module) = setup_module("TransferAllowlistModule", array![]);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(module, owner());
+    ITransferAllowlistModuleDispatcher { contract_address: module }.allow_pair(alice(), bob());
+    stop_cheat_caller_address(module);
+    
+    transfer(suite, alice(), bob(), 100);
+    assert(suite.token.balance_of(bob()) == 100, 'Allowed pair transfer');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_transfer_allowlist_blocks_unlisted_pair() {
+    let (suite, _) = setup_module("TransferAllowlistModule", array![]);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_velocity_limit() {
+    let (suite, module) = setup_module("VelocityLimitModule", array![100, 1]);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 100);
+    assert(IVelocityLimitModuleDispatcher { contract_address: module }.get_transfer_count(alice()) == 1, 'Transfer counted');
+    transfer(suite, alice(), bob(), 100);
+}
+
+#[test]
+fn test_velocity_limit_window_elapses() {
+    let (suite, _) = setup_module("VelocityLimitModule", array![100, 1]);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 100);
+    start_cheat_block_timestamp_global(START + 100);
+    transfer(suite, alice(), bob(), 100);
+    assert(suite.token.balance_of(bob()) == 200, 'New window allowed');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_vesting_locks_unvested_tokens() {
+    let (suite, module) = setup_module("VestingModule", array![]);
+    mint(suite, alice(), 1000);
+    
+    let vesting = IVestingModuleDispatcher { contract_address: module };
+    start_cheat_caller_address(module, owner());
+    vesting.create_vesting(alice(), 1000, START, 100, 1000);
+    stop_cheat_caller_address(module);
+    assert(vesting.get_locked_amount(alice()) == 1000, 'Nothing vested before cliff');
+    
+    transfer(suite, alice(), bob(), 1);
+}
+
+#[test]
+fn test_vesting_releases_after_duration() {
+    let (suite, module) = setup_module("VestingModule", array![]);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(module, owner());
+    IVestingModuleDispatcher { contract_address: module }.create_vesting(alice(), 1000, START, 100, 1000);
+    stop_cheat_caller_address(module);
+    
+    start_cheat_block_timestamp_global(START + 1000);
+    transfer(suite, alice(), bob(), 1000);
+    assert(suite.token.balance_of(bob()) == 1000, 'Vested transfer');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_whole_units_rejects_fractions() {
+    let (suite, _) = setup_module("WholeUnitsModule", array![]);
+    mint(suite, alice(), 5 * UNIT);
+    
+    transfer(suite, alice(), bob(), UNIT);
+    transfer(suite, alice(), bob(), UNIT + 1);
+}
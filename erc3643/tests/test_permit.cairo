@@ -0,0 +1,51 @@
+use snforge_std::{start_mock_call, start_cheat_caller_address, start_cheat_block_timestamp_global};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use crate::common::{deploy_suite, deploy_signer, alice, bob, charlie};
+
+// The holder account is mocked: these tests cover the permit flow, the SRC6 account checks the signature
+fn mock_signature_check(valid: bool) {
+    let result = if valid { starknet::VALIDATED } else { 0 };
+    start_mock_call(alice(), selector!("is_valid_signature"), result);
+}
+
+#[test]
+fn test_permit_approves_and_uses_nonce() {
+    let suite = deploy_suite();
+    mock_signature_check(true);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.permit(alice(), bob(), 500, 100, array!['r', 's'].span());
+    
+    assert(suite.token.allowance(alice(), bob()) == 500, 'Permit approved');
+    assert(suite.token.nonces(alice()) == 1, 'Nonce used');
+}
+
+#[test]
+#[should_panic(expected: 'Invalid signature')]
+fn test_permit_rejects_invalid_signature() {
+    let suite = deploy_suite();
+    mock_signature_check(false);
+    suite.token.permit(alice(), bob(), 500, 100, array!['r', 's'].span());
+}
+
+#[test]
+#[should_panic(expected: 'Permit expired')]
+fn test_permit_rejects_expired_deadline() {
+    let suite = deploy_suite();
+    mock_signature_check(true);
+    start_cheat_block_timestamp_global(200);
+    suite.token.permit(alice(), bob(), 500, 100, array!['r', 's'].span());
+}
+
+#[test]
+#[should_panic(expected: 'Invalid signature')]
+fn test_permit_not_replayable_on_other_token() {
+    let first = deploy_suite();
+    let second = deploy_suite();
+    let holder = deploy_signer(array![first, second]);
+    
+    // Both tokens start their nonces at 0, the message names the token
+    first.token.permit(holder, bob(), 500, 100, array!['r', 's'].span());
+    assert(first.token.allowance(holder, bob()) == 500, 'Permit approved');
+    second.token.permit(holder, bob(), 500, 100, array!['r', 's'].span());
+}
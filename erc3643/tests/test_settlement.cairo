@@ -0,0 +1,554 @@
+use starknet::ContractAddress;
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address, start_cheat_block_timestamp_global};
+use erc3643::token::{IERC3643TokenDispatcher, IERC3643TokenDispatcherTrait};
+use erc3643::dva::{IDVATransferManagerDispatcher, IDVATransferManagerDispatcherTrait};
+use erc3643::dvp::{IDvPManagerDispatcher, IDvPManagerDispatcherTrait, TRADE_SETTLED};
+use erc3643::dvd::{IDvDTransferManagerDispatcher, IDvDTransferManagerDispatcherTrait, SWAP_SETTLED};
+use erc3643::escrow::{IEscrowDispatcher, IEscrowDispatcherTrait, ESCROW_CANCELLED};
+use erc3643::distribution::{IDistributionDispatcher, IDistributionDispatcherTrait, DAY_COUNT_ACTUAL_365};
+use erc3643::redemption::{IRedemptionDispatcher, IRedemptionDispatcherTrait, REDEMPTION_SETTLED};
+use erc3643::buyback::{IBuybackDispatcher, IBuybackDispatcherTrait};
+use crate::common::{
+    Suite, deploy, deploy_suite, register, mint, owner, alice, bob, charlie, treasury, COUNTRY_FRANCE
+};
+
+const START: u64 = 1000;
+const UNIT: u256 = 1000000000000000000; // One whole token at 18 decimals
+
+fn attester() -> ContractAddress {
+    'attester'.try_into().unwrap()
+}
+
+// A security token with Alice holding 1000 and a cash token with Bob holding 10000,
+// both parties registered in both suites
+fn setup_markets() -> (Suite, Suite) {
+    start_cheat_block_timestamp_global(START);
+    let security = deploy_suite();
+    let cash = deploy_suite();
+    register(security, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(security, bob(), 'bob_id', COUNTRY_FRANCE);
+    register(cash, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(cash, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(security, alice(), 1000);
+    mint(cash, bob(), 10000);
+    (security, cash)
+}
+
+fn approve(token: IERC3643TokenDispatcher, holder: ContractAddress, spender: ContractAddress, amount: u256) {
+    start_cheat_caller_address(token.contract_address, holder);
+    token.approve(spender, amount);
+    stop_cheat_caller_address(token.contract_address);
+}
+
+fn add_agent(suite: Suite, agent: ContractAddress) {
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.add_agent(agent);
+    stop_cheat_caller_address(suite.token.contract_address);
+}
+
+// The owner funds pools from its own cash, so it is registered in the cash suite
+fn fund_owner(cash: Suite, spender: ContractAddress, amount: u256) {
+    register(cash, owner(), 'owner_id', COUNTRY_FRANCE);
+    mint(cash, owner(), amount);
+    approve(cash.token, owner(), spender, amount);
+}
+
+// DvP
+
+fn open_trade(security: Suite, cash: Suite, expiry: u64) -> (IDvPManagerDispatcher, u64) {
+    let dvp = IDvPManagerDispatcher { contract_address: deploy("DvPManager", array![]) };
+    approve(security.token, alice(), dvp.contract_address, 100);
+    
+    start_cheat_caller_address(dvp.contract_address, alice());
+    let trade_id = dvp.open_trade(security.token.contract_address, 100, bob(), cash.token.contract_address, 500, expiry);
+    stop_cheat_caller_address(dvp.contract_address);
+    (dvp, trade_id)
+}
+
+#[test]
+fn test_dvp_settles_both_legs() {
+    let (security, cash) = setup_markets();
+    let (dvp, trade_id) = open_trade(security, cash, 0);
+    approve(cash.token, bob(), dvp.contract_address, 500);
+    
+    start_cheat_caller_address(dvp.contract_address, bob());
+    dvp.settle_trade(trade_id);
+    stop_cheat_caller_address(dvp.contract_address);
+    
+    assert(security.token.balance_of(alice()) == 900, 'Seller delivered');
+    assert(security.token.balance_of(bob()) == 100, 'Buyer received');
+    assert(cash.token.balance_of(alice()) == 500, 'Seller paid');
+    assert(cash.token.balance_of(bob()) == 9500, 'Buyer charged');
+    let (_, _, _, _, _, _, _, status) = dvp.get_trade(trade_id);
+    assert(status == TRADE_SETTLED, 'Trade settled');
+}
+
+#[test]
+#[should_panic(expected: 'Only buyer can settle')]
+fn test_dvp_settle_by_non_buyer() {
+    let (security, cash) = setup_markets();
+    let (dvp, trade_id) = open_trade(security, cash, 0);
+    
+    start_cheat_caller_address(dvp.contract_address, charlie());
+    dvp.settle_trade(trade_id);
+}
+
+#[test]
+#[should_panic(expected: 'Trade expired')]
+fn test_dvp_settle_after_expiry() {
+    let (security, cash) = setup_markets();
+    let (dvp, trade_id) = open_trade(security, cash, START + 100);
+    approve(cash.token, bob(), dvp.contract_address, 500);
+    
+    start_cheat_block_timestamp_global(START + 101);
+    start_cheat_caller_address(dvp.contract_address, bob());
+    dvp.settle_trade(trade_id);
+}
+
+// DvA
+
+// Alice transfers 100 to Bob once Bob approves
+fn initiate_dva(security: Suite) -> (IDVATransferManagerDispatcher, u64) {
+    let dva = IDVATransferManagerDispatcher { contract_address: deploy("DVATransferManager", array![]) };
+    add_agent(security, dva.contract_address);
+    
+    start_cheat_caller_address(dva.contract_address, owner());
+    dva.set_approval_criteria(security.token.contract_address, true, false, array![], 0);
+    stop_cheat_caller_address(dva.contract_address);
+    
+    approve(security.token, alice(), dva.contract_address, 100);
+    start_cheat_caller_address(dva.contract_address, alice());
+    let transfer_id = dva.initiate_transfer(security.token.contract_address, bob(), 100);
+    stop_cheat_caller_address(dva.contract_address);
+    (dva, transfer_id)
+}
+
+#[test]
+fn test_dva_settles_on_last_approval() {
+    let (security, _) = setup_markets();
+    let (dva, transfer_id) = initiate_dva(security);
+    assert(security.token.get_held_tokens(alice(), dva.contract_address) == 100, 'Amount held');
+    
+    start_cheat_caller_address(dva.contract_address, bob());
+    dva.approve_transfer(transfer_id);
+    stop_cheat_caller_address(dva.contract_address);
+    
+    assert(security.token.get_held_tokens(alice(), dva.contract_address) == 0, 'Hold released');
+    assert(security.token.balance_of(bob()) == 100, 'Recipient credited');
+}
+
+#[test]
+#[should_panic(expected: 'Insufficient unfrozen balance')]
+fn test_dva_held_amount_cannot_move() {
+    let (security, _) = setup_markets();
+    initiate_dva(security);
+    
+    start_cheat_caller_address(security.token.contract_address, alice());
+    security.token.transfer(bob(), 901);
+}
+
+#[test]
+fn test_dva_cancel_keeps_agent_freeze() {
+    let (security, _) = setup_markets();
+    start_cheat_caller_address(security.token.contract_address, owner());
+    security.token.freeze_partial_tokens_with_reason(alice(), 200, 'court_order');
+    stop_cheat_caller_address(security.token.contract_address);
+    let (dva, transfer_id) = initiate_dva(security);
+    
+    start_cheat_caller_address(dva.contract_address, alice());
+    dva.cancel_transfer(transfer_id);
+    stop_cheat_caller_address(dva.contract_address);
+    
+    // Only the manager's hold is lifted, the court-ordered freeze and its record stay
+    assert(security.token.get_held_tokens(alice(), dva.contract_address) == 0, 'Hold released');
+    assert(security.token.get_frozen_tokens(alice()) == 200, 'Freeze kept');
+    let (reason, agent, _) = security.token.get_freeze_record(alice());
+    assert(reason == 'court_order' && agent == owner(), 'Freeze record kept');
+}
+
+// DvD
+
+// Alice offers 100 of the first security for 200 of a second one held by Bob
+fn initiate_swap() -> (Suite, Suite, IDvDTransferManagerDispatcher, u64) {
+    let (security, _) = setup_markets();
+    let other = deploy_suite();
+    register(other, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(other, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(other, bob(), 1000);
+    
+    let dvd = IDvDTransferManagerDispatcher { contract_address: deploy("DvDTransferManager", array![owner().into()]) };
+    approve(security.token, alice(), dvd.contract_address, 100);
+    
+    start_cheat_caller_address(dvd.contract_address, alice());
+    let swap_id = dvd.initiate_swap(
+        security.token.contract_address, 100, bob(), other.token.contract_address, 200, 0
+    );
+    stop_cheat_caller_address(dvd.contract_address);
+    (security, other, dvd, swap_id)
+}
+
+#[test]
+fn test_dvd_swap_takes_venue_fee() {
+    let (security, other, dvd, swap_id) = initiate_swap();
+    register(security, treasury(), 'treasury_id', COUNTRY_FRANCE);
+    
+    start_cheat_caller_address(dvd.contract_address, owner());
+    dvd.set_fee(security.token.contract_address, 100, treasury()); // 1%
+    stop_cheat_caller_address(dvd.contract_address);
+    
+    approve(other.token, bob(), dvd.contract_address, 200);
+    start_cheat_caller_address(dvd.contract_address, bob());
+    dvd.take_swap(swap_id);
+    stop_cheat_caller_address(dvd.contract_address);
+    
+    assert(security.token.balance_of(bob()) == 99, 'Taker received net');
+    assert(security.token.balance_of(treasury()) == 1, 'Fee wallet received fee');
+    assert(other.token.balance_of(alice()) == 200, 'Maker received');
+    let (_, _, _, _, _, _, _, status) = dvd.get_swap(swap_id);
+    assert(status == SWAP_SETTLED, 'Swap settled');
+}
+
+#[test]
+#[should_panic(expected: 'Caller is not the owner')]
+fn test_dvd_set_fee_by_non_owner() {
+    let (security, _, dvd, _) = initiate_swap();
+    
+    start_cheat_caller_address(dvd.contract_address, alice());
+    dvd.set_fee(security.token.contract_address, 100, treasury());
+}
+
+#[test]
+#[should_panic(expected: 'Only taker can take')]
+fn test_dvd_take_by_non_taker() {
+    let (_, _, dvd, swap_id) = initiate_swap();
+    
+    start_cheat_caller_address(dvd.contract_address, charlie());
+    dvd.take_swap(swap_id);
+}
+
+#[test]
+#[should_panic(expected: 'Only counterparties can cancel')]
+fn test_dvd_cancel_by_third_party() {
+    let (_, _, dvd, swap_id) = initiate_swap();
+    
+    start_cheat_caller_address(dvd.contract_address, charlie());
+    dvd.cancel_swap(swap_id);
+}
+
+// Escrow
+
+// Alice escrows 100 for Bob until START + 100, released by the attester
+fn create_escrow() -> (Suite, IEscrowDispatcher, u64) {
+    let (security, _) = setup_markets();
+    let escrow = IEscrowDispatcher { contract_address: deploy("Escrow", array![]) };
+    add_agent(security, escrow.contract_address);
+    approve(security.token, alice(), escrow.contract_address, 100);
+    
+    start_cheat_caller_address(escrow.contract_address, alice());
+    let escrow_id = escrow.create_escrow(security.token.contract_address, bob(), 100, START + 100, attester());
+    stop_cheat_caller_address(escrow.contract_address);
+    (security, escrow, escrow_id)
+}
+
+#[test]
+fn test_escrow_release_by_attester() {
+    let (security, escrow, escrow_id) = create_escrow();
+    assert(security.token.get_frozen_tokens(alice()) == 100, 'Escrowed amount frozen');
+    
+    start_cheat_caller_address(escrow.contract_address, attester());
+    escrow.release_escrow(escrow_id);
+    stop_cheat_caller_address(escrow.contract_address);
+    
+    assert(security.token.get_frozen_tokens(alice()) == 0, 'Escrowed amount unfrozen');
+    assert(security.token.balance_of(alice()) == 900, 'Depositor debited');
+    assert(security.token.balance_of(bob()) == 100, 'Beneficiary credited');
+}
+
+#[test]
+#[should_panic(expected: 'Not allowed to release')]
+fn test_escrow_release_by_stranger() {
+    let (_, escrow, escrow_id) = create_escrow();
+    
+    start_cheat_caller_address(escrow.contract_address, charlie());
+    escrow.release_escrow(escrow_id);
+}
+
+#[test]
+#[should_panic(expected: 'Escrow not timed out')]
+fn test_escrow_cancel_before_timeout() {
+    let (_, escrow, escrow_id) = create_escrow();
+    
+    start_cheat_caller_address(escrow.contract_address, alice());
+    escrow.cancel_escrow(escrow_id);
+}
+
+#[test]
+fn test_escrow_cancel_after_timeout() {
+    let (security, escrow, escrow_id) = create_escrow();
+    
+    start_cheat_block_timestamp_global(START + 101);
+    start_cheat_caller_address(escrow.contract_address, alice());
+    escrow.cancel_escrow(escrow_id);
+    stop_cheat_caller_address(escrow.contract_address);
+    
+    assert(security.token.get_frozen_tokens(alice()) == 0, 'Refund unfrozen');
+    assert(security.token.balance_of(alice()) == 1000, 'Depositor kept tokens');
+    let (_, _, _, _, _, _, status) = escrow.get_escrow(escrow_id);
+    assert(status == ESCROW_CANCELLED, 'Escrow cancelled');
+}
+
+// Distribution
+
+// 1000 of cash against a snapshot where Alice holds 750 and Bob 250
+fn create_distribution() -> (Suite, IDistributionDispatcher, u64) {
+    start_cheat_block_timestamp_global(START);
+    let security = deploy_suite();
+    let cash = deploy_suite();
+    register(security, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(security, bob(), 'bob_id', COUNTRY_FRANCE);
+    register(cash, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(cash, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(security, alice(), 750);
+    mint(security, bob(), 250);
+    
+    let distribution = IDistributionDispatcher { contract_address: deploy("Distribution", array![]) };
+    register(cash, distribution.contract_address, 'distribution_id', COUNTRY_FRANCE);
+    fund_owner(cash, distribution.contract_address, 1000);
+    
+    start_cheat_caller_address(security.token.contract_address, owner());
+    let snapshot_id = security.token.snapshot();
+    stop_cheat_caller_address(security.token.contract_address);
+    
+    start_cheat_caller_address(distribution.contract_address, owner());
+    let distribution_id = distribution.create_distribution(
+        security.token.contract_address, snapshot_id, cash.token.contract_address, 1000
+    );
+    stop_cheat_caller_address(distribution.contract_address);
+    (cash, distribution, distribution_id)
+}
+
+#[test]
+fn test_distribution_claim_pro_rata() {
+    let (cash, distribution, distribution_id) = create_distribution();
+    
+    start_cheat_caller_address(distribution.contract_address, alice());
+    assert(distribution.claim(distribution_id) == 750, 'Claimed share');
+    stop_cheat_caller_address(distribution.contract_address);
+    
+    assert(cash.token.balance_of(alice()) == 750, 'Holder paid');
+    assert(distribution.get_claimable(distribution_id, bob()) == 250, 'Other share unchanged');
+}
+
+#[test]
+#[should_panic(expected: 'Already claimed')]
+fn test_distribution_claim_twice() {
+    let (_, distribution, distribution_id) = create_distribution();
+    
+    start_cheat_caller_address(distribution.contract_address, alice());
+    distribution.claim(distribution_id);
+    distribution.claim(distribution_id);
+}
+
+#[test]
+fn test_distribution_batch_claim_skips_paid_holders() {
+    let (cash, distribution, distribution_id) = create_distribution();
+    
+    start_cheat_caller_address(distribution.contract_address, alice());
+    distribution.claim(distribution_id);
+    stop_cheat_caller_address(distribution.contract_address);
+    
+    start_cheat_caller_address(distribution.contract_address, owner());
+    let paid = distribution.batch_claim(distribution_id, array![alice(), bob()]);
+    stop_cheat_caller_address(distribution.contract_address);
+    
+    assert(paid == 250, 'Only Bob paid');
+    assert(cash.token.balance_of(alice()) == 750, 'Alice paid once');
+    assert(cash.token.balance_of(bob()) == 250, 'Bob paid');
+}
+
+#[test]
+#[should_panic(expected: 'Only agents allowed')]
+fn test_distribution_batch_claim_by_non_agent() {
+    let (_, distribution, distribution_id) = create_distribution();
+    
+    start_cheat_caller_address(distribution.contract_address, alice());
+    distribution.batch_claim(distribution_id, array![bob()]);
+}
+
+// 10% a year on a face value of one cash unit per token, paid once after 365 days
+fn schedule_coupon(security: Suite, cash: Suite) -> IDistributionDispatcher {
+    let distribution = IDistributionDispatcher { contract_address: deploy("Distribution", array![]) };
+    add_agent(security, distribution.contract_address);
+    
+    start_cheat_caller_address(distribution.contract_address, owner());
+    distribution.set_coupon_schedule(
+        security.token.contract_address,
+        cash.token.contract_address,
+        UNIT,
+        1000,
+        DAY_COUNT_ACTUAL_365,
+        START,
+        array![START + 365 * 86400]
+    );
+    stop_cheat_caller_address(distribution.contract_address);
+    
+    start_cheat_block_timestamp_global(START + 365 * 86400);
+    distribution
+}
+
+#[test]
+fn test_coupon_recorded_on_a_snapshot_after_the_payment_date() {
+    let (security, cash) = setup_markets();
+    let distribution = schedule_coupon(security, cash);
+    
+    start_cheat_caller_address(distribution.contract_address, owner());
+    let amount = distribution.record_coupon(security.token.contract_address, 0);
+    stop_cheat_caller_address(distribution.contract_address);
+    
+    let (snapshot_id, recorded, _) = distribution.get_coupon(security.token.contract_address, 0);
+    assert(amount == 100 && recorded == 100, 'Interest on the supply');
+    assert(snapshot_id == security.token.get_current_snapshot_id(), 'Snapshot taken on record');
+}
+
+#[test]
+#[should_panic(expected: 'No coupon due')]
+fn test_coupon_on_empty_supply_rejected() {
+    start_cheat_block_timestamp_global(START);
+    let security = deploy_suite();
+    let distribution = schedule_coupon(security, deploy_suite());
+    
+    start_cheat_caller_address(distribution.contract_address, owner());
+    distribution.record_coupon(security.token.contract_address, 0);
+}
+
+// Redemption
+
+// A window open from START to START + 100, Alice requesting 100
+fn request_redemption() -> (Suite, IRedemptionDispatcher, u64) {
+    let (security, _) = setup_markets();
+    let redemption = IRedemptionDispatcher { contract_address: deploy("Redemption", array![]) };
+    add_agent(security, redemption.contract_address);
+    
+    start_cheat_caller_address(redemption.contract_address, owner());
+    redemption.set_redemption_window(security.token.contract_address, START, START + 100);
+    stop_cheat_caller_address(redemption.contract_address);
+    
+    start_cheat_caller_address(redemption.contract_address, alice());
+    let request_id = redemption.request_redemption(security.token.contract_address, 100);
+    stop_cheat_caller_address(redemption.contract_address);
+    (security, redemption, request_id)
+}
+
+#[test]
+fn test_redemption_settle_burns_frozen_amount() {
+    let (security, redemption, request_id) = request_redemption();
+    assert(security.token.get_frozen_tokens(alice()) == 100, 'Request frozen');
+    
+    start_cheat_caller_address(redemption.contract_address, owner());
+    redemption.settle_redemption(request_id, 'wire_ref');
+    stop_cheat_caller_address(redemption.contract_address);
+    
+    assert(security.token.balance_of(alice()) == 900, 'Holder burnt');
+    assert(security.token.total_supply() == 900, 'Supply reduced');
+    assert(security.token.get_frozen_tokens(alice()) == 0, 'Nothing left frozen');
+    let (_, _, _, _, status, reference) = redemption.get_redemption(request_id);
+    assert(status == REDEMPTION_SETTLED, 'Request settled');
+    assert(reference == 'wire_ref', 'Reference recorded');
+}
+
+#[test]
+#[should_panic(expected: 'Only agents allowed')]
+fn test_redemption_settle_by_non_agent() {
+    let (_, redemption, request_id) = request_redemption();
+    
+    start_cheat_caller_address(redemption.contract_address, alice());
+    redemption.settle_redemption(request_id, 'wire_ref');
+}
+
+#[test]
+#[should_panic(expected: 'Request not expired')]
+fn test_redemption_release_before_close() {
+    let (_, redemption, request_id) = request_redemption();
+    
+    redemption.release_expired_redemption(request_id);
+}
+
+#[test]
+fn test_redemption_release_after_close() {
+    let (security, redemption, request_id) = request_redemption();
+    
+    start_cheat_block_timestamp_global(START + 100);
+    redemption.release_expired_redemption(request_id);
+    
+    assert(security.token.get_frozen_tokens(alice()) == 0, 'Request unfrozen');
+    assert(security.token.balance_of(alice()) == 1000, 'Holder kept tokens');
+}
+
+// Buyback
+
+// A pool of 1000 cash paying 5 per whole token
+fn open_buyback(buyback_treasury: ContractAddress) -> (Suite, Suite, IBuybackDispatcher, u64) {
+    let (security, cash) = setup_markets();
+    let buyback = IBuybackDispatcher { contract_address: deploy("Buyback", array![]) };
+    register(cash, buyback.contract_address, 'buyback_id', COUNTRY_FRANCE);
+    fund_owner(cash, buyback.contract_address, 1000);
+    
+    start_cheat_caller_address(buyback.contract_address, owner());
+    let buyback_id = buyback.open_buyback(
+        security.token.contract_address, cash.token.contract_address, 5 * UNIT, 1000, buyback_treasury
+    );
+    stop_cheat_caller_address(buyback.contract_address);
+    (security, cash, buyback, buyback_id)
+}
+
+fn sell(buyback: IBuybackDispatcher, buyback_id: u64, amount: u256) -> u256 {
+    start_cheat_caller_address(buyback.contract_address, alice());
+    let payout = buyback.sell_to_buyback(buyback_id, amount);
+    stop_cheat_caller_address(buyback.contract_address);
+    payout
+}
+
+#[test]
+fn test_buyback_sells_into_treasury() {
+    let (security, cash, buyback, buyback_id) = open_buyback(treasury());
+    register(security, treasury(), 'treasury_id', COUNTRY_FRANCE);
+    approve(security.token, alice(), buyback.contract_address, 100);
+    
+    assert(sell(buyback, buyback_id, 100) == 500, 'Payout at price');
+    
+    assert(security.token.balance_of(treasury()) == 100, 'Treasury received');
+    assert(cash.token.balance_of(alice()) == 500, 'Seller paid');
+}
+
+#[test]
+fn test_buyback_burns_without_treasury() {
+    let (security, cash, buyback, buyback_id) = open_buyback(0.try_into().unwrap());
+    add_agent(security, buyback.contract_address);
+    
+    sell(buyback, buyback_id, 100);
+    
+    assert(security.token.total_supply() == 900, 'Tokens burnt');
+    assert(cash.token.balance_of(alice()) == 500, 'Seller paid');
+}
+
+#[test]
+#[should_panic(expected: 'Buyback pool exhausted')]
+fn test_buyback_sell_beyond_pool() {
+    let (security, _, buyback, buyback_id) = open_buyback(0.try_into().unwrap());
+    add_agent(security, buyback.contract_address);
+    
+    sell(buyback, buyback_id, 300);
+}
+
+#[test]
+fn test_buyback_close_refunds_agent() {
+    let (security, cash, buyback, buyback_id) = open_buyback(0.try_into().unwrap());
+    add_agent(security, buyback.contract_address);
+    sell(buyback, buyback_id, 100);
+    
+    start_cheat_caller_address(buyback.contract_address, owner());
+    assert(buyback.close_buyback(buyback_id) == 500, 'Rest of pool refunded');
+    stop_cheat_caller_address(buyback.contract_address);
+    
+    assert(cash.token.balance_of(owner()) == 500, 'Agent refunded');
+}
@@ -0,0 +1,211 @@
+use starknet::ContractAddress;
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::modules::transfer_fees::{ITransferFeesModuleDispatcher, ITransferFeesModuleDispatcherTrait};
+use erc3643::modules::max_holders::{IMaxHoldersModuleDispatcher, IMaxHoldersModuleDispatcherTrait};
+use crate::common::{
+    Suite, deploy_suite, deploy_module, add_rule, register, mint, transfer, owner, alice, bob, charlie, treasury,
+    COUNTRY_FRANCE
+};
+
+// Suite with a 1% fee to the treasury and at most `max_holders` holders
+fn setup(max_holders: u32) -> (Suite, ITransferFeesModuleDispatcher, IMaxHoldersModuleDispatcher) {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    register(suite, charlie(), 'charlie_id', COUNTRY_FRANCE);
+    register(suite, treasury(), 'treasury_id', COUNTRY_FRANCE);
+    
+    let fees_address = deploy_module(suite, "TransferFeesModule", array![treasury().into()]);
+    let max_holders_address = deploy_module(suite, "MaxHoldersModule", array![max_holders.into()]);
+    add_rule(suite, fees_address);
+    add_rule(suite, max_holders_address);
+    
+    let fees = ITransferFeesModuleDispatcher { contract_address: fees_address };
+    start_cheat_caller_address(fees_address, owner());
+    fees.set_fee(100, 0);
+    stop_cheat_caller_address(fees_address);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.set_transfer_fees(fees_address);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    (suite, fees, IMaxHoldersModuleDispatcher { contract_address: max_holders_address })
+}
+
+fn assert_tracked(suite: Suite, max_holders: IMaxHoldersModuleDispatcher, wallet: ContractAddress) {
+    assert(
+        max_holders.get_investor_balance(wallet) == suite.token.balance_of(wallet),
+        'Tracked balance mismatch'
+    );
+}
+
+#[test]
+fn test_fee_is_split_off_the_transfer() {
+    let (suite, _, max_holders) = setup(10);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 500);
+    
+    assert(suite.token.balance_of(alice()) == 500, 'Sender pays the amount');
+    assert(suite.token.balance_of(bob()) == 495, 'Recipient gets amount - fee');
+    assert(suite.token.balance_of(treasury()) == 5, 'Treasury gets the fee');
+    assert(suite.token.total_supply() == 1000, 'Supply unchanged');
+    
+    // Both legs went through the module hooks, the tracked balances follow the token
+    assert_tracked(suite, max_holders, alice());
+    assert_tracked(suite, max_holders, bob());
+    assert_tracked(suite, max_holders, treasury());
+    assert(max_holders.get_holder_count() == 3, 'Treasury counted as holder');
+}
+
+#[test]
+fn test_fee_charged_on_transfer_from() {
+    let (suite, _, max_holders) = setup(10);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.approve(charlie(), 500);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transfer_from(alice(), bob(), 500);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    // Delegated transfers pay the same fee as the holder's own
+    assert(suite.token.balance_of(bob()) == 495, 'Recipient gets amount - fee');
+    assert(suite.token.balance_of(treasury()) == 5, 'Treasury gets the fee');
+    assert(suite.token.allowance(alice(), charlie()) == 0, 'Allowance spent');
+    assert_tracked(suite, max_holders, bob());
+    assert_tracked(suite, max_holders, treasury());
+}
+
+#[test]
+fn test_emptied_wallet_stops_holding() {
+    let (suite, _, max_holders) = setup(10);
+    mint(suite, alice(), 1000);
+    
+    transfer(suite, alice(), bob(), 1000);
+    
+    assert(suite.token.balance_of(alice()) == 0, 'Sender emptied');
+    assert_tracked(suite, max_holders, alice());
+    assert_tracked(suite, max_holders, bob());
+    assert(max_holders.get_holder_count() == 2, 'Sender no longer a holder');
+}
+
+#[test]
+fn test_exempt_wallet_pays_no_fee() {
+    let (suite, fees, max_holders) = setup(10);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(fees.contract_address, owner());
+    fees.set_fee_exempt(alice(), true);
+    stop_cheat_caller_address(fees.contract_address);
+    
+    transfer(suite, alice(), bob(), 500);
+    
+    assert(suite.token.balance_of(bob()) == 500, 'No fee for exempt sender');
+    assert(suite.token.balance_of(treasury()) == 0, 'Treasury untouched');
+    assert(max_holders.get_holder_count() == 2, 'Treasury not a holder');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_max_holders_still_enforced_with_fees() {
+    let (suite, _, _) = setup(3);
+    mint(suite, alice(), 1000);
+    transfer(suite, alice(), bob(), 500);
+    
+    // Alice, Bob and the treasury already hold tokens
+    transfer(suite, alice(), charlie(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Recipient frozen')]
+fn test_fee_to_frozen_treasury_reverts() {
+    let (suite, _, _) = setup(10);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.set_address_frozen(treasury(), true);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    transfer(suite, alice(), bob(), 500);
+}
+
+#[test]
+#[should_panic(expected: 'Recipient not verified')]
+fn test_fee_to_unverified_treasury_reverts() {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    
+    let fees_address = deploy_module(suite, "TransferFeesModule", array![treasury().into()]);
+    add_rule(suite, fees_address);
+    let fees = ITransferFeesModuleDispatcher { contract_address: fees_address };
+    start_cheat_caller_address(fees_address, owner());
+    fees.set_fee(100, 0);
+    stop_cheat_caller_address(fees_address);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.set_transfer_fees(fees_address);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    mint(suite, alice(), 1000);
+    transfer(suite, alice(), bob(), 500);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer not compliant')]
+fn test_max_holders_counts_the_treasury_before_the_recipient() {
+    let (suite, _, _) = setup(2);
+    mint(suite, alice(), 1000);
+    
+    // The fee makes the treasury the second holder, leaving no room for Bob
+    transfer(suite, alice(), bob(), 500);
+}
+
+#[test]
+#[should_panic(expected: 'Arithmetic overflow')]
+fn test_fee_overflow_reverts() {
+    let (suite, _, _) = setup(10);
+    let max: u256 = core::num::traits::Bounded::<u256>::MAX;
+    mint(suite, alice(), max / 2);
+    
+    // amount * fee_bps does not fit in a u256
+    transfer(suite, alice(), bob(), max / 2);
+}
+
+#[test]
+#[should_panic(expected: 'Fees module bound elsewhere')]
+fn test_fee_module_must_share_the_compliance() {
+    let suite = deploy_suite();
+    let other_suite = deploy_suite();
+    let fees_address = deploy_module(other_suite, "TransferFeesModule", array![treasury().into()]);
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.set_transfer_fees(fees_address);
+}
+
+#[test]
+fn test_partition_credited_net_of_fee() {
+    let (suite, _, _) = setup(10);
+    mint(suite, alice(), 1000);
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.reassign_partition(alice(), 0, 'reg_s', 500);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.transfer_by_partition('reg_s', bob(), 500, "");
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    // The partition holds what arrived, so the default partition does not underflow
+    assert(suite.token.balance_of(bob()) == 495, 'Recipient gets amount - fee');
+    assert(suite.token.balance_of_by_partition(bob(), 'reg_s') == 495, 'Partition gets amount - fee');
+    assert(suite.token.balance_of_by_partition(bob(), 0) == 0, 'Default partition empty');
+    
+    // Later debits of the recipient still work
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.forced_transfer(bob(), charlie(), 100);
+    assert(suite.token.balance_of(bob()) == 395, 'Forced transfer done');
+}
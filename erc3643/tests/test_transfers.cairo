@@ -0,0 +1,220 @@
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address, start_mock_call};
+use erc3643::token::IERC3643TokenDispatcherTrait;
+use erc3643::compliance::IComplianceDispatcherTrait;
+use crate::common::{
+    deploy_suite, deploy_signer, register, mint, transfer, store_map_entry, owner, alice, bob, charlie, COUNTRY_FRANCE,
+    Suite
+};
+
+const MAX_U128: felt252 = 0xffffffffffffffffffffffffffffffff;
+
+// Storage value of u256::MAX, one felt per 128-bit half
+fn max_u256_storage() -> Array<felt252> {
+    array![MAX_U128, MAX_U128]
+}
+
+fn setup_holders() -> Suite {
+    let suite = deploy_suite();
+    register(suite, alice(), 'alice_id', COUNTRY_FRANCE);
+    register(suite, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(suite, alice(), 1000);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.approve(charlie(), 500);
+    stop_cheat_caller_address(suite.token.contract_address);
+    suite
+}
+
+fn require_reference(suite: Suite) {
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.set_transfer_reference_required(true);
+    stop_cheat_caller_address(suite.token.contract_address);
+}
+
+#[test]
+fn test_transfer_with_data_meets_reference_requirement() {
+    let suite = setup_holders();
+    require_reference(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.transfer_with_data(bob(), 100, "trade-42");
+    assert(suite.token.balance_of(bob()) == 100, 'Referenced transfer done');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer reference required')]
+fn test_transfer_from_requires_reference() {
+    let suite = setup_holders();
+    require_reference(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transfer_from(alice(), bob(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Transfer reference required')]
+fn test_camel_case_transfer_from_requires_reference() {
+    let suite = setup_holders();
+    require_reference(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transferFrom(alice(), bob(), 100);
+}
+
+#[test]
+#[should_panic(expected: 'Arithmetic overflow')]
+fn test_increase_allowance_overflow() {
+    let suite = setup_holders();
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.approve(bob(), core::num::traits::Bounded::MAX);
+    suite.token.increase_allowance(bob(), 1);
+}
+
+#[test]
+#[should_panic(expected: 'Arithmetic overflow')]
+fn test_free_balance_overflow() {
+    let suite = setup_holders();
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.reassign_partition(alice(), 0, 'restricted', 1);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    // Frozen and partitioned amounts adding up past u256::MAX must not wrap
+    store_map_entry(suite.token.contract_address, selector!("frozen_tokens"), array![alice().into()], max_u256_storage());
+    transfer(suite, alice(), bob(), 1);
+}
+
+#[test]
+#[should_panic(expected: 'Arithmetic overflow')]
+fn test_assign_partition_overflow() {
+    let suite = setup_holders();
+    store_map_entry(
+        suite.token.contract_address,
+        selector!("partition_balances"),
+        array![alice().into(), 'restricted'],
+        max_u256_storage()
+    );
+    
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.reassign_partition(alice(), 0, 'restricted', 1);
+}
+
+fn block_zero_amount_transfers(suite: Suite) {
+    start_cheat_caller_address(suite.compliance.contract_address, owner());
+    suite.compliance.set_zero_amount_transfers_allowed(false);
+    stop_cheat_caller_address(suite.compliance.contract_address);
+}
+
+#[test]
+fn test_transfer_from_spends_allowance() {
+    let suite = setup_holders();
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transfer_from(alice(), bob(), 200);
+    
+    assert(suite.token.balance_of(bob()) == 200, 'Delegated transfer done');
+    assert(suite.token.allowance(alice(), charlie()) == 300, 'Allowance spent');
+}
+
+#[test]
+#[should_panic(expected: 'Zero amount transfer disallowed')]
+fn test_transfer_from_zero_amount_blocked() {
+    let suite = setup_holders();
+    block_zero_amount_transfers(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transfer_from(alice(), bob(), 0);
+}
+
+#[test]
+#[should_panic(expected: 'Zero amount transfer disallowed')]
+fn test_camel_case_transfer_from_zero_amount_blocked() {
+    let suite = setup_holders();
+    block_zero_amount_transfers(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transferFrom(alice(), bob(), 0);
+}
+
+#[test]
+#[should_panic(expected: 'Zero amount transfer disallowed')]
+fn test_transfer_with_authorization_zero_amount_blocked() {
+    let suite = setup_holders();
+    block_zero_amount_transfers(suite);
+    
+    // The holder account is mocked to accept the authorization signature
+    start_mock_call(alice(), selector!("is_valid_signature"), starknet::VALIDATED);
+    start_cheat_caller_address(suite.token.contract_address, charlie());
+    suite.token.transfer_with_authorization(alice(), bob(), 0, 'nonce', 100, array!['r', 's']);
+}
+
+#[test]
+#[should_panic(expected: 'Invalid signature')]
+fn test_transfer_authorization_not_replayable_on_other_token() {
+    let first = deploy_suite();
+    let second = deploy_suite();
+    let holder = deploy_signer(array![first, second]);
+    register(first, bob(), 'bob_id', COUNTRY_FRANCE);
+    register(second, bob(), 'bob_id', COUNTRY_FRANCE);
+    mint(first, holder, 1000);
+    mint(second, holder, 1000);
+    
+    // Both tokens share the class and its SNIP-12 domain, the message names the token
+    first.token.transfer_with_authorization(holder, bob(), 100, 'nonce', 100, array!['r', 's']);
+    assert(first.token.balance_of(bob()) == 100, 'Authorized transfer done');
+    second.token.transfer_with_authorization(holder, bob(), 100, 'nonce', 100, array!['r', 's']);
+}
+
+#[test]
+fn test_transfer_by_partition_meets_reference_requirement() {
+    let suite = setup_holders();
+    require_reference(suite);
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    suite.token.reassign_partition(alice(), 0, 'reg_s', 200);
+    stop_cheat_caller_address(suite.token.contract_address);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.transfer_by_partition('reg_s', bob(), 100, "trade-42");
+    assert(suite.token.balance_of_by_partition(bob(), 'reg_s') == 100, 'Referenced transfer done');
+}
+
+#[test]
+#[should_panic(expected: 'Transfer reference required')]
+fn test_transfer_by_partition_requires_reference() {
+    let suite = setup_holders();
+    require_reference(suite);
+    
+    start_cheat_caller_address(suite.token.contract_address, alice());
+    suite.token.transfer_by_partition(0, bob(), 100, "");
+}
+
+fn take_snapshot(suite: Suite) -> u64 {
+    start_cheat_caller_address(suite.token.contract_address, owner());
+    let snapshot_id = suite.token.snapshot();
+    stop_cheat_caller_address(suite.token.contract_address);
+    snapshot_id
+}
+
+#[test]
+fn test_balances_at_each_snapshot() {
+    let suite = setup_holders();
+    let first = take_snapshot(suite);
+    transfer(suite, alice(), bob(), 100);
+    let second = take_snapshot(suite);
+    let third = take_snapshot(suite);
+    transfer(suite, alice(), bob(), 200);
+    let fourth = take_snapshot(suite);
+    mint(suite, alice(), 50);
+    
+    // Snapshots without changes in between read the next recorded entry
+    let token = suite.token;
+    assert(token.balance_of_at(alice(), first) == 1000, 'Alice at first');
+    assert(token.balance_of_at(alice(), second) == 900, 'Alice at second');
+    assert(token.balance_of_at(alice(), third) == 900, 'Alice at third');
+    assert(token.balance_of_at(alice(), fourth) == 700, 'Alice at fourth');
+    assert(token.balance_of_at(bob(), first) == 0, 'Bob at first');
+    assert(token.balance_of_at(bob(), third) == 100, 'Bob at third');
+    assert(token.balance_of_at(bob(), fourth) == 300, 'Bob at fourth');
+    assert(token.total_supply_at(first) == 1000, 'Supply at first');
+    assert(token.total_supply_at(fourth) == 1000, 'Supply at fourth');
+    assert(token.total_supply() == 1050, 'Current supply');
+}
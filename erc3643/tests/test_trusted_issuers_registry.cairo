@@ -0,0 +1,38 @@
+use snforge_std::{start_cheat_caller_address, stop_cheat_caller_address};
+use erc3643::trusted_issuers_registry::ITrustedIssuersRegistryDispatcherTrait;
+use crate::common::{deploy_suite, trust_issuer, owner, store_map_entry, set_storage_version};
+
+const CLAIM_TOPIC_KYC: felt252 = 1;
+const ISSUER: felt252 = 'issuer';
+
+#[test]
+fn test_topic_lookups_before_and_after_migration() {
+    let suite = deploy_suite();
+    let registry = suite.trusted_issuers_registry;
+    trust_issuer(suite, ISSUER, array![CLAIM_TOPIC_KYC]);
+    
+    // A version 1 deployment has the issuer topics but no per-topic index yet
+    set_storage_version(registry.contract_address, 1);
+    store_map_entry(registry.contract_address, selector!("topic_issuer_indexes"), array![CLAIM_TOPIC_KYC, ISSUER], array![0]);
+    store_map_entry(registry.contract_address, selector!("topic_issuer_counts"), array![CLAIM_TOPIC_KYC], array![0]);
+    
+    assert(registry.has_claim_topic(ISSUER, CLAIM_TOPIC_KYC), 'Scan finds topic');
+    assert(!registry.has_claim_topic(ISSUER, 'other'), 'Scan rejects other topic');
+    assert(registry.get_trusted_issuers_for_claim_topic(CLAIM_TOPIC_KYC) == array![ISSUER], 'Scan lists issuer');
+    
+    start_cheat_caller_address(registry.contract_address, owner());
+    registry.migrate();
+    stop_cheat_caller_address(registry.contract_address);
+    
+    assert(registry.storage_version() == 2, 'Migrated to version 2');
+    assert(registry.has_claim_topic(ISSUER, CLAIM_TOPIC_KYC), 'Index finds topic');
+    assert(registry.get_trusted_issuers_for_claim_topic(CLAIM_TOPIC_KYC) == array![ISSUER], 'Index lists issuer');
+}
+
+#[test]
+#[should_panic(expected: 'Storage already migrated')]
+fn test_migrate_twice_fails() {
+    let suite = deploy_suite();
+    start_cheat_caller_address(suite.trusted_issuers_registry.contract_address, owner());
+    suite.trusted_issuers_registry.migrate();
+}